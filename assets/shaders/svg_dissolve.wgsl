@@ -0,0 +1,23 @@
+#import bevy_svg::svg_functions
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> progress: f32;
+
+// Size of the dissolving cells in pixels.
+const CELL_SIZE: f32 = 6.0;
+
+fn hash(cell: vec2<f32>) -> f32 {
+    return fract(sin(dot(cell, vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+@fragment
+fn fragment(in: svg_functions::SvgVertexOutput) -> @location(0) vec4<f32> {
+    let noise = hash(floor(in.position.xy / CELL_SIZE));
+    if noise < progress {
+        discard;
+    }
+    var color = svg_functions::fragment_color(in);
+    // a glowing rim along the dissolving edge
+    let rim = 1.0 - smoothstep(0.0, 0.08, noise - progress);
+    color = vec4<f32>(mix(color.rgb, vec3<f32>(4.0, 1.5, 0.3), rim), color.a);
+    return color;
+}
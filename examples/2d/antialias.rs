@@ -0,0 +1,77 @@
+use bevy::{prelude::*, render::view::Msaa};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_antialias".to_string(),
+                resolution: (800, 450).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .run();
+}
+
+#[derive(Component)]
+struct Rotate;
+
+fn setup(mut commands: Commands, mut svgs: ResMut<Assets<Svg>>, asset_server: Res<AssetServer>) {
+    // Without MSAA, the left wheel has jagged edges, the right one is smoothed by the fringe.
+    commands.spawn((Camera2d, Msaa::Off));
+
+    let center = Vec2::splat(100.0);
+    let mut builder = Svg::builder().size(200.0, 200.0).circle(
+        center,
+        95.0,
+        SvgStyle::fill(Color::srgb(0.2, 0.4, 0.8)).with_stroke(Color::WHITE, 1.5),
+    );
+    for idx in 0..24 {
+        let direction = Vec2::from_angle(idx as f32 * std::f32::consts::TAU / 24.0);
+        builder = builder.polyline(
+            &[center + direction * 15.0, center + direction * 90.0],
+            SvgStyle::stroke(Color::WHITE, 1.0),
+        );
+    }
+    let svg = builder.build();
+    let mut antialiased = svg.clone();
+    antialiased.antialias = true;
+
+    commands.spawn((
+        Svg2d(svgs.add(svg)),
+        Origin::Center,
+        Transform::from_xyz(-150.0, 50.0, 0.0),
+        Rotate,
+    ));
+    commands.spawn((
+        Svg2d(svgs.add(antialiased)),
+        Origin::Center,
+        Transform::from_xyz(150.0, 50.0, 0.0),
+        Rotate,
+    ));
+
+    // Loaded files get the fringe with the loader settings.
+    commands.spawn((
+        Svg2d(
+            asset_server
+                .load_with_settings("asteroid_field.svg", |settings: &mut SvgLoaderSettings| {
+                    settings.antialias = true
+                }),
+        ),
+        Origin::Center,
+        Transform::from_xyz(0.0, -150.0, 0.0),
+    ));
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Rotate>>) {
+    for mut transform in &mut query {
+        transform.rotate_z(0.1 * time.delta_secs());
+    }
+}
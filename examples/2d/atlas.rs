@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_atlas".to_string(),
+                resolution: (800, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    images: Res<Assets<Image>>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+) {
+    let level = asset_server.load("level.svg");
+    let builder = SvgAtlasBuilder::default()
+        .add("box", asset_server.load("box.svg"), UVec2::splat(128))
+        .add_element("player", level.clone(), "player", UVec2::splat(64))
+        .add_element("enemy_1", level.clone(), "enemy_1", UVec2::splat(64))
+        .add_element("enemy_2", level.clone(), "enemy_2", UVec2::splat(64))
+        .add_element("enemy_3", level, "enemy_3", UVec2::splat(64))
+        .with_padding(UVec2::splat(2));
+    // The atlas is built once the SVGs are loaded, and rebuilt when they change, e.g. when they
+    // are edited with the `file_watcher` feature of Bevy enabled.
+    let atlas = SvgAtlas::new(builder, &images, &layouts);
+
+    commands.spawn(Camera2d);
+    // the whole atlas
+    commands.spawn((
+        Sprite::from_image(atlas.image.clone()),
+        Transform::from_xyz(-200.0, 0.0, 0.0),
+    ));
+    // a sprite for every texture in the atlas
+    for (idx, name) in ["box", "player", "enemy_1", "enemy_2", "enemy_3"]
+        .into_iter()
+        .enumerate()
+    {
+        commands.spawn((
+            Sprite::from_atlas_image(
+                atlas.image.clone(),
+                atlas.texture_atlas(name).expect("the name was added"),
+            ),
+            Transform::from_xyz(150.0, 200.0 - idx as f32 * 100.0, 0.0),
+        ));
+    }
+    commands.spawn(atlas);
+}
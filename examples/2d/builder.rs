@@ -0,0 +1,73 @@
+use bevy::{math::Affine2, prelude::*};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_builder".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut svgs: ResMut<Assets<Svg>>) {
+    let outline = Color::srgb(0.1, 0.1, 0.1);
+    let svg = Svg::builder()
+        .name("car")
+        .size(240.0, 140.0)
+        .path(
+            "M20 90 L40 50 Q60 30 100 30 L150 30 Q180 30 200 60 L220 90 Z",
+            SvgStyle::fill(Color::srgb(0.9, 0.3, 0.2)).with_stroke(outline, 4.0),
+        )
+        .expect("valid path data")
+        .rect(
+            Rect::new(10.0, 80.0, 230.0, 105.0),
+            SvgStyle::fill(Color::srgb(0.8, 0.25, 0.15)).with_stroke(outline, 4.0),
+        )
+        .polygon(
+            &[
+                Vec2::new(60.0, 75.0),
+                Vec2::new(72.0, 45.0),
+                Vec2::new(115.0, 45.0),
+                Vec2::new(115.0, 75.0),
+            ],
+            SvgStyle::fill(Color::srgb(0.6, 0.85, 1.0)),
+        )
+        .polyline(
+            &[Vec2::new(5.0, 125.0), Vec2::new(235.0, 125.0)],
+            SvgStyle::stroke(Color::srgb(0.5, 0.5, 0.5), 2.0),
+        )
+        .group(|wheels| {
+            let wheel = SvgStyle::fill(outline).with_stroke(Color::srgb(0.7, 0.7, 0.7), 3.0);
+            wheels.circle(Vec2::new(60.0, 105.0), 18.0, wheel).circle(
+                Vec2::new(180.0, 105.0),
+                18.0,
+                wheel,
+            )
+        })
+        .with_id("wheels")
+        .ellipse(
+            Vec2::new(210.0, 70.0),
+            Vec2::new(8.0, 5.0),
+            SvgStyle::fill(Color::srgb(1.0, 0.9, 0.4)),
+        )
+        .with_id("headlight")
+        .with_transform(Affine2::from_angle_translation(0.3, Vec2::new(25.0, -60.0)))
+        .build();
+
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Svg2d(svgs.add(svg)),
+        Origin::Center,
+        Transform::from_scale(Vec3::splat(2.0)),
+    ));
+}
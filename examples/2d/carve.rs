@@ -0,0 +1,85 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_svg::{geometry::BooleanOp, prelude::*};
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_carve".to_string(),
+                resolution: (800, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, carve)
+        .run();
+}
+
+#[derive(Component)]
+struct Terrain;
+
+fn setup(mut commands: Commands, mut svgs: ResMut<Assets<Svg>>) {
+    let terrain = Svg::builder()
+        .name("terrain")
+        .size(400.0, 300.0)
+        .rect(
+            Rect::new(0.0, 0.0, 400.0, 300.0),
+            SvgStyle::fill(Color::srgb(0.55, 0.75, 0.95)),
+        )
+        .with_id("sky")
+        .path(
+            "M0 180 Q60 120 120 160 T240 150 Q320 100 400 170 V300 H0 Z",
+            SvgStyle::fill(Color::srgb(0.45, 0.3, 0.2))
+                .with_stroke(Color::srgb(0.2, 0.6, 0.2), 6.0),
+        )
+        .expect("valid path data")
+        .with_id("ground")
+        .build();
+
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Svg2d(svgs.add(terrain)),
+        Origin::Center,
+        Transform::from_scale(Vec3::splat(2.0)),
+        Terrain,
+    ));
+}
+
+/// Blows a hole into the ground where the left mouse button is clicked.
+fn carve(
+    buttons: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    terrain: Single<(&Svg2d, &Origin, &Transform), With<Terrain>>,
+    mut svgs: ResMut<Assets<Svg>>,
+) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let (camera, camera_transform) = *camera;
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+    let (Svg2d(handle), origin, transform) = *terrain;
+    let Some(svg) = svgs.get(handle) else {
+        return;
+    };
+
+    let center = svg.world_to_svg(cursor.extend(0.0), *origin, transform);
+    let explosion = Svg::builder()
+        .circle(center, 30.0, SvgStyle::fill(Color::BLACK))
+        .build();
+    // the mesh is tessellated again once the svg is replaced
+    let carved = svg.element_boolean("ground", BooleanOp::Difference, &explosion, None, 0.25);
+    if let Err(error) = svgs.insert(handle, carved) {
+        error!("Failed to carve the terrain: {error}");
+    }
+}
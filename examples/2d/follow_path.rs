@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_follow_path".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let svg = asset_server.load("route.svg");
+    commands.spawn(Camera2d);
+    let route = commands.spawn((Svg2d(svg), Origin::Center)).id();
+
+    // A small arrow which drives along the road and back.
+    commands.spawn((
+        Mesh2d(meshes.add(Triangle2d::new(
+            Vec2::new(12.0, 0.0),
+            Vec2::new(-8.0, 8.0),
+            Vec2::new(-8.0, -8.0),
+        ))),
+        MeshMaterial2d(materials.add(Color::WHITE)),
+        FollowSvgPath::new(route, "road", 120.0)
+            .with_repeat(PathRepeat::PingPong)
+            .aligned(),
+    ));
+    // And a dot which circles the ring.
+    commands.spawn((
+        Mesh2d(meshes.add(Circle::new(6.0))),
+        MeshMaterial2d(materials.add(Color::WHITE)),
+        FollowSvgPath::new(route, "ring", 80.0).with_repeat(PathRepeat::Loop),
+    ));
+}
@@ -0,0 +1,51 @@
+//! Plays a walk cycle whose frames are the groups `frame_1` to `frame_8` of one SVG.
+//!
+//! Press `Space` to pause and resume, and `Left` and `Right` to step through the frames.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_frame_animation".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, control)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("walk_cycle.svg");
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Svg2d(svg),
+        Origin::Center,
+        Transform::from_scale(Vec3::splat(4.0)),
+        SvgFrameAnimation::new(10.0).with_prefix("frame_"),
+    ));
+}
+
+fn control(keyboard_input: Res<ButtonInput<KeyCode>>, mut query: Query<&mut SvgFrameAnimation>) {
+    for mut animation in &mut query {
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            animation.playing = !animation.playing;
+        }
+        let frame = animation.frame();
+        if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+            animation.set_frame(frame + 1);
+        }
+        if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+            animation.set_frame(frame.saturating_sub(1));
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Renders 10 000 coins to compare instanced and individual draws.
+//!
+//! All coins share one `Handle<Svg>`, so they share the mesh and the material and are rendered
+//! with instanced draws, with the transform and `SvgTint` of every coin as per-instance data.
+//! Press `Space` to give every coin its own copy of the `Svg` instead, which needs one draw call
+//! per coin. The number of draw calls is counted from the 2D render phases and logged every
+//! second, compare it and the frame times of both modes.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use bevy::{
+    core_pipeline::core_2d::{AlphaMask2d, Opaque2d, Transparent2d},
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        render_phase::{
+            BinnedPhaseItem, PhaseItem, ViewBinnedRenderPhases, ViewSortedRenderPhases,
+        },
+    },
+    time::common_conditions::on_timer,
+};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+const COLUMNS: usize = 125;
+const ROWS: usize = 80;
+const SPACING: f32 = 12.0;
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "2d_instancing".to_string(),
+            resolution: (1600, 1000).into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }))
+    .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+    .init_resource::<Mode>()
+    .init_resource::<DrawCalls>()
+    .add_systems(Startup, setup)
+    .add_systems(
+        Update,
+        (
+            toggle_mode,
+            spawn_coins,
+            spin,
+            log_draw_calls.run_if(on_timer(Duration::from_secs(1))),
+        ),
+    );
+    let draw_calls = app.world().resource::<DrawCalls>().clone();
+    app.sub_app_mut(RenderApp)
+        .insert_resource(draw_calls)
+        .add_systems(
+            Render,
+            count_draw_calls.in_set(RenderSystems::PrepareBindGroups),
+        );
+    app.run();
+}
+
+#[derive(Default, PartialEq, Resource)]
+enum Mode {
+    #[default]
+    Shared,
+    Unique,
+}
+
+/// The number of draw calls of the last rendered frame, shared with the render world.
+#[derive(Clone, Default, Resource)]
+struct DrawCalls(Arc<AtomicUsize>);
+
+#[derive(Resource)]
+struct CoinSvg(Handle<Svg>);
+
+#[derive(Component)]
+struct Coin;
+
+fn setup(mut commands: Commands, mut svgs: ResMut<Assets<Svg>>) {
+    let svg = Svg::builder()
+        .circle(
+            Vec2::splat(5.0),
+            4.5,
+            SvgStyle::fill(Color::WHITE).with_stroke(Color::srgb(0.6, 0.6, 0.6), 1.0),
+        )
+        .rect(
+            Rect::new(4.0, 2.0, 6.0, 8.0),
+            SvgStyle::fill(Color::srgb(0.8, 0.8, 0.8)),
+        )
+        .build();
+    commands.insert_resource(CoinSvg(svgs.add(svg)));
+    commands.spawn(Camera2d);
+}
+
+fn toggle_mode(keyboard_input: Res<ButtonInput<KeyCode>>, mut mode: ResMut<Mode>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        *mode = match *mode {
+            Mode::Shared => Mode::Unique,
+            Mode::Unique => Mode::Shared,
+        };
+    }
+}
+
+fn spawn_coins(
+    mut commands: Commands,
+    mode: Res<Mode>,
+    coin: Res<CoinSvg>,
+    mut svgs: ResMut<Assets<Svg>>,
+    coins: Query<Entity, With<Coin>>,
+) {
+    if !mode.is_changed() {
+        return;
+    }
+    for entity in &coins {
+        commands.entity(entity).despawn();
+    }
+    let Some(shared) = svgs.get(&coin.0).cloned() else {
+        return;
+    };
+    info!(
+        "{} coins, {}",
+        COLUMNS * ROWS,
+        match *mode {
+            Mode::Shared => "sharing one Svg",
+            Mode::Unique => "each with its own Svg",
+        }
+    );
+
+    let offset = Vec2::new(COLUMNS as f32, ROWS as f32) * SPACING / 2.0;
+    for row in 0..ROWS {
+        for column in 0..COLUMNS {
+            let handle = match *mode {
+                Mode::Shared => coin.0.clone(),
+                // a copy without mesh, which gets its own mesh once it is added
+                Mode::Unique => svgs.add(Svg {
+                    mesh: Handle::default(),
+                    ..shared.clone()
+                }),
+            };
+            let position = Vec2::new(column as f32, row as f32) * SPACING - offset;
+            let hue = (column * 360 / COLUMNS) as f32;
+            let lightness = 0.4 + 0.3 * row as f32 / ROWS as f32;
+            commands.spawn((
+                Coin,
+                Svg2d(handle),
+                Origin::Center,
+                SvgTint(Color::hsl(hue, 0.8, lightness)),
+                Transform::from_translation(position.extend(0.0)),
+            ));
+        }
+    }
+}
+
+fn spin(time: Res<Time>, mut coins: Query<&mut Transform, With<Coin>>) {
+    for mut transform in &mut coins {
+        let phase = transform.translation.x * 0.01 + transform.translation.y * 0.02;
+        transform.scale.x = (time.elapsed_secs() * 2.0 + phase).sin();
+    }
+}
+
+fn log_draw_calls(mode: Res<Mode>, draw_calls: Res<DrawCalls>) {
+    let mode = match *mode {
+        Mode::Shared => "shared",
+        Mode::Unique => "unique",
+    };
+    info!(
+        "{mode}: {} draw calls",
+        draw_calls.0.load(Ordering::Relaxed)
+    );
+}
+
+/// Counts the draw calls of the 2D phases after they were batched, like they are rendered: a
+/// batch of a sorted phase is one draw, a bin of a binned phase one instanced draw and every
+/// unbatchable entity one draw.
+fn count_draw_calls(
+    opaque: Res<ViewBinnedRenderPhases<Opaque2d>>,
+    alpha_mask: Res<ViewBinnedRenderPhases<AlphaMask2d>>,
+    transparent: Res<ViewSortedRenderPhases<Transparent2d>>,
+    draw_calls: Res<DrawCalls>,
+) {
+    let sorted = transparent
+        .values()
+        .flat_map(|phase| &phase.items)
+        .filter(|item| !item.batch_range().is_empty())
+        .count();
+    let count = binned_draw_calls(&opaque) + binned_draw_calls(&alpha_mask) + sorted;
+    draw_calls.0.store(count, Ordering::Relaxed);
+}
+
+fn binned_draw_calls<BPI: BinnedPhaseItem>(phases: &ViewBinnedRenderPhases<BPI>) -> usize {
+    phases
+        .values()
+        .map(|phase| {
+            phase.multidrawable_meshes.len()
+                + phase.batchable_meshes.len()
+                + phase
+                    .unbatchable_meshes
+                    .values()
+                    .map(|bin| bin.entities.len())
+                    .sum::<usize>()
+                + phase
+                    .non_mesh_items
+                    .values()
+                    .map(|bin| bin.entities.len())
+                    .sum::<usize>()
+        })
+        .sum()
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_level_spawn".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut spawners: ResMut<SvgSpawners>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // Every goblin in the level gets a small yellow ring on top of its marker.
+    let goblin = (
+        meshes.add(Annulus::new(12.0, 16.0)),
+        materials.add(Color::srgb(1.0, 0.85, 0.0)),
+    );
+    spawners
+        .add("circle[data-kind=goblin]", move |commands, ctx| {
+            if let Some(position) = ctx.world_position {
+                commands.spawn((
+                    Mesh2d(goblin.0.clone()),
+                    MeshMaterial2d(goblin.1.clone()),
+                    Transform::from_translation(position.with_z(1.0)),
+                ));
+            }
+        })
+        .add("#player", |_, ctx| {
+            info!("player starts at {:?}", ctx.world_position);
+        });
+
+    commands.spawn(Camera2d);
+    commands.spawn((Svg2d(asset_server.load("level.svg")), Origin::Center));
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_lod".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, log_tolerance)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("neutron_star.svg");
+    commands.spawn(Camera2d);
+    // Zoom with the mouse wheel, the mesh gets finer when zooming in and coarser when zooming out.
+    commands.spawn((
+        Svg2d(svg),
+        Origin::Center,
+        SvgLod::default().with_pixel_tolerance(0.5),
+    ));
+}
+
+fn log_tolerance(query: Query<&SvgLod, Changed<SvgLod>>, mut last: Local<Option<f32>>) {
+    for lod in &query {
+        if lod.tolerance() != *last {
+            *last = lod.tolerance();
+            info!("tessellation tolerance: {:?}", lod.tolerance());
+        }
+    }
+}
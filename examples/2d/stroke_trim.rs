@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "2d_stroke_trim".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, animate_trim)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let svg = asset_server.load("route.svg");
+    commands.spawn(Camera2d);
+    commands.spawn((
+        Svg2d(svg),
+        Origin::Center,
+        StrokeTrim::new(TrimRange::FULL)
+            .with_element("trail", TrimRange::new(0.0, 0.0))
+            .with_element("ring", TrimRange::new(0.0, 0.0)),
+    ));
+}
+
+fn animate_trim(time: Res<Time>, mut query: Query<&mut StrokeTrim>) {
+    let progress = (time.elapsed_secs() * 0.25).fract();
+    for mut trim in &mut query {
+        // The trail draws itself along the road, while a dash runs around the ring.
+        trim.elements
+            .insert("trail".to_string(), TrimRange::new(0.0, progress));
+        trim.elements.insert(
+            "ring".to_string(),
+            TrimRange::new(0.0, 0.25).with_offset(progress),
+        );
+    }
+}
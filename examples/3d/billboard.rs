@@ -0,0 +1,75 @@
+//! Markers above a grid of units, which turn towards the orbiting camera in the vertex shader.
+//! The left half are spherical billboards, the right half only rotate around their y-axis.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_billboard".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, orbit_camera)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 8.0, 20.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let unit = meshes.add(Cuboid::new(0.8, 0.8, 0.8));
+    let unit_material = materials.add(Color::srgb(0.4, 0.5, 0.6));
+    let marker = asset_server.load("twinkle.svg");
+    for x in -6..6 {
+        for z in -6..6 {
+            let position = Vec3::new(x as f32 + 0.5, 0.4, z as f32 + 0.5) * 1.5;
+            commands.spawn((
+                Mesh3d(unit.clone()),
+                MeshMaterial3d(unit_material.clone()),
+                Transform::from_translation(position),
+            ));
+            // the pivot is the bottom center of the marker, just above the unit
+            commands.spawn((
+                Svg3d(marker.clone()),
+                if x < 0 {
+                    SvgBillboard::Spherical
+                } else {
+                    SvgBillboard::Cylindrical
+                },
+                Origin::Custom((0.5, 1.0)),
+                Transform::from_translation(position + Vec3::Y * 0.6)
+                    .with_scale(Vec3::splat(0.002)),
+            ));
+        }
+    }
+}
+
+fn orbit_camera(time: Res<Time>, mut query: Query<&mut Transform, With<Camera3d>>) {
+    for mut transform in &mut query {
+        let angle = time.elapsed_secs() * 0.3;
+        let height = 8.0 + (time.elapsed_secs() * 0.5).sin() * 6.0;
+        *transform = Transform::from_xyz(angle.sin() * 20.0, height, angle.cos() * 20.0)
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
@@ -0,0 +1,96 @@
+//! Renders an `Svg3d` with a custom dissolve material, which uses the vertex shader of the SVG
+//! material and its own fragment shader.
+
+use bevy::{
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{MaterialPipeline, MaterialPipelineKey},
+    prelude::*,
+    render::render_resource::{
+        AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+    },
+    shader::ShaderRef,
+};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_custom_material".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((
+            common::CommonPlugin,
+            bevy_svg::prelude::SvgPlugin,
+            MaterialPlugin::<DissolveMaterial>::default(),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, dissolve)
+        .run();
+}
+
+/// Discards the SVG in random cells, more of them the higher the `progress`.
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+struct DissolveMaterial {
+    #[uniform(0)]
+    progress: f32,
+}
+
+impl Material for DissolveMaterial {
+    fn vertex_shader() -> ShaderRef {
+        SVG_3D_SHADER_HANDLE.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/svg_dissolve.wgsl".into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.primitive.cull_mode = None;
+        specialize_svg_pipeline(descriptor, layout)
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<DissolveMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(5.0, 8.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Svg3d(asset_server.load("twinkle.svg")),
+        SvgMaterial3d(materials.add(DissolveMaterial { progress: 0.0 })),
+        Origin::Center,
+        Transform {
+            translation: Vec3::new(0.0, 0.0, -1.0),
+            scale: Vec3::new(0.01, 0.01, 1.0),
+            rotation: Quat::from_rotation_x(-std::f32::consts::PI / 5.0),
+        },
+    ));
+}
+
+fn dissolve(
+    time: Res<Time>,
+    query: Query<&SvgMaterial3d<DissolveMaterial>>,
+    mut materials: ResMut<Assets<DissolveMaterial>>,
+) {
+    for material in &query {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.progress = (time.elapsed_secs() * 0.5).sin() * 0.5 + 0.5;
+        }
+    }
+}
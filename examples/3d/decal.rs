@@ -0,0 +1,84 @@
+//! Projects SVGs onto a floor and a box: a clustered decal, which needs bindless textures, and
+//! forward decals, which need a depth prepass.
+
+use bevy::{core_pipeline::prepass::DepthPrepass, prelude::*};
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_decal".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate_decal)
+        .run();
+}
+
+#[derive(Component)]
+struct Rotating;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        DepthPrepass,
+        Msaa::Off,
+        Transform::from_xyz(0.0, 5.0, 8.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(10.0, 10.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.5, 0.5, 0.5))),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.5, 1.5, 1.5))),
+        MeshMaterial3d(materials.add(Color::srgb(0.6, 0.4, 0.3))),
+        Transform::from_xyz(1.5, 0.75, 0.0),
+    ));
+
+    // projected straight down onto the floor, rotating around the y-axis
+    commands.spawn((
+        SvgDecal::new(asset_server.load("twinkle.svg")).with_resolution(UVec2::new(1052, 1014)),
+        Transform::from_xyz(-2.0, 0.0, 0.0)
+            .looking_to(Vec3::NEG_Y, Vec3::Z)
+            .with_scale(Vec3::new(2.6, 2.5, 1.0)),
+        Rotating,
+    ));
+    // forward decals on the top and the front of the box
+    let logo = SvgDecal::new(asset_server.load("box.svg"))
+        .with_mode(SvgDecalMode::Forward { depth_fade: 0.2 });
+    commands.spawn((
+        logo.clone(),
+        Transform::from_xyz(1.5, 1.5, 0.0)
+            .looking_to(Vec3::NEG_Y, Vec3::NEG_Z)
+            .with_scale(Vec3::splat(1.2)),
+    ));
+    commands.spawn((
+        logo,
+        Transform::from_xyz(1.5, 0.75, 0.75)
+            .looking_to(Vec3::NEG_Z, Vec3::Y)
+            .with_scale(Vec3::splat(1.2)),
+    ));
+}
+
+fn rotate_decal(time: Res<Time>, mut query: Query<&mut Transform, With<Rotating>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_secs() * 0.5);
+    }
+}
@@ -0,0 +1,80 @@
+//! Extrudes an SVG into a beveled solid which is lit with a `StandardMaterial`.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_extrude".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (spawn_solid, spin))
+        .run();
+}
+
+#[derive(Resource)]
+struct Logo(Handle<Svg>);
+
+#[derive(Component)]
+struct Solid;
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Logo(asset_server.load("twinkle.svg")));
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        Transform::from_xyz(4.0, 6.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+/// Spawns the solid once the SVG is loaded.
+fn spawn_solid(
+    mut commands: Commands,
+    logo: Res<Logo>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spawned: Local<bool>,
+) {
+    let Some(svg) = svgs.get(&logo.0).filter(|_| !*spawned) else {
+        return;
+    };
+    *spawned = true;
+    let extrusion = SvgExtrusion::new(40.0).with_bevel(0.3, 4.0, 4);
+    // the mesh starts at the top left corner of the SVG, move its center to the origin
+    let mesh =
+        svg.extrude(&extrusion)
+            .translated_by(Vec3::new(-svg.size.x / 2.0, svg.size.y / 2.0, 0.0));
+    commands.spawn((
+        Solid,
+        Mesh3d(meshes.add(mesh)),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            perceptual_roughness: 0.4,
+            metallic: 0.2,
+            ..Default::default()
+        })),
+        Transform::from_scale(Vec3::splat(0.01)),
+    ));
+}
+
+fn spin(time: Res<Time>, mut query: Query<&mut Transform, With<Solid>>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_secs() * 0.5);
+    }
+}
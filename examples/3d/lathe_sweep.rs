@@ -0,0 +1,79 @@
+//! Revolves an SVG profile into a vase and sweeps a circle along an SVG path into a pipe.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_lathe_sweep".to_string(),
+                resolution: (800, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, spawn_meshes)
+        .run();
+}
+
+#[derive(Resource)]
+struct Profiles(Handle<Svg>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Profiles(asset_server.load("profiles.svg")));
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 4.0, 7.0).looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        Transform::from_xyz(3.0, 6.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+/// Spawns the meshes once the SVG is loaded.
+fn spawn_meshes(
+    mut commands: Commands,
+    profiles: Res<Profiles>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut spawned: Local<bool>,
+) {
+    let Some(svg) = svgs.get(&profiles.0).filter(|_| !*spawned) else {
+        return;
+    };
+    *spawned = true;
+    let material = materials.add(StandardMaterial {
+        perceptual_roughness: 0.3,
+        ..Default::default()
+    });
+
+    // the profile starts at the rim, which is at `y = 0`, so move the bottom onto the ground
+    if let Some(vase) = svg.lathe("vase", &SvgLathe::new(0.0).with_segments(48)) {
+        commands.spawn((
+            Mesh3d(meshes.add(vase)),
+            MeshMaterial3d(material.clone()),
+            Transform::from_xyz(-1.5, 1.4, 0.0).with_scale(Vec3::splat(0.01)),
+        ));
+    }
+    // the track is drawn from above, rotate it so it lies on the ground
+    if let Some(pipe) = svg.sweep("pipe", "track", &SvgSweep::default().with_segments(256)) {
+        commands.spawn((
+            Mesh3d(meshes.add(pipe)),
+            MeshMaterial3d(material),
+            Transform::from_xyz(-2.5, 0.04, -1.5)
+                .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2))
+                .with_scale(Vec3::splat(0.01)),
+        ));
+    }
+}
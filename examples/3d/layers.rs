@@ -0,0 +1,55 @@
+//! Overlapping paths stay in paint order when seen at a grazing angle, and with a layer spacing
+//! the same SVG is shown as an exploded view of its paths.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_layers".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, orbit_camera)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 2.0, 12.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    // all paths in one plane, kept in order by the depth bias of the SVG material
+    commands.spawn((
+        Svg3d(asset_server.load("twinkle.svg")),
+        Origin::Center,
+        Transform::from_xyz(-2.5, 0.0, 0.0).with_scale(Vec3::new(0.01, 0.01, 1.0)),
+    ));
+    // every path 0.3 units in front of the path below it
+    commands.spawn((
+        Svg3d(
+            asset_server.load_with_settings("twinkle.svg", |settings: &mut SvgLoaderSettings| {
+                settings.layer_spacing = 0.3
+            }),
+        ),
+        Origin::Center,
+        Transform::from_xyz(2.5, 0.0, 0.0).with_scale(Vec3::new(0.01, 0.01, 1.0)),
+    ));
+}
+
+fn orbit_camera(time: Res<Time>, mut query: Query<&mut Transform, With<Camera3d>>) {
+    for mut transform in &mut query {
+        let angle = (time.elapsed_secs() * 0.4).sin() * 1.4;
+        *transform = Transform::from_xyz(angle.sin() * 12.0, 2.0, angle.cos() * 12.0)
+            .looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}
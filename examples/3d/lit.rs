@@ -0,0 +1,66 @@
+//! Renders an `Svg3d` with a `StandardMaterial`, so it is lit by a moving point light and casts
+//! a shadow onto the ground.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+#[path = "../common/lib.rs"]
+mod common;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "3d_lit".to_string(),
+                resolution: (600, 600).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }))
+        .add_plugins((common::CommonPlugin, bevy_svg::prelude::SvgPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, move_light)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 3.0, 8.0).looking_at(Vec3::new(0.0, 1.5, 0.0), Vec3::Y),
+    ));
+    commands.spawn((
+        Svg3d(asset_server.load("twinkle.svg")),
+        SvgLit(materials.add(StandardMaterial {
+            perceptual_roughness: 0.5,
+            double_sided: true,
+            cull_mode: None,
+            ..Default::default()
+        })),
+        Origin::Custom((0.5, 1.0)),
+        Transform::from_scale(Vec3::new(0.006, 0.006, 1.0)),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(10.0, 10.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.35, 0.3))),
+    ));
+    commands.spawn((
+        PointLight {
+            intensity: 2_000_000.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        Transform::from_xyz(0.0, 3.0, 3.0),
+    ));
+}
+
+fn move_light(time: Res<Time>, mut query: Query<&mut Transform, With<PointLight>>) {
+    for mut transform in &mut query {
+        let angle = time.elapsed_secs() * 0.8;
+        transform.translation = Vec3::new(angle.sin() * 3.0, 3.0, angle.cos().abs() * 3.0 + 0.5);
+    }
+}
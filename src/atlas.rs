@@ -0,0 +1,269 @@
+//! Rasterizes many [`Svg`]s into a single texture atlas.
+
+use bevy::{
+    asset::{AssetEvent, Assets, Handle},
+    ecs::{
+        component::Component,
+        message::MessageReader,
+        system::{Query, Res, ResMut},
+    },
+    image::{
+        Image, TextureAtlas, TextureAtlasBuilder, TextureAtlasBuilderError, TextureAtlasLayout,
+    },
+    log::error,
+    math::UVec2,
+    platform::collections::{HashMap, HashSet},
+};
+use thiserror::Error;
+
+use crate::svg::Svg;
+
+/// An [`Svg`], or an element of it, which is rasterized into an atlas.
+#[derive(Clone, Debug)]
+struct AtlasEntry {
+    name: String,
+    svg: Handle<Svg>,
+    id: Option<String>,
+    size: UVec2,
+}
+
+/// Rasterizes [`Svg`]s, or elements of them, and packs them into one [`Image`] with a
+/// [`TextureAtlasLayout`].
+///
+/// Every entry has a name, the index of its texture in the layout is the order in which the
+/// entries were added, see [`index`](Self::index). To keep an atlas up to date when its
+/// [`Svg`]s are loaded or changed, e.g. by hot reloading, use an [`SvgAtlas`].
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_svg::prelude::*;
+/// fn setup(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+///     images: Res<Assets<Image>>,
+///     layouts: Res<Assets<TextureAtlasLayout>>,
+/// ) {
+///     let builder = SvgAtlasBuilder::default()
+///         .add("box", asset_server.load("box.svg"), UVec2::splat(64))
+///         .add_element("player", asset_server.load("level.svg"), "player", UVec2::splat(32));
+///     let atlas = SvgAtlas::new(builder, &images, &layouts);
+///     commands.spawn(Sprite::from_atlas_image(
+///         atlas.image.clone(),
+///         atlas.texture_atlas("player").unwrap(),
+///     ));
+///     commands.spawn(atlas);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SvgAtlasBuilder {
+    entries: Vec<AtlasEntry>,
+    padding: UVec2,
+    max_size: UVec2,
+}
+
+impl Default for SvgAtlasBuilder {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            padding: UVec2::ONE,
+            max_size: UVec2::splat(4096),
+        }
+    }
+}
+
+impl SvgAtlasBuilder {
+    /// Adds a whole [`Svg`], rasterized with the given size in pixels.
+    pub fn add(mut self, name: impl Into<String>, svg: Handle<Svg>, size: UVec2) -> Self {
+        self.entries.push(AtlasEntry {
+            name: name.into(),
+            svg,
+            id: None,
+            size,
+        });
+        self
+    }
+
+    /// Adds the element with the given `id`, or all elements in a group with that `id`, cropped
+    /// to their bounds and rasterized with the given size in pixels.
+    pub fn add_element(
+        mut self,
+        name: impl Into<String>,
+        svg: Handle<Svg>,
+        id: impl Into<String>,
+        size: UVec2,
+    ) -> Self {
+        self.entries.push(AtlasEntry {
+            name: name.into(),
+            svg,
+            id: Some(id.into()),
+            size,
+        });
+        self
+    }
+
+    /// Sets the empty space in pixels to the right of and below every texture, one pixel by
+    /// default, so that textures don't bleed into each other when they are filtered.
+    pub fn with_padding(mut self, padding: UVec2) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the maximum size of the atlas in pixels, `4096x4096` by default.
+    pub fn with_max_size(mut self, max_size: UVec2) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Returns the index of the texture with the given name in the [`TextureAtlasLayout`].
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.name == name)
+    }
+
+    /// Rasterizes all entries and packs them into an atlas.
+    ///
+    /// Returns the layout, the index of every name and the texture of the atlas, like
+    /// [`TextureAtlasBuilder::build`].
+    pub fn build(
+        &self,
+        svgs: &Assets<Svg>,
+    ) -> Result<(TextureAtlasLayout, HashMap<String, usize>, Image), SvgAtlasError> {
+        let images = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let svg = svgs
+                    .get(&entry.svg)
+                    .ok_or_else(|| SvgAtlasError::NotLoaded(entry.name.clone()))?;
+                match &entry.id {
+                    Some(id) => svg.rasterize_element(id, entry.size).ok_or_else(|| {
+                        SvgAtlasError::MissingElement {
+                            name: entry.name.clone(),
+                            id: id.clone(),
+                        }
+                    }),
+                    None => Ok(svg.rasterize(entry.size)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut builder = TextureAtlasBuilder::default();
+        builder.padding(self.padding).max_size(self.max_size);
+        for image in &images {
+            builder.add_texture(None, image);
+        }
+        let (layout, _, image) = builder.build()?;
+        let indices = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| (entry.name.clone(), idx))
+            .collect();
+        Ok((layout, indices, image))
+    }
+}
+
+/// An error that occurs when building an atlas with an [`SvgAtlasBuilder`].
+#[derive(Error, Debug)]
+pub enum SvgAtlasError {
+    #[error("the SVG of `{0}` is not loaded")]
+    NotLoaded(String),
+    #[error("the SVG of `{name}` has no element with the id `{id}`")]
+    MissingElement { name: String, id: String },
+    #[error("failed to pack the atlas: {0}")]
+    Packing(#[from] TextureAtlasBuilderError),
+}
+
+/// A texture atlas which is built once all of its [`Svg`]s are loaded, and rebuilt whenever one
+/// of them changes.
+///
+/// The handles of the [`Image`] and [`TextureAtlasLayout`] are reserved up front, so sprites
+/// can use them right away. They show the atlas once it is built, and the new atlas after a
+/// rebuild.
+#[derive(Component, Debug)]
+pub struct SvgAtlas {
+    builder: SvgAtlasBuilder,
+    /// The texture of the atlas.
+    pub image: Handle<Image>,
+    /// The layout of the atlas.
+    pub layout: Handle<TextureAtlasLayout>,
+    dirty: bool,
+}
+
+impl SvgAtlas {
+    /// Creates an atlas from the builder, it is built once all [`Svg`]s are loaded.
+    pub fn new(
+        builder: SvgAtlasBuilder,
+        images: &Assets<Image>,
+        layouts: &Assets<TextureAtlasLayout>,
+    ) -> Self {
+        Self {
+            builder,
+            image: images.reserve_handle(),
+            layout: layouts.reserve_handle(),
+            dirty: true,
+        }
+    }
+
+    /// Returns the index of the texture with the given name, see [`SvgAtlasBuilder::index`].
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.builder.index(name)
+    }
+
+    /// Returns the [`TextureAtlas`] for the texture with the given name, e.g. for a `Sprite`.
+    pub fn texture_atlas(&self, name: &str) -> Option<TextureAtlas> {
+        Some(TextureAtlas {
+            layout: self.layout.clone(),
+            index: self.index(name)?,
+        })
+    }
+}
+
+/// Builds every [`SvgAtlas`] whose [`Svg`]s are loaded for the first time or changed.
+pub fn update_svg_atlases(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut atlases: Query<&mut SvgAtlas>,
+) {
+    let changed = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for mut atlas in &mut atlases {
+        if atlas
+            .builder
+            .entries
+            .iter()
+            .any(|entry| changed.contains(&entry.svg.id()))
+        {
+            atlas.dirty = true;
+        }
+        // wait until all svgs are loaded
+        let is_loaded = || {
+            atlas
+                .builder
+                .entries
+                .iter()
+                .all(|entry| svgs.contains(&entry.svg))
+        };
+        if !atlas.dirty || !is_loaded() {
+            continue;
+        }
+        atlas.dirty = false;
+        match atlas.builder.build(&svgs) {
+            Ok((layout, _, image)) => {
+                if images.insert(&atlas.image, image).is_err()
+                    || layouts.insert(&atlas.layout, layout).is_err()
+                {
+                    error!("Failed to insert an SVG atlas");
+                }
+            }
+            Err(err) => error!("Failed to build an SVG atlas: {err}"),
+        }
+    }
+}
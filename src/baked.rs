@@ -0,0 +1,669 @@
+//! A binary format for pre-tessellated [`Svg`]s, created by the asset processor.
+//!
+//! Loading a baked [`Svg`] neither parses the SVG file nor tessellates it, the mesh and all
+//! paths are read as they are.
+
+use bevy::{
+    asset::{
+        AssetLoader, AsyncWriteExt, LoadContext, RenderAssetUsages,
+        io::{Reader, Writer},
+        processor::LoadTransformAndSave,
+        saver::{AssetSaver, SavedAsset},
+        transformer::IdentityAssetTransformer,
+    },
+    color::{Color, ColorToComponents, Srgba},
+    log::debug,
+    math::{Rect, Vec2},
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    platform::collections::HashMap,
+    reflect::TypePath,
+    render::render_resource::PrimitiveTopology,
+    tasks::ConditionalSendFuture,
+};
+use lyon_path::{PathEvent, math::Point};
+use lyon_tessellation::{FillRule, LineCap, LineJoin, StrokeOptions};
+use svgtypes::ViewBox;
+use thiserror::Error;
+
+use crate::{
+    element::SvgElement,
+    loader::SvgAssetLoader,
+    render::{antialias, vertex_buffer},
+    svg::{DrawType, PathDescriptor, Svg, SvgFrame},
+};
+
+const MAGIC: &[u8; 4] = b"SVGB";
+const VERSION: u16 = 5;
+
+/// Processes SVG files into the baked format of [`BakedSvgSaver`].
+pub type SvgBakeProcessor =
+    LoadTransformAndSave<SvgAssetLoader, IdentityAssetTransformer<Svg>, BakedSvgSaver>;
+
+/// Saves an [`Svg`] with its mesh in a compact binary format, which is loaded by
+/// [`BakedSvgLoader`].
+#[derive(Default, TypePath)]
+pub struct BakedSvgSaver;
+
+impl AssetSaver for BakedSvgSaver {
+    type Asset = Svg;
+    type Settings = ();
+    type OutputLoader = BakedSvgLoader;
+    type Error = BakedSvgError;
+
+    fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _settings: &(),
+    ) -> impl ConditionalSendFuture<Output = Result<(), Self::Error>> {
+        Box::pin(async move {
+            debug!("Baking SVG: {} ...", asset.name);
+            let mesh = asset
+                .get_labeled::<Mesh, _>("mesh")
+                .ok_or(BakedSvgError::MissingMesh)?;
+            let bytes = encode(&asset, &mesh)?;
+            writer.write_all(&bytes).await?;
+            debug!("Baking SVG: {} ... Done", asset.name);
+            Ok(())
+        })
+    }
+}
+
+/// Loads [`Svg`]s which were saved by [`BakedSvgSaver`].
+#[derive(Default, TypePath)]
+pub struct BakedSvgLoader;
+
+impl AssetLoader for BakedSvgLoader {
+    type Asset = Svg;
+    type Settings = ();
+    type Error = BakedSvgError;
+
+    fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            debug!("Loading baked SVG: {} ...", load_context.path());
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let (mut svg, mesh) = decode(&bytes)?;
+            for frame in &mut svg.frames {
+                let frame_mesh = frame.extract_mesh(&mesh);
+                frame.mesh =
+                    load_context.add_labeled_asset(format!("frame/{}", frame.id), frame_mesh);
+            }
+            svg.mesh = load_context.add_labeled_asset("mesh".to_string(), mesh);
+            debug!("Loading baked SVG: {} ... Done", load_context.path());
+            Ok(svg)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["svgb"]
+    }
+}
+
+/// An error that occurs when baking or loading a baked [`Svg`].
+#[derive(Error, Debug)]
+pub enum BakedSvgError {
+    #[error("could not read or write file: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("the SVG has no mesh")]
+    MissingMesh,
+    #[error("the mesh has no {0} attribute")]
+    MissingAttribute(&'static str),
+    #[error("not a baked SVG")]
+    InvalidMagic,
+    #[error("unsupported version {0}, expected {VERSION}")]
+    UnsupportedVersion(u16),
+    #[error("unexpected end of data")]
+    UnexpectedEnd,
+    #[error("invalid data: {0}")]
+    InvalidData(&'static str),
+}
+
+impl Svg {
+    /// Encodes the [`Svg`] together with its tessellated `mesh` in the format written by
+    /// [`BakedSvgSaver`].
+    pub fn to_baked_bytes(&self, mesh: &Mesh) -> Result<Vec<u8>, BakedSvgError> {
+        encode(self, mesh)
+    }
+
+    /// Decodes an [`Svg`] and its mesh from the format written by [`BakedSvgSaver`].
+    ///
+    /// The [`Svg::mesh`] and the meshes of the [`Svg::frames`] are not set, unlike when the
+    /// [`Svg`] is loaded with the [`BakedSvgLoader`].
+    pub fn from_baked_bytes(bytes: &[u8]) -> Result<(Svg, Mesh), BakedSvgError> {
+        decode(bytes)
+    }
+}
+
+fn encode(svg: &Svg, mesh: &Mesh) -> Result<Vec<u8>, BakedSvgError> {
+    let mut out = Encoder::default();
+    out.bytes.extend_from_slice(MAGIC);
+    out.u16(VERSION);
+    out.str(&svg.name);
+    out.f32(svg.size.x);
+    out.f32(svg.size.y);
+    for value in [
+        svg.view_box.x,
+        svg.view_box.y,
+        svg.view_box.w,
+        svg.view_box.h,
+    ] {
+        out.f64(value);
+    }
+    out.u8(svg.antialias as u8);
+    out.f32(svg.layer_spacing);
+
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return Err(BakedSvgError::MissingAttribute("position"));
+    };
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        return Err(BakedSvgError::MissingAttribute("color"));
+    };
+    let Some(VertexAttributeValues::Float32(layers)) =
+        mesh.attribute(vertex_buffer::ATTRIBUTE_LAYER)
+    else {
+        return Err(BakedSvgError::MissingAttribute("layer"));
+    };
+    out.len(positions.len());
+    for ((position, color), layer) in positions.iter().zip(colors).zip(layers) {
+        position
+            .iter()
+            .chain(color)
+            .chain([layer])
+            .for_each(|v| out.f32(*v));
+    }
+    match (
+        mesh.attribute(antialias::ATTRIBUTE_AA_NORMAL),
+        mesh.attribute(antialias::ATTRIBUTE_COVERAGE),
+    ) {
+        (
+            Some(VertexAttributeValues::Float32x2(normals)),
+            Some(VertexAttributeValues::Float32(coverages)),
+        ) => {
+            out.u8(1);
+            for (normal, coverage) in normals.iter().zip(coverages) {
+                normal.iter().chain([coverage]).for_each(|v| out.f32(*v));
+            }
+        }
+        _ => out.u8(0),
+    }
+    let indices = mesh
+        .indices()
+        .map(|indices| indices.iter().map(|i| i as u32).collect::<Vec<_>>())
+        .unwrap_or_default();
+    out.len(indices.len());
+    indices.iter().for_each(|i| out.u32(*i));
+
+    out.len(svg.paths.len());
+    for path in &svg.paths {
+        encode_path(&mut out, path);
+    }
+
+    out.len(svg.elements.len());
+    for element in &svg.elements {
+        out.str(&element.id);
+        out.str(&element.tag);
+        out.len(element.classes.len());
+        element.classes.iter().for_each(|class| out.str(class));
+        let mut data = element.data.iter().collect::<Vec<_>>();
+        data.sort();
+        out.len(data.len());
+        for (key, value) in data {
+            out.str(key);
+            out.str(value);
+        }
+        match element.bounds {
+            Some(bounds) => {
+                out.u8(1);
+                [bounds.min, bounds.max].iter().for_each(|v| out.vec2(*v));
+            }
+            None => out.u8(0),
+        }
+    }
+
+    out.len(svg.frames.len());
+    for frame in &svg.frames {
+        out.str(&frame.id);
+        for value in [
+            frame.vertices.start,
+            frame.vertices.end,
+            frame.indices.start,
+            frame.indices.end,
+        ] {
+            out.u32(value);
+        }
+    }
+    Ok(out.bytes)
+}
+
+fn encode_path(out: &mut Encoder, path: &PathDescriptor) {
+    out.str(&path.id);
+    out.len(path.ancestor_ids.len());
+    path.ancestor_ids.iter().for_each(|id| out.str(id));
+    Srgba::from(path.color)
+        .to_f32_array()
+        .iter()
+        .for_each(|v| out.f32(*v));
+    out.u8(path.is_stroke as u8);
+    let t = path.abs_transform;
+    [t.sx, t.ky, t.kx, t.sy, t.tx, t.ty]
+        .iter()
+        .for_each(|v| out.f32(*v));
+    match path.draw_type {
+        DrawType::Fill(fill_rule) => {
+            out.u8(0);
+            out.u8(match fill_rule {
+                FillRule::EvenOdd => 0,
+                FillRule::NonZero => 1,
+            });
+        }
+        DrawType::Stroke(options) => {
+            out.u8(1);
+            out.f32(options.tolerance);
+            out.f32(options.line_width);
+            out.f32(options.miter_limit);
+            out.u8(line_cap_to_u8(options.start_cap));
+            out.u8(line_cap_to_u8(options.end_cap));
+            out.u8(match options.line_join {
+                LineJoin::Miter => 0,
+                LineJoin::MiterClip => 1,
+                LineJoin::Round => 2,
+                LineJoin::Bevel => 3,
+            });
+        }
+    }
+
+    out.len(path.segments.len());
+    let point = |out: &mut Encoder, point: Point| out.vec2(Vec2::new(point.x, point.y));
+    for event in &path.segments {
+        match *event {
+            PathEvent::Begin { at } => {
+                out.u8(0);
+                point(out, at);
+            }
+            PathEvent::Line { from, to } => {
+                out.u8(1);
+                [from, to].into_iter().for_each(|p| point(out, p));
+            }
+            PathEvent::Quadratic { from, ctrl, to } => {
+                out.u8(2);
+                [from, ctrl, to].into_iter().for_each(|p| point(out, p));
+            }
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                out.u8(3);
+                [from, ctrl1, ctrl2, to]
+                    .into_iter()
+                    .for_each(|p| point(out, p));
+            }
+            PathEvent::End { last, first, close } => {
+                out.u8(4);
+                [last, first].into_iter().for_each(|p| point(out, p));
+                out.u8(close as u8);
+            }
+        }
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<(Svg, Mesh), BakedSvgError> {
+    let mut input = Decoder { bytes };
+    if input.take(4)? != MAGIC {
+        return Err(BakedSvgError::InvalidMagic);
+    }
+    let version = input.u16()?;
+    if version != VERSION {
+        return Err(BakedSvgError::UnsupportedVersion(version));
+    }
+    let name = input.str()?;
+    let size = input.vec2()?;
+    let view_box = ViewBox {
+        x: input.f64()?,
+        y: input.f64()?,
+        w: input.f64()?,
+        h: input.f64()?,
+    };
+    let antialias = input.u8()? != 0;
+    let layer_spacing = input.f32()?;
+
+    let vertex_count = input.len()?;
+    let mut positions = Vec::with_capacity(vertex_count);
+    let mut colors = Vec::with_capacity(vertex_count);
+    let mut layers = Vec::with_capacity(vertex_count);
+    for _ in 0..vertex_count {
+        positions.push([input.f32()?, input.f32()?, input.f32()?]);
+        colors.push([input.f32()?, input.f32()?, input.f32()?, input.f32()?]);
+        layers.push(input.f32()?);
+    }
+    let fringe = match input.u8()? {
+        0 => None,
+        _ => {
+            let mut normals = Vec::with_capacity(vertex_count);
+            let mut coverages = Vec::with_capacity(vertex_count);
+            for _ in 0..vertex_count {
+                normals.push([input.f32()?, input.f32()?]);
+                coverages.push(input.f32()?);
+            }
+            Some((normals, coverages))
+        }
+    };
+    let index_count = input.len()?;
+    let indices = (0..index_count)
+        .map(|_| input.u32())
+        .collect::<Result<Vec<_>, _>>()?;
+    if index_count % 3 != 0 {
+        return Err(BakedSvgError::InvalidData("index count"));
+    }
+    if indices.iter().any(|&index| index as usize >= vertex_count) {
+        return Err(BakedSvgError::InvalidData("index out of bounds"));
+    }
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.insert_attribute(vertex_buffer::ATTRIBUTE_LAYER, layers);
+    if let Some((normals, coverages)) = fringe {
+        mesh.insert_attribute(antialias::ATTRIBUTE_AA_NORMAL, normals);
+        mesh.insert_attribute(antialias::ATTRIBUTE_COVERAGE, coverages);
+    }
+    mesh.insert_indices(Indices::U32(indices));
+    // derived from the positions, so they are not stored
+    vertex_buffer::insert_surface_attributes(&mut mesh, size);
+
+    let path_count = input.len()?;
+    let paths = (0..path_count)
+        .map(|_| decode_path(&mut input))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let element_count = input.len()?;
+    let mut elements = Vec::with_capacity(element_count);
+    for _ in 0..element_count {
+        let id = input.str()?;
+        let tag = input.str()?;
+        let class_count = input.len()?;
+        let classes = (0..class_count)
+            .map(|_| input.str())
+            .collect::<Result<Vec<_>, _>>()?;
+        let data_count = input.len()?;
+        let data = (0..data_count)
+            .map(|_| Ok((input.str()?, input.str()?)))
+            .collect::<Result<HashMap<_, _>, BakedSvgError>>()?;
+        let bounds = match input.u8()? {
+            0 => None,
+            _ => {
+                let (min, max) = (input.vec2()?, input.vec2()?);
+                Some(Rect::from_corners(min, max))
+            }
+        };
+        elements.push(SvgElement {
+            id,
+            tag,
+            classes,
+            data,
+            bounds,
+        });
+    }
+
+    let frame_count = input.len()?;
+    let mut frames = Vec::with_capacity(frame_count);
+    for _ in 0..frame_count {
+        let id = input.str()?;
+        let vertices = input.u32()?..input.u32()?;
+        let indices = input.u32()?..input.u32()?;
+        if vertices.start > vertices.end
+            || vertices.end as usize > vertex_count
+            || indices.start > indices.end
+            || indices.end as usize > index_count
+        {
+            return Err(BakedSvgError::InvalidData("frame out of bounds"));
+        }
+        // the mesh of a frame only contains its own vertices
+        let outside_frame = mesh.indices().into_iter().any(|mesh_indices| {
+            mesh_indices
+                .iter()
+                .skip(indices.start as usize)
+                .take(indices.len())
+                .any(|index| !vertices.contains(&(index as u32)))
+        });
+        if outside_frame {
+            return Err(BakedSvgError::InvalidData("frame index out of bounds"));
+        }
+        frames.push(SvgFrame {
+            id,
+            mesh: Default::default(),
+            vertices,
+            indices,
+        });
+    }
+
+    let svg = Svg {
+        name,
+        size,
+        view_box,
+        paths,
+        elements,
+        antialias,
+        layer_spacing,
+        mesh: Default::default(),
+        frames,
+    };
+    Ok((svg, mesh))
+}
+
+fn decode_path(input: &mut Decoder) -> Result<PathDescriptor, BakedSvgError> {
+    let id = input.str()?;
+    let ancestor_count = input.len()?;
+    let ancestor_ids = (0..ancestor_count)
+        .map(|_| input.str())
+        .collect::<Result<Vec<_>, _>>()?;
+    let color = Color::srgba(input.f32()?, input.f32()?, input.f32()?, input.f32()?);
+    let is_stroke = input.u8()? != 0;
+    let abs_transform = usvg::Transform::from_row(
+        input.f32()?,
+        input.f32()?,
+        input.f32()?,
+        input.f32()?,
+        input.f32()?,
+        input.f32()?,
+    );
+    let draw_type = match input.u8()? {
+        0 => DrawType::Fill(match input.u8()? {
+            0 => FillRule::EvenOdd,
+            _ => FillRule::NonZero,
+        }),
+        1 => {
+            let tolerance = input.f32()?;
+            let line_width = input.f32()?;
+            let miter_limit = input.f32()?;
+            let start_cap = line_cap_from_u8(input.u8()?)?;
+            let end_cap = line_cap_from_u8(input.u8()?)?;
+            let line_join = match input.u8()? {
+                0 => LineJoin::Miter,
+                1 => LineJoin::MiterClip,
+                2 => LineJoin::Round,
+                3 => LineJoin::Bevel,
+                _ => return Err(BakedSvgError::InvalidData("line join")),
+            };
+            DrawType::Stroke(
+                StrokeOptions::tolerance(tolerance)
+                    .with_line_width(line_width)
+                    .with_miter_limit(miter_limit)
+                    .with_start_cap(start_cap)
+                    .with_end_cap(end_cap)
+                    .with_line_join(line_join),
+            )
+        }
+        _ => return Err(BakedSvgError::InvalidData("draw type")),
+    };
+
+    let segment_count = input.len()?;
+    let mut segments = Vec::with_capacity(segment_count);
+    let point = |input: &mut Decoder| input.vec2().map(|v| Point::new(v.x, v.y));
+    for _ in 0..segment_count {
+        segments.push(match input.u8()? {
+            0 => PathEvent::Begin { at: point(input)? },
+            1 => PathEvent::Line {
+                from: point(input)?,
+                to: point(input)?,
+            },
+            2 => PathEvent::Quadratic {
+                from: point(input)?,
+                ctrl: point(input)?,
+                to: point(input)?,
+            },
+            3 => PathEvent::Cubic {
+                from: point(input)?,
+                ctrl1: point(input)?,
+                ctrl2: point(input)?,
+                to: point(input)?,
+            },
+            4 => PathEvent::End {
+                last: point(input)?,
+                first: point(input)?,
+                close: input.u8()? != 0,
+            },
+            _ => return Err(BakedSvgError::InvalidData("path event")),
+        });
+    }
+
+    Ok(PathDescriptor {
+        segments,
+        color,
+        draw_type,
+        abs_transform,
+        is_stroke,
+        id,
+        ancestor_ids,
+    })
+}
+
+fn line_cap_to_u8(cap: LineCap) -> u8 {
+    match cap {
+        LineCap::Butt => 0,
+        LineCap::Square => 1,
+        LineCap::Round => 2,
+    }
+}
+
+fn line_cap_from_u8(value: u8) -> Result<LineCap, BakedSvgError> {
+    match value {
+        0 => Ok(LineCap::Butt),
+        1 => Ok(LineCap::Square),
+        2 => Ok(LineCap::Round),
+        _ => Err(BakedSvgError::InvalidData("line cap")),
+    }
+}
+
+/// Writes little endian values.
+#[derive(Default)]
+struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn vec2(&mut self, value: Vec2) {
+        self.f32(value.x);
+        self.f32(value.y);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.bytes.extend_from_slice(value.as_bytes());
+    }
+}
+
+/// Reads little endian values.
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BakedSvgError> {
+        if self.bytes.len() < len {
+            return Err(BakedSvgError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BakedSvgError> {
+        self.take(N).map(|bytes| bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, BakedSvgError> {
+        self.array::<1>().map(|[value]| value)
+    }
+
+    fn u16(&mut self) -> Result<u16, BakedSvgError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, BakedSvgError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn len(&mut self) -> Result<usize, BakedSvgError> {
+        let len = self.u32()? as usize;
+        // every element takes at least one byte, which protects against huge allocations
+        if len > self.bytes.len() {
+            return Err(BakedSvgError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+
+    fn f32(&mut self) -> Result<f32, BakedSvgError> {
+        self.array().map(f32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, BakedSvgError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn vec2(&mut self) -> Result<Vec2, BakedSvgError> {
+        Ok(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    fn str(&mut self) -> Result<String, BakedSvgError> {
+        let len = self.len()?;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| BakedSvgError::InvalidData("string"))
+    }
+}
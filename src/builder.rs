@@ -0,0 +1,389 @@
+//! Creates [`Svg`]s in code, without writing and parsing an SVG file.
+
+use std::ops::Range;
+
+use bevy::{
+    color::Color,
+    math::{Affine2, Rect, Vec2},
+};
+use lyon_path::PathEvent;
+use lyon_tessellation::{FillRule, StrokeOptions};
+use svgtypes::{SimplePathSegment, SimplifyingPathParser, ViewBox};
+use usvg::tiny_skia_path::{self, PathBuilder};
+
+use crate::{
+    Convert,
+    element::SvgElement,
+    geometry::path_bounds,
+    svg::{DrawType, PathConvIter, PathDescriptor, Svg},
+};
+
+/// How a shape of an [`SvgBuilder`] is painted.
+///
+/// Shapes without fill and stroke are not added to the [`Svg`]. The default options match the
+/// ones used for loaded SVG files, e.g. the `nonzero` fill rule.
+#[derive(Clone, Copy, Debug)]
+pub struct SvgStyle {
+    /// Color of the fill.
+    pub fill: Option<Color>,
+    /// Fill rule of the fill.
+    pub fill_rule: FillRule,
+    /// Color of the stroke.
+    pub stroke: Option<Color>,
+    /// Options for the stroke tessellation, e.g. line width, caps and joins.
+    pub stroke_options: StrokeOptions,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self {
+            fill: None,
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            stroke_options: StrokeOptions::tolerance(0.01),
+        }
+    }
+}
+
+impl SvgStyle {
+    /// A style which only fills the shape.
+    pub fn fill(color: impl Into<Color>) -> Self {
+        Self::default().with_fill(color)
+    }
+
+    /// A style which only strokes the shape.
+    pub fn stroke(color: impl Into<Color>, width: f32) -> Self {
+        Self::default().with_stroke(color, width)
+    }
+
+    /// Fills the shape with the given color.
+    pub fn with_fill(mut self, color: impl Into<Color>) -> Self {
+        self.fill = Some(color.into());
+        self
+    }
+
+    /// Strokes the shape with the given color and width.
+    pub fn with_stroke(mut self, color: impl Into<Color>, width: f32) -> Self {
+        self.stroke = Some(color.into());
+        self.stroke_options = self.stroke_options.with_line_width(width);
+        self
+    }
+
+    /// Sets the fill rule of the fill.
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    /// Sets the options used to tessellate the stroke.
+    pub fn with_stroke_options(mut self, options: StrokeOptions) -> Self {
+        self.stroke_options = options;
+        self
+    }
+}
+
+/// Builds an [`Svg`] from shapes.
+///
+/// All coordinates are SVG coordinates, so the y-axis points down. Every shape and group can be
+/// given an `id` and a transform with [`with_id`](Self::with_id) and
+/// [`with_transform`](Self::with_transform), which apply to the element added last. Shapes and
+/// groups with an `id` are added to the [`Svg::elements`], so they can be found with
+/// [`Svg::element`] and [`Svg::select`].
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_svg::prelude::*;
+/// fn setup(mut commands: Commands, mut svgs: ResMut<Assets<Svg>>) {
+///     let svg = Svg::builder()
+///         .rect(
+///             Rect::new(0.0, 0.0, 100.0, 60.0),
+///             SvgStyle::fill(Color::WHITE).with_stroke(Color::BLACK, 2.0),
+///         )
+///         .group(|group| {
+///             group
+///                 .circle(Vec2::new(25.0, 60.0), 10.0, SvgStyle::fill(Color::BLACK))
+///                 .circle(Vec2::new(75.0, 60.0), 10.0, SvgStyle::fill(Color::BLACK))
+///         })
+///         .with_id("wheels")
+///         .build();
+///     // the mesh is generated once the `Svg` is added
+///     commands.spawn(Svg2d(svgs.add(svg)));
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SvgBuilder {
+    name: String,
+    size: Option<Vec2>,
+    paths: Vec<PathDescriptor>,
+    last: Option<Element>,
+    /// The number of shapes and groups added so far.
+    element_count: usize,
+    /// The shapes and groups with an `id`, with the range of their paths.
+    elements: Vec<(Element, String, Range<usize>)>,
+}
+
+/// A shape or group, with its position in document order and the index of its first path.
+#[derive(Clone, Copy, Debug)]
+enum Element {
+    Shape {
+        key: usize,
+        start: usize,
+        tag: &'static str,
+    },
+    Group {
+        key: usize,
+        start: usize,
+    },
+}
+
+impl Element {
+    /// The position of the element in document order, which is unique within a builder.
+    fn key(self) -> usize {
+        match self {
+            Element::Shape { key, .. } | Element::Group { key, .. } => key,
+        }
+    }
+
+    /// The index of the first path of the element.
+    fn start(self) -> usize {
+        match self {
+            Element::Shape { start, .. } | Element::Group { start, .. } => start,
+        }
+    }
+}
+
+impl Svg {
+    /// Creates an [`SvgBuilder`] to build an [`Svg`] in code.
+    pub fn builder() -> SvgBuilder {
+        SvgBuilder::default()
+    }
+}
+
+impl SvgBuilder {
+    /// Sets the name of the [`Svg`].
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the size of the [`Svg`]. If it is not set, the size reaches from `(0, 0)` to the
+    /// bottom right corner of all shapes.
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some(Vec2::new(width, height));
+        self
+    }
+
+    /// Adds a rectangle.
+    pub fn rect(self, rect: Rect, style: SvgStyle) -> Self {
+        let path = tiny_skia_path::Rect::from_ltrb(rect.min.x, rect.min.y, rect.max.x, rect.max.y)
+            .map(PathBuilder::from_rect);
+        self.shape("rect", path, style)
+    }
+
+    /// Adds a circle.
+    pub fn circle(self, center: Vec2, radius: f32, style: SvgStyle) -> Self {
+        let path = PathBuilder::from_circle(center.x, center.y, radius);
+        self.shape("circle", path, style)
+    }
+
+    /// Adds an ellipse.
+    pub fn ellipse(self, center: Vec2, radii: Vec2, style: SvgStyle) -> Self {
+        let (min, max) = (center - radii, center + radii);
+        let path = tiny_skia_path::Rect::from_ltrb(min.x, min.y, max.x, max.y)
+            .and_then(PathBuilder::from_oval);
+        self.shape("ellipse", path, style)
+    }
+
+    /// Adds an open line through the points.
+    pub fn polyline(self, points: &[Vec2], style: SvgStyle) -> Self {
+        self.lines("polyline", points, false, style)
+    }
+
+    /// Adds a closed polygon through the points.
+    pub fn polygon(self, points: &[Vec2], style: SvgStyle) -> Self {
+        self.lines("polygon", points, true, style)
+    }
+
+    /// Adds a path from SVG path data, the `d` attribute of a `<path>` element.
+    pub fn path(self, data: &str, style: SvgStyle) -> Result<Self, svgtypes::Error> {
+        let mut builder = PathBuilder::new();
+        for segment in SimplifyingPathParser::from(data) {
+            match segment? {
+                SimplePathSegment::MoveTo { x, y } => builder.move_to(x as f32, y as f32),
+                SimplePathSegment::LineTo { x, y } => builder.line_to(x as f32, y as f32),
+                SimplePathSegment::CurveTo {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    x,
+                    y,
+                } => builder.cubic_to(
+                    x1 as f32, y1 as f32, x2 as f32, y2 as f32, x as f32, y as f32,
+                ),
+                SimplePathSegment::Quadratic { x1, y1, x, y } => {
+                    builder.quad_to(x1 as f32, y1 as f32, x as f32, y as f32)
+                }
+                SimplePathSegment::ClosePath => builder.close(),
+            }
+        }
+        Ok(self.shape("path", builder.finish(), style))
+    }
+
+    /// Adds a group, the shapes added inside the closure are part of it.
+    pub fn group(mut self, build: impl FnOnce(SvgBuilder) -> SvgBuilder) -> Self {
+        let start = self.paths.len();
+        // a group comes before its children in document order
+        let key = self.next_key();
+        self = build(self);
+        self.last = Some(Element::Group { key, start });
+        self
+    }
+
+    /// Sets the `id` of the shape or group added last.
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        let id = id.into();
+        if let Some(last) = self.last {
+            self.elements
+                .retain(|(element, ..)| element.key() != last.key());
+            self.elements
+                .push((last, id.clone(), last.start()..self.paths.len()));
+        }
+        match self.last {
+            Some(Element::Shape { start, .. }) => {
+                for path in &mut self.paths[start..] {
+                    path.id = id.clone();
+                }
+            }
+            Some(Element::Group { start, .. }) => {
+                // groups get their `id` after their children, so it is the outermost one
+                for path in &mut self.paths[start..] {
+                    path.ancestor_ids.insert(0, id.clone());
+                }
+            }
+            None => {}
+        }
+        self
+    }
+
+    /// Transforms the shape or group added last, in SVG coordinates.
+    pub fn with_transform(mut self, transform: Affine2) -> Self {
+        let [a, b] = transform.matrix2.to_cols_array_2d();
+        let t = transform.translation;
+        let transform = usvg::Transform::from_row(a[0], a[1], b[0], b[1], t.x, t.y);
+        let Some(start) = self.last.map(Element::start) else {
+            return self;
+        };
+        for path in &mut self.paths[start..] {
+            path.abs_transform = path.abs_transform.post_concat(transform);
+        }
+        self
+    }
+
+    /// Builds the [`Svg`]. Its mesh is generated once it is added to the [`Assets<Svg>`](bevy::asset::Assets).
+    pub fn build(self) -> Svg {
+        let size = self.size.unwrap_or_else(|| {
+            self.paths
+                .iter()
+                .flat_map(|path| path.transformed_segments())
+                .filter_map(|event| match event {
+                    PathEvent::Begin { at } => Some(at),
+                    PathEvent::Line { to, .. }
+                    | PathEvent::Quadratic { to, .. }
+                    | PathEvent::Cubic { to, .. } => Some(to),
+                    PathEvent::End { .. } => None,
+                })
+                .fold(Vec2::ZERO, |size, point| {
+                    size.max(Vec2::new(point.x, point.y))
+                })
+        });
+        // the keys are in document order
+        let mut elements = self.elements;
+        elements.sort_by_key(|(element, ..)| element.key());
+        let elements = elements
+            .into_iter()
+            .map(|(element, id, range)| SvgElement {
+                id,
+                tag: match element {
+                    Element::Shape { tag, .. } => tag,
+                    Element::Group { .. } => "g",
+                }
+                .to_string(),
+                bounds: self.paths[range]
+                    .iter()
+                    .filter_map(path_bounds)
+                    .reduce(|a, b| a.union(b)),
+                ..Default::default()
+            })
+            .collect();
+        Svg {
+            name: self.name,
+            size,
+            view_box: ViewBox {
+                x: 0.0,
+                y: 0.0,
+                w: size.x as f64,
+                h: size.y as f64,
+            },
+            paths: self.paths,
+            elements,
+            ..Default::default()
+        }
+    }
+
+    fn next_key(&mut self) -> usize {
+        self.element_count += 1;
+        self.element_count - 1
+    }
+
+    fn lines(self, tag: &'static str, points: &[Vec2], close: bool, style: SvgStyle) -> Self {
+        let mut builder = PathBuilder::new();
+        for (idx, point) in points.iter().enumerate() {
+            if idx == 0 {
+                builder.move_to(point.x, point.y);
+            } else {
+                builder.line_to(point.x, point.y);
+            }
+        }
+        if close {
+            builder.close();
+        }
+        self.shape(tag, builder.finish(), style)
+    }
+
+    fn shape(
+        mut self,
+        tag: &'static str,
+        path: Option<tiny_skia_path::Path>,
+        style: SvgStyle,
+    ) -> Self {
+        let start = self.paths.len();
+        let key = self.next_key();
+        self.last = Some(Element::Shape { key, start, tag });
+        let Some(path) = path else {
+            return self;
+        };
+        let segments = Convert::<PathConvIter>::convert(&path).collect::<Vec<_>>();
+        let descriptor = |color, draw_type, is_stroke| PathDescriptor {
+            segments: segments.clone(),
+            color,
+            draw_type,
+            abs_transform: usvg::Transform::identity(),
+            is_stroke,
+            id: String::new(),
+            ancestor_ids: Vec::new(),
+        };
+        if let Some(color) = style.fill {
+            self.paths
+                .push(descriptor(color, DrawType::Fill(style.fill_rule), false));
+        }
+        if let Some(color) = style.stroke {
+            self.paths.push(descriptor(
+                color,
+                DrawType::Stroke(style.stroke_options),
+                true,
+            ));
+        }
+        self
+    }
+}
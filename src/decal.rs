@@ -0,0 +1,232 @@
+//! Projects [`Svg`]s onto 3D surfaces as decals.
+
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets, Handle},
+    camera::visibility::Visibility,
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        component::Component,
+        entity::Entity,
+        lifecycle::RemovedComponents,
+        message::MessageReader,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+        world::{FromWorld, World},
+    },
+    image::Image,
+    light::ClusteredDecal,
+    log::error,
+    math::{UVec2, Vec2, primitives::Rectangle},
+    mesh::{Mesh, Mesh3d, MeshBuilder, Meshable},
+    pbr::{
+        MeshMaterial3d, StandardMaterial,
+        decal::{ForwardDecal, ForwardDecalMaterial, ForwardDecalMaterialExt},
+    },
+    platform::collections::HashMap,
+    render::alpha::AlphaMode,
+    transform::components::Transform,
+};
+
+use crate::svg::Svg;
+
+/// Projects an [`Svg`] onto the surfaces in a box, e.g. for logos, road markings or bullet holes.
+///
+/// Like a [`ClusteredDecal`], the box is a 1×1×1 cube centered on the entity, which is sized
+/// and oriented with its [`Transform`]. The [`Svg`] is projected along the negative z-axis of the
+/// box, with its top towards the positive y-axis. It is rasterized into a texture of
+/// `resolution` pixels, scaled to fit and centered, so a resolution and a box with the aspect
+/// ratio of the [`Svg`] show it undistorted. Decals with the same [`Svg`] and resolution share
+/// the texture, which is rasterized again when the [`Svg`] changes.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_svg::prelude::*;
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn((
+///         SvgDecal::new(asset_server.load("box.svg")).with_resolution(UVec2::splat(1024)),
+///         Transform::from_xyz(0.0, 0.5, 0.0)
+///             .looking_to(Vec3::NEG_Y, Vec3::Z)
+///             .with_scale(Vec3::new(2.0, 2.0, 1.0)),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Component, Debug)]
+#[require(Transform, Visibility)]
+pub struct SvgDecal {
+    /// The projected [`Svg`].
+    pub svg: Handle<Svg>,
+    /// The size of the texture in pixels.
+    pub resolution: UVec2,
+    /// How the decal is rendered.
+    pub mode: SvgDecalMode,
+}
+
+impl SvgDecal {
+    /// Creates a clustered decal with a texture of 512×512 pixels.
+    pub fn new(svg: Handle<Svg>) -> Self {
+        Self {
+            svg,
+            resolution: UVec2::splat(512),
+            mode: SvgDecalMode::default(),
+        }
+    }
+
+    /// Sets the size of the texture in pixels.
+    pub fn with_resolution(mut self, resolution: UVec2) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Sets how the decal is rendered.
+    pub fn with_mode(mut self, mode: SvgDecalMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+/// How an [`SvgDecal`] is rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SvgDecalMode {
+    /// A [`ClusteredDecal`], which works with forward and deferred rendering and is cut off
+    /// exactly at the box. It needs bindless textures, which are not available on WebGL 2,
+    /// WebGPU, macOS and iOS.
+    #[default]
+    Clustered,
+    /// A [`ForwardDecal`], a quad through the center of the box facing its positive z-axis,
+    /// which fades out on surfaces further than `depth_fade` away from it. The camera needs a
+    /// [`DepthPrepass`](bevy::core_pipeline::prepass::DepthPrepass).
+    Forward {
+        /// The distance in world units at which the decal is faded out.
+        depth_fade: f32,
+    },
+}
+
+/// The textures and forward decal materials of all [`SvgDecal`]s.
+#[derive(Resource)]
+pub(crate) struct SvgDecals {
+    textures: HashMap<(AssetId<Svg>, UVec2), Handle<Image>>,
+    materials: HashMap<(AssetId<Svg>, UVec2, u32), Handle<ForwardDecalMaterial<StandardMaterial>>>,
+    /// A quad facing the positive z-axis, Bevy's forward decal mesh faces the positive y-axis.
+    forward_mesh: Handle<Mesh>,
+}
+
+impl FromWorld for SvgDecals {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = Rectangle::from_size(Vec2::ONE)
+            .mesh()
+            .build()
+            .with_generated_tangents()
+            .expect("a rectangle has normals and UVs");
+        Self {
+            textures: HashMap::default(),
+            materials: HashMap::default(),
+            forward_mesh: world.resource_mut::<Assets<Mesh>>().add(mesh),
+        }
+    }
+}
+
+/// Rasterizes the [`Svg`]s of changed [`SvgDecal`]s and adds the components of their mode.
+pub fn update_svg_decals(
+    mut commands: Commands,
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<ForwardDecalMaterial<StandardMaterial>>>,
+    mut decals: ResMut<SvgDecals>,
+    query: Query<(Entity, Ref<SvgDecal>)>,
+) {
+    let mut changed = Vec::new();
+    for event in svg_events.read() {
+        match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => changed.push(*id),
+            AssetEvent::Removed { id } => {
+                decals.textures.retain(|(svg, _), _| svg != id);
+                decals.materials.retain(|(svg, ..), _| svg != id);
+            }
+            _ => (),
+        }
+    }
+
+    // rasterize the textures of changed svgs again, decals which use them keep their handles
+    let SvgDecals {
+        textures,
+        materials: decal_materials,
+        forward_mesh,
+    } = &mut *decals;
+    for ((id, resolution), texture) in textures.iter() {
+        if changed.contains(id)
+            && let Some(svg) = svgs.get(*id)
+            && images.insert(texture, svg.rasterize(*resolution)).is_err()
+        {
+            error!("Failed to insert the texture of an SVG decal");
+        }
+    }
+
+    for (entity, decal) in &query {
+        let svg_id = decal.svg.id();
+        let key = (svg_id, decal.resolution);
+        if !decal.is_changed() && !changed.contains(&svg_id) {
+            continue;
+        }
+        let texture = match textures.get(&key) {
+            Some(texture) => texture.clone(),
+            None => {
+                let Some(svg) = svgs.get(svg_id) else {
+                    continue;
+                };
+                let texture = images.add(svg.rasterize(decal.resolution));
+                textures.insert(key, texture.clone());
+                texture
+            }
+        };
+
+        let mut entity = commands.entity(entity);
+        entity.try_remove::<DecalComponents>();
+        match decal.mode {
+            SvgDecalMode::Clustered => {
+                entity.try_insert(ClusteredDecal {
+                    base_color_texture: Some(texture),
+                    ..Default::default()
+                });
+            }
+            SvgDecalMode::Forward { depth_fade } => {
+                let mesh = forward_mesh.clone();
+                let material = decal_materials
+                    .entry((svg_id, decal.resolution, depth_fade.to_bits()))
+                    .or_insert_with(|| {
+                        materials.add(ForwardDecalMaterial {
+                            base: StandardMaterial {
+                                base_color_texture: Some(texture),
+                                alpha_mode: AlphaMode::Blend,
+                                ..Default::default()
+                            },
+                            extension: ForwardDecalMaterialExt {
+                                depth_fade_factor: depth_fade,
+                            },
+                        })
+                    })
+                    .clone();
+                entity.try_insert((ForwardDecal, Mesh3d(mesh), MeshMaterial3d(material)));
+            }
+        }
+    }
+}
+
+/// Removes the components of the mode from entities whose [`SvgDecal`] was removed.
+pub fn remove_svg_decals(mut commands: Commands, mut removed: RemovedComponents<SvgDecal>) {
+    for entity in removed.read() {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.try_remove::<DecalComponents>();
+        }
+    }
+}
+
+/// The components added for the modes of an [`SvgDecal`].
+type DecalComponents = (
+    ClusteredDecal,
+    ForwardDecal,
+    Mesh3d,
+    MeshMaterial3d<ForwardDecalMaterial<StandardMaterial>>,
+);
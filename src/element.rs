@@ -0,0 +1,284 @@
+//! Metadata of the elements of an SVG file, which `usvg` does not preserve.
+
+use bevy::{
+    math::{Rect, Vec2},
+    platform::collections::HashMap,
+};
+use svgtypes::ViewBox;
+
+/// An element of an SVG file with an `id`, a `class` or `data-*` attributes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SvgElement {
+    /// The `id` of the element, may be empty.
+    pub id: String,
+    /// The tag name of the element, e.g. `circle`.
+    pub tag: String,
+    /// The classes of the element.
+    pub classes: Vec<String>,
+    /// The `data-*` attributes of the element, the keys are stored without the `data-` prefix.
+    pub data: HashMap<String, String>,
+    /// The bounding box of the element in SVG coordinates, if it is rendered.
+    pub bounds: Option<Rect>,
+}
+
+impl SvgElement {
+    /// Returns `true` if the element has the given class.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.classes.iter().any(|c| c == class)
+    }
+
+    /// Returns the value of the `data-*` attribute with the given key, without the `data-` prefix.
+    pub fn data(&self, key: &str) -> Option<&str> {
+        self.data.get(key).map(String::as_str)
+    }
+
+    /// The center of the bounding box, in the coordinate system of the mesh of the
+    /// [`Svg`](crate::svg::Svg). The y-axis is flipped compared to the SVG.
+    pub fn position(&self) -> Option<Vec2> {
+        self.bounds
+            .map(|bounds| bounds.center())
+            .map(|center| Vec2::new(center.x, -center.y))
+    }
+}
+
+/// Selects [`SvgElement`]s, written like a simple CSS selector.
+///
+/// Supports a tag name, an `#id`, any number of `.class`es and `[data-key]` or
+/// `[data-key=value]` attributes, which all have to match, e.g. `circle.enemy[data-kind=goblin]`.
+/// Combinators and selector lists, e.g. `g circle`, `g > circle` or `rect, circle`, are not
+/// supported, a selector containing them matches no element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SvgSelector {
+    /// Required tag name.
+    pub tag: Option<String>,
+    /// Required `id`.
+    pub id: Option<String>,
+    /// Required classes.
+    pub classes: Vec<String>,
+    /// Required `data-*` attributes without the `data-` prefix, and optionally their value.
+    pub data: Vec<(String, Option<String>)>,
+    /// The unsupported part of the selector starting at the first whitespace or combinator, if any.
+    /// The selector matches no element when it is set.
+    pub unsupported: Option<String>,
+}
+
+impl SvgSelector {
+    /// Parses a selector, unknown parts are ignored and combinators are kept in
+    /// [`SvgSelector::unsupported`].
+    pub fn parse(selector: &str) -> Self {
+        const COMBINATORS: [char; 4] = ['>', '+', '~', ','];
+        let mut result = SvgSelector::default();
+        let mut rest = selector.trim();
+        let name_len = |s: &str| {
+            s.find(|c: char| {
+                matches!(c, '#' | '.' | '[') || c.is_whitespace() || COMBINATORS.contains(&c)
+            })
+            .unwrap_or(s.len())
+        };
+
+        let len = name_len(rest);
+        if len > 0 {
+            result.tag = Some(rest[..len].to_string());
+        }
+        rest = &rest[len..];
+
+        while let Some(c) = rest.chars().next() {
+            if c.is_whitespace() || COMBINATORS.contains(&c) {
+                bevy::log::warn!("SvgSelector `{selector}`: combinators are not supported.");
+                result.unsupported = Some(rest.to_string());
+                break;
+            }
+            rest = &rest[c.len_utf8()..];
+            match c {
+                '#' | '.' => {
+                    let len = name_len(rest);
+                    let name = rest[..len].to_string();
+                    rest = &rest[len..];
+                    if c == '#' {
+                        result.id = Some(name);
+                    } else {
+                        result.classes.push(name);
+                    }
+                }
+                '[' => {
+                    let len = rest.find(']').unwrap_or(rest.len());
+                    let attribute = &rest[..len];
+                    rest = rest.get(len + 1..).unwrap_or_default();
+                    let (key, value) = match attribute.split_once('=') {
+                        Some((key, value)) => (
+                            key.trim(),
+                            Some(value.trim().trim_matches(['"', '\'']).to_string()),
+                        ),
+                        None => (attribute.trim(), None),
+                    };
+                    if let Some(key) = key.strip_prefix("data-") {
+                        result.data.push((key.to_string(), value));
+                    }
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    /// Returns `true` if the element matches the selector.
+    pub fn matches(&self, element: &SvgElement) -> bool {
+        self.unsupported.is_none()
+            && self.tag.as_ref().is_none_or(|tag| *tag == element.tag)
+            && self.id.as_ref().is_none_or(|id| *id == element.id)
+            && self.classes.iter().all(|class| element.has_class(class))
+            && self.data.iter().all(|(key, value)| match value {
+                Some(value) => element.data(key) == Some(value.as_str()),
+                None => element.data.contains_key(key),
+            })
+    }
+}
+
+impl From<&str> for SvgSelector {
+    fn from(selector: &str) -> Self {
+        SvgSelector::parse(selector)
+    }
+}
+
+/// The metadata of an SVG file which `usvg` does not preserve.
+#[derive(Default)]
+pub(crate) struct SourceMetadata {
+    /// All elements with an `id`, a `class` or `data-*` attributes.
+    pub(crate) elements: Vec<SvgElement>,
+    /// The `viewBox` of the root element, if it is valid.
+    pub(crate) view_box: Option<ViewBox>,
+}
+
+/// Parses the raw XML of an SVG file, which may be compressed, for its [`SourceMetadata`].
+pub(crate) fn parse_source(
+    data: &[u8],
+    tree: &usvg::Tree,
+    usvg_options: &usvg::Options,
+) -> SourceMetadata {
+    let data = if data.starts_with(&[0x1f, 0x8b]) {
+        match usvg::decompress_svgz(data) {
+            Ok(data) => std::borrow::Cow::Owned(data),
+            Err(_) => return SourceMetadata::default(),
+        }
+    } else {
+        std::borrow::Cow::Borrowed(data)
+    };
+    let Ok(text) = std::str::from_utf8(&data) else {
+        return SourceMetadata::default();
+    };
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let Ok(document) = roxmltree::Document::parse_with_options(text, options) else {
+        return SourceMetadata::default();
+    };
+
+    let view_box = document
+        .root_element()
+        .attribute("viewBox")
+        .and_then(|view_box| view_box.parse::<ViewBox>().ok())
+        .filter(|view_box| view_box.w > 0.0 && view_box.h > 0.0);
+    SourceMetadata {
+        elements: parse_elements(&document, tree, usvg_options),
+        view_box,
+    }
+}
+
+/// Prefix of the `id`s which are given to elements without one to look up their bounds.
+const GENERATED_ID: &str = "__bevy_svg_element_";
+
+/// Collects the metadata of all elements with an `id`, a `class` or `data-*` attributes from the
+/// raw XML, the bounds are looked up in the `usvg` tree. Elements inside of `<defs>` are skipped.
+///
+/// `usvg` only keeps the `id` of elements, so the bounds of elements without one are looked up in
+/// a second tree, parsed with `usvg_options` from a copy of the document with generated `id`s.
+fn parse_elements(
+    document: &roxmltree::Document,
+    tree: &usvg::Tree,
+    usvg_options: &usvg::Options,
+) -> Vec<SvgElement> {
+    let text = document.input_text();
+    let nodes = document
+        .descendants()
+        .filter(|node| node.is_element())
+        .filter(|node| {
+            !node
+                .ancestors()
+                .any(|ancestor| ancestor.tag_name().name() == "defs")
+        })
+        .filter(|node| {
+            has_id(node)
+                || node
+                    .attribute("class")
+                    .is_some_and(|class| !class.trim().is_empty())
+                || node.attributes().any(|a| a.name().starts_with("data-"))
+        })
+        .collect::<Vec<_>>();
+
+    // insert the generated `id`s from back to front, so the positions stay valid
+    let without_id = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| !has_id(node))
+        .collect::<Vec<_>>();
+    let generated_tree = (!without_id.is_empty()).then(|| {
+        let mut text = text.to_string();
+        for (idx, node) in without_id.iter().rev() {
+            // right after the tag name
+            let start = node.range().start + 1;
+            let end = text[start..]
+                .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .map_or(text.len(), |len| start + len);
+            text.insert_str(end, &format!(r#" id="{GENERATED_ID}{idx}""#));
+        }
+        usvg::Tree::from_str(&text, usvg_options).ok()
+    });
+    let bounds = |idx: usize, id: &str| {
+        let node = if id.is_empty() {
+            generated_tree
+                .as_ref()?
+                .as_ref()?
+                .node_by_id(&format!("{GENERATED_ID}{idx}"))?
+                .abs_bounding_box()
+        } else {
+            tree.node_by_id(id)?.abs_bounding_box()
+        };
+        Some(Rect::new(
+            node.left(),
+            node.top(),
+            node.right(),
+            node.bottom(),
+        ))
+    };
+
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| {
+            let id = node.attribute("id").unwrap_or_default().to_string();
+            let classes = node
+                .attribute("class")
+                .map(|class| class.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_else(Vec::new);
+            let data = node
+                .attributes()
+                .filter_map(|attribute| {
+                    let key = attribute.name().strip_prefix("data-")?;
+                    Some((key.to_string(), attribute.value().to_string()))
+                })
+                .collect::<HashMap<_, _>>();
+            SvgElement {
+                bounds: bounds(idx, &id),
+                id,
+                tag: node.tag_name().name().to_string(),
+                classes,
+                data,
+            }
+        })
+        .collect()
+}
+
+fn has_id(node: &roxmltree::Node) -> bool {
+    node.attribute("id").is_some_and(|id| !id.is_empty())
+}
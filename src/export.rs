@@ -0,0 +1,225 @@
+//! Writes an [`Svg`] back to an SVG document.
+
+use std::fmt::Write;
+
+use bevy::color::{Color, ColorToPacked, Srgba};
+use lyon_path::PathEvent;
+use lyon_tessellation::{FillRule, LineCap, LineJoin, StrokeOptions};
+use svgtypes::ViewBox;
+use usvg::Transform;
+
+use crate::svg::{DrawType, PathDescriptor, Svg};
+
+impl Svg {
+    /// Serializes the [`Svg`] to an SVG document.
+    ///
+    /// Every path becomes a `<path>` element with its absolute transform, groups are recreated
+    /// from the `id`s of the enclosing groups. The document keeps the [`Svg::view_box`], so the
+    /// transforms are written relative to it. The classes and `data-*` attributes of
+    /// [`Svg::elements`] are written to the elements with the same `id`.
+    pub fn to_svg_string(&self) -> String {
+        let (w, h) = (self.size.x as f64, self.size.y as f64);
+        let view_box = match self.view_box {
+            view_box if view_box.w > 0.0 && view_box.h > 0.0 && w > 0.0 && h > 0.0 => view_box,
+            _ => ViewBox::new(0.0, 0.0, w, h),
+        };
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{w}" height="{h}" viewBox="{} {} {} {}""#,
+            view_box.x, view_box.y, view_box.w, view_box.h,
+        );
+        // the view box is stretched to the size, like the transforms of the paths
+        if view_box.w * h != view_box.h * w {
+            out.push_str(r#" preserveAspectRatio="none""#);
+        }
+        out.push_str(">\n");
+        let to_view_box = Transform::from_row(
+            (view_box.w / w) as f32,
+            0.0,
+            0.0,
+            (view_box.h / h) as f32,
+            view_box.x as f32,
+            view_box.y as f32,
+        );
+
+        let mut groups: Vec<&str> = Vec::new();
+        let mut idx = 0;
+        while idx < self.paths.len() {
+            let path = &self.paths[idx];
+            // the fill and the stroke of an element are written as a single element
+            let pair = self
+                .paths
+                .get(idx + 1)
+                .filter(|next| next.is_same_element(path) && next.is_stroke != path.is_stroke);
+            idx += if pair.is_some() { 2 } else { 1 };
+
+            let common = groups
+                .iter()
+                .zip(&path.ancestor_ids)
+                .take_while(|(a, b)| **a == b.as_str())
+                .count();
+            while groups.len() > common {
+                groups.pop();
+                let _ = writeln!(out, "{}</g>", indent(groups.len()));
+            }
+            for id in &path.ancestor_ids[common..] {
+                let _ = writeln!(
+                    out,
+                    "{}<g{}>",
+                    indent(groups.len()),
+                    self.element_attributes(id)
+                );
+                groups.push(id);
+            }
+
+            let _ = writeln!(
+                out,
+                "{}{}",
+                indent(groups.len()),
+                self.path_element(path, pair, to_view_box)
+            );
+        }
+        while groups.pop().is_some() {
+            let _ = writeln!(out, "{}</g>", indent(groups.len()));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// The `id`, `class` and `data-*` attributes of an element.
+    fn element_attributes(&self, id: &str) -> String {
+        let mut attributes = String::new();
+        if id.is_empty() {
+            return attributes;
+        }
+        let _ = write!(attributes, r#" id="{}""#, escape(id));
+        if let Some(element) = self.element(id) {
+            if !element.classes.is_empty() {
+                let _ = write!(
+                    attributes,
+                    r#" class="{}""#,
+                    escape(&element.classes.join(" "))
+                );
+            }
+            let mut data = element.data.iter().collect::<Vec<_>>();
+            data.sort();
+            for (key, value) in data {
+                let _ = write!(attributes, r#" data-{}="{}""#, key, escape(value));
+            }
+        }
+        attributes
+    }
+
+    /// A `<path>` element, `pair` is the stroke of a filled path or the fill of a stroked path.
+    /// `to_view_box` maps from the size of the [`Svg`] to its view box.
+    fn path_element(
+        &self,
+        path: &PathDescriptor,
+        pair: Option<&PathDescriptor>,
+        to_view_box: Transform,
+    ) -> String {
+        let mut element = format!(
+            r#"<path{} d="{}""#,
+            self.element_attributes(&path.id),
+            path_data(&path.segments)
+        );
+        let transform = to_view_box.pre_concat(path.abs_transform);
+        if !transform.is_identity() {
+            let _ = write!(
+                element,
+                r#" transform="matrix({} {} {} {} {} {})""#,
+                transform.sx, transform.ky, transform.kx, transform.sy, transform.tx, transform.ty
+            );
+        }
+
+        let (fill, stroke) = match (path.is_stroke, pair) {
+            (false, pair) => (Some(path), pair),
+            (true, pair) => (pair, Some(path)),
+        };
+        match fill {
+            Some(fill) => {
+                element.push_str(&paint("fill", fill.color));
+                if let DrawType::Fill(FillRule::EvenOdd) = fill.draw_type {
+                    element.push_str(r#" fill-rule="evenodd""#);
+                }
+            }
+            None => element.push_str(r#" fill="none""#),
+        }
+        if let Some(stroke) = stroke
+            && let DrawType::Stroke(options) = stroke.draw_type
+        {
+            element.push_str(&paint("stroke", stroke.color));
+            element.push_str(&stroke_attributes(&options));
+        }
+        if path.is_stroke && pair.is_some() {
+            element.push_str(r#" paint-order="stroke""#);
+        }
+        element.push_str("/>");
+        element
+    }
+}
+
+fn indent(depth: usize) -> String {
+    "    ".repeat(depth + 1)
+}
+
+/// Converts path events to SVG path data.
+fn path_data(events: &[PathEvent]) -> String {
+    let mut data = String::new();
+    for event in events {
+        let _ = match *event {
+            PathEvent::Begin { at } => write!(data, "M{} {} ", at.x, at.y),
+            PathEvent::Line { to, .. } => write!(data, "L{} {} ", to.x, to.y),
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                write!(data, "Q{} {} {} {} ", ctrl.x, ctrl.y, to.x, to.y)
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => write!(
+                data,
+                "C{} {} {} {} {} {} ",
+                ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+            ),
+            PathEvent::End { close: true, .. } => write!(data, "Z "),
+            PathEvent::End { close: false, .. } => Ok(()),
+        };
+    }
+    data.trim_end().to_string()
+}
+
+/// The color and opacity attributes of a paint.
+fn paint(name: &str, color: Color) -> String {
+    let [r, g, b, a] = Srgba::from(color).to_u8_array();
+    let mut paint = format!(r##" {name}="#{r:02x}{g:02x}{b:02x}""##);
+    if a != u8::MAX {
+        let _ = write!(paint, r#" {name}-opacity="{}""#, a as f32 / 255.0);
+    }
+    paint
+}
+
+fn stroke_attributes(options: &StrokeOptions) -> String {
+    let linecap = match options.start_cap {
+        LineCap::Butt => "butt",
+        LineCap::Square => "square",
+        LineCap::Round => "round",
+    };
+    let linejoin = match options.line_join {
+        LineJoin::Miter => "miter",
+        LineJoin::MiterClip => "miter-clip",
+        LineJoin::Round => "round",
+        LineJoin::Bevel => "bevel",
+    };
+    format!(
+        r#" stroke-width="{}" stroke-linecap="{}" stroke-linejoin="{}" stroke-miterlimit="{}""#,
+        options.line_width, linecap, linejoin, options.miter_limit
+    )
+}
+
+/// Escapes a string for an attribute value.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;")
+}
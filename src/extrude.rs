@@ -0,0 +1,343 @@
+//! Extrudes the filled areas of an [`Svg`] into solid 3D meshes.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    color::ColorToComponents,
+    math::{Vec2, Vec3},
+    mesh::{Indices, Mesh},
+    render::render_resource::PrimitiveTopology,
+};
+
+use crate::{
+    geometry::{SvgPolygon, flatten, offset_ring},
+    svg::{DrawType, PathDescriptor, Svg},
+};
+
+/// Options to extrude the filled areas of an [`Svg`] into a solid, see [`Svg::extrude`].
+///
+/// All lengths are in SVG units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgExtrusion {
+    /// The thickness of the solid along the z-axis, it is centered on `z = 0`.
+    pub depth: f32,
+    /// How far the rounded bevel reaches into the caps, the outline keeps its size. It should be
+    /// smaller than half the width of the thinnest part of a shape.
+    pub bevel_size: f32,
+    /// How far the bevel reaches into the sides, at most half of the depth.
+    pub bevel_depth: f32,
+    /// The number of segments of each bevel, `0` for no bevel.
+    pub bevel_segments: u32,
+    /// The maximum distance between a curve and its approximation.
+    pub tolerance: f32,
+    /// The largest angle in radians between neighboring side faces which are shaded smoothly,
+    /// sharper corners get a hard edge.
+    pub smoothing_angle: f32,
+}
+
+impl Default for SvgExtrusion {
+    fn default() -> Self {
+        Self {
+            depth: 10.0,
+            bevel_size: 0.0,
+            bevel_depth: 0.0,
+            bevel_segments: 0,
+            tolerance: 0.1,
+            smoothing_angle: 30f32.to_radians(),
+        }
+    }
+}
+
+impl SvgExtrusion {
+    /// Creates an extrusion with the given depth and without a bevel.
+    pub fn new(depth: f32) -> Self {
+        Self {
+            depth,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a rounded bevel to the front and the back.
+    pub fn with_bevel(mut self, size: f32, depth: f32, segments: u32) -> Self {
+        self.bevel_size = size;
+        self.bevel_depth = depth;
+        self.bevel_segments = segments;
+        self
+    }
+
+    /// Sets the maximum distance between a curve and its approximation.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the largest angle in radians between side faces which are shaded smoothly.
+    pub fn with_smoothing_angle(mut self, smoothing_angle: f32) -> Self {
+        self.smoothing_angle = smoothing_angle;
+        self
+    }
+
+    /// The rings of the sides from the front to the back, as the distance by which the outline
+    /// is moved inwards, the z coordinate and the normal in the plane of the inset and z.
+    fn profile(&self) -> Vec<ProfilePoint> {
+        let half = self.depth / 2.0;
+        let has_bevel =
+            self.bevel_segments > 0 && (self.bevel_size > 0.0 || self.bevel_depth > 0.0);
+        if !has_bevel {
+            let side = |z| ProfilePoint {
+                inset: 0.0,
+                z,
+                normal: Vec2::X,
+            };
+            return vec![side(half), side(-half)];
+        }
+
+        let segments = self.bevel_segments;
+        let bevel_depth = self.bevel_depth.clamp(0.0, half);
+        let front = (0..=segments)
+            .map(|segment| {
+                let angle = segment as f32 / segments as f32 * FRAC_PI_2;
+                let (sin, cos) = angle.sin_cos();
+                ProfilePoint {
+                    inset: self.bevel_size * (1.0 - sin),
+                    z: half - bevel_depth * (1.0 - cos),
+                    // perpendicular to the derivative of the quarter ellipse
+                    normal: Vec2::new(bevel_depth * sin, self.bevel_size * cos)
+                        .try_normalize()
+                        .unwrap_or(Vec2::X),
+                }
+            })
+            .collect::<Vec<_>>();
+        let back = front.iter().rev().map(|point| ProfilePoint {
+            z: -point.z,
+            normal: Vec2::new(point.normal.x, -point.normal.y),
+            ..*point
+        });
+        front.iter().copied().chain(back).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ProfilePoint {
+    inset: f32,
+    z: f32,
+    normal: Vec2,
+}
+
+impl Svg {
+    /// Extrudes all filled paths into a solid [`Mesh`] with front and back caps and side walls.
+    ///
+    /// The mesh has positions, normals, UVs and the colors of the paths, so it can be rendered
+    /// with a `StandardMaterial`. It is in the coordinate system of the [`Svg`] mesh, where the
+    /// y-axis is flipped compared to the SVG. The caps have UVs across the size of the [`Svg`],
+    /// the sides go once around every outline in `u` and from the front to the back in `v`.
+    /// Strokes are not extruded, and overlapping fills become overlapping solids.
+    pub fn extrude(&self, extrusion: &SvgExtrusion) -> Mesh {
+        extrude_paths(self, |_| true, extrusion)
+    }
+
+    /// Extrudes the element with the given `id`, or all elements in a group with that `id`,
+    /// see [`Svg::extrude`].
+    pub fn extrude_element(&self, id: &str, extrusion: &SvgExtrusion) -> Mesh {
+        extrude_paths(self, |path| path.has_id(id), extrusion)
+    }
+}
+
+/// Collects the vertices and triangles of a lit [`Mesh`].
+#[derive(Default)]
+pub(crate) struct MeshBuilder {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl MeshBuilder {
+    pub(crate) fn vertex(
+        &mut self,
+        position: Vec3,
+        normal: Vec3,
+        uv: Vec2,
+        color: [f32; 4],
+    ) -> u32 {
+        self.positions.push(position.to_array());
+        self.normals.push(normal.to_array());
+        self.uvs.push(uv.to_array());
+        self.colors.push(color);
+        self.positions.len() as u32 - 1
+    }
+
+    /// Adds a triangle, its winding is chosen so it faces the same way as its vertex normals.
+    pub(crate) fn facing_triangle(&mut self, [a, b, c]: [u32; 3]) {
+        let [pa, pb, pc] = [a, b, c].map(|idx| Vec3::from(self.positions[idx as usize]));
+        let normal = [a, b, c]
+            .map(|idx| Vec3::from(self.normals[idx as usize]))
+            .into_iter()
+            .sum::<Vec3>();
+        if (pb - pa).cross(pc - pa).dot(normal) < 0.0 {
+            self.indices.extend_from_slice(&[a, c, b]);
+        } else {
+            self.indices.extend_from_slice(&[a, b, c]);
+        }
+    }
+
+    pub(crate) fn build(self) -> Mesh {
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors)
+        .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+fn extrude_paths(
+    svg: &Svg,
+    filter: impl Fn(&PathDescriptor) -> bool,
+    extrusion: &SvgExtrusion,
+) -> Mesh {
+    let profile = extrusion.profile();
+    let size = svg.size.max(Vec2::splat(f32::EPSILON));
+    let mut mesh = MeshBuilder::default();
+    for path in svg.paths.iter().filter(|path| filter(path)) {
+        let DrawType::Fill(fill_rule) = path.draw_type else {
+            continue;
+        };
+        let rings = flatten(&path.transformed_segments(), extrusion.tolerance)
+            .into_iter()
+            .map(|polyline| polyline.points)
+            .collect();
+        let color = path.color.to_linear().to_f32_array();
+        for polygon in SvgPolygon::from_rings(rings, fill_rule) {
+            add_caps(&mut mesh, &polygon, &profile, size, color);
+            for ring in std::iter::once(&polygon.exterior).chain(&polygon.holes) {
+                add_sides(&mut mesh, ring, &profile, extrusion.smoothing_angle, color);
+            }
+        }
+    }
+    mesh.build()
+}
+
+/// Adds the front and back cap, moved inwards by the bevel.
+fn add_caps(
+    mesh: &mut MeshBuilder,
+    polygon: &SvgPolygon,
+    profile: &[ProfilePoint],
+    size: Vec2,
+    color: [f32; 4],
+) {
+    let inset = profile[0].inset;
+    let triangulation = SvgPolygon {
+        exterior: offset_ring(&polygon.exterior, inset),
+        holes: polygon
+            .holes
+            .iter()
+            .map(|hole| offset_ring(hole, inset))
+            .collect(),
+    }
+    .triangulate();
+    let z = profile[0].z;
+    for (normal, flip) in [(Vec3::Z, false), (Vec3::NEG_Z, true)] {
+        let offset = mesh.positions.len() as u32;
+        for point in &triangulation.vertices {
+            let uv = Vec2::new(point.x, -point.y) / size;
+            mesh.vertex(point.extend(z * normal.z), normal, uv, color);
+        }
+        for [a, b, c] in &triangulation.triangles {
+            let triangle = if flip { [a, c, b] } else { [a, b, c] };
+            mesh.indices.extend(triangle.map(|idx| idx + offset));
+        }
+    }
+}
+
+/// Adds the side walls of a ring, the filled area is on the left of the ring.
+fn add_sides(
+    mesh: &mut MeshBuilder,
+    ring: &[Vec2],
+    profile: &[ProfilePoint],
+    smoothing_angle: f32,
+    color: [f32; 4],
+) {
+    // unclosed paths may end where they start, which would be an edge without a direction
+    let mut ring = ring.to_vec();
+    ring.dedup();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    let len = ring.len();
+    if len < 3 {
+        return;
+    }
+    // outward normals of the edges, the edge `i` goes from point `i` to point `i + 1`
+    let edge_normals = (0..len)
+        .map(|i| {
+            let direction = ring[(i + 1) % len] - ring[i];
+            Vec2::new(direction.y, -direction.x).normalize_or_zero()
+        })
+        .collect::<Vec<_>>();
+    let mut distances = Vec::with_capacity(len + 1);
+    distances.push(0.0);
+    for i in 0..len {
+        distances.push(distances[i] + ring[i].distance(ring[(i + 1) % len]));
+    }
+    let perimeter = distances[len].max(f32::EPSILON);
+    let mut profile_distances = vec![0.0];
+    for pair in profile.windows(2) {
+        let step =
+            Vec2::new(pair[0].inset, pair[0].z).distance(Vec2::new(pair[1].inset, pair[1].z));
+        profile_distances.push(profile_distances.last().unwrap() + step);
+    }
+    let profile_length = profile_distances.last().unwrap().max(f32::EPSILON);
+    let min_cos = smoothing_angle.cos();
+
+    // for every level of the profile and every point, the vertex which starts the next edge and
+    // the vertex which ends the previous edge
+    let mut levels = Vec::with_capacity(profile.len());
+    for (level, profile_distance) in profile.iter().zip(&profile_distances) {
+        let points = offset_ring(&ring, level.inset);
+        let v = profile_distance / profile_length;
+        let mut vertex = |point: Vec2, normal: Vec2, u: f32| {
+            let normal = (normal * level.normal.x)
+                .extend(level.normal.y)
+                .normalize_or_zero();
+            mesh.vertex(point.extend(level.z), normal, Vec2::new(u, v), color)
+        };
+        let vertices = (0..len)
+            .map(|i| {
+                let (previous, next) = (edge_normals[(i + len - 1) % len], edge_normals[i]);
+                let u = distances[i] / perimeter;
+                if previous.dot(next) >= min_cos {
+                    let normal = (previous + next).normalize_or_zero();
+                    let start = vertex(points[i], normal, u);
+                    // the seam needs vertices for both ends of the texture
+                    let end = if i == 0 {
+                        vertex(points[i], normal, 1.0)
+                    } else {
+                        start
+                    };
+                    (start, end)
+                } else {
+                    let end = vertex(points[i], previous, if i == 0 { 1.0 } else { u });
+                    (vertex(points[i], next, u), end)
+                }
+            })
+            .collect::<Vec<_>>();
+        levels.push(vertices);
+    }
+
+    for pair in levels.windows(2) {
+        let (front, back) = (&pair[0], &pair[1]);
+        for i in 0..len {
+            let j = (i + 1) % len;
+            let (a, b) = (front[i].0, front[j].1);
+            let (a_back, b_back) = (back[i].0, back[j].1);
+            mesh.indices
+                .extend_from_slice(&[a_back, b_back, b, a_back, b, a]);
+        }
+    }
+}
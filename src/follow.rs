@@ -0,0 +1,176 @@
+//! Moves entities along the paths of an [`Svg`].
+
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        message::MessageReader,
+        system::{Query, Res},
+    },
+    math::{Mat3, Quat, Vec3},
+    platform::collections::HashSet,
+    time::Time,
+    transform::components::{GlobalTransform, Transform},
+};
+
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
+use crate::{geometry::SvgPathCurve, svg::Svg};
+
+/// What happens when a [`FollowSvgPath`] reaches the end of its path, or an
+/// [`SvgFrameAnimation`](crate::prelude::SvgFrameAnimation) its last frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathRepeat {
+    /// Stop at the end of the path.
+    #[default]
+    Once,
+    /// Jump back to the start of the path.
+    Loop,
+    /// Reverse the direction of travel.
+    PingPong,
+}
+
+/// Moves an entity along a path of an [`Svg`] which is displayed by another entity.
+///
+/// The path is selected by the `id` of its element, see [`Svg::path_curve`]. Each frame the
+/// [`Transform`] of the entity is set to the position on the path in world space, which follows
+/// the [`GlobalTransform`] and [`Origin`](crate::prelude::Origin) of the SVG entity. The entity
+/// following the path should not be a child of another entity.
+#[derive(Clone, Component, Debug)]
+pub struct FollowSvgPath {
+    /// The entity which displays the [`Svg`] with [`Svg2d`] or [`Svg3d`].
+    pub target: Entity,
+    /// The `id` of the path element.
+    pub element_id: String,
+    /// Speed in SVG units per second.
+    pub speed: f32,
+    /// The current distance along the path.
+    pub distance: f32,
+    /// What happens at the end of the path.
+    pub repeat: PathRepeat,
+    /// Rotates the entity so its x-axis points along the path.
+    pub align: bool,
+    direction: f32,
+    /// The curve of the path, with the [`Svg`] and element it was created from.
+    curve: Option<(AssetId<Svg>, String, SvgPathCurve)>,
+}
+
+impl FollowSvgPath {
+    /// Creates a new follower which starts at the beginning of the path.
+    pub fn new(target: Entity, element_id: impl Into<String>, speed: f32) -> Self {
+        Self {
+            target,
+            element_id: element_id.into(),
+            speed,
+            distance: 0.0,
+            repeat: PathRepeat::default(),
+            align: false,
+            direction: 1.0,
+            curve: None,
+        }
+    }
+
+    /// Sets what happens at the end of the path.
+    pub fn with_repeat(mut self, repeat: PathRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Rotates the entity along the path.
+    pub fn aligned(mut self) -> Self {
+        self.align = true;
+        self
+    }
+
+    /// Returns the curve of the path, once the [`Svg`] is loaded.
+    pub fn curve(&self) -> Option<&SvgPathCurve> {
+        self.curve.as_ref().map(|(_, _, curve)| curve)
+    }
+
+    /// Advances the distance by `delta` along a path of the given length, which wraps or
+    /// reverses at the ends depending on [`PathRepeat`]. A negative `delta` moves backwards.
+    ///
+    /// This is called by the plugin every frame, with the speed times the frame time.
+    pub fn advance(&mut self, delta: f32, length: f32) {
+        let length = length.max(0.0);
+        let distance = self.distance + delta * self.direction;
+        self.distance = match self.repeat {
+            PathRepeat::Once => distance.clamp(0.0, length),
+            PathRepeat::Loop if length == 0.0 => 0.0,
+            PathRepeat::Loop => distance.rem_euclid(length),
+            PathRepeat::PingPong => {
+                if distance > length {
+                    self.direction = -1.0;
+                    (2.0 * length - distance).max(0.0)
+                } else if distance < 0.0 {
+                    self.direction = 1.0;
+                    (-distance).min(length)
+                } else {
+                    distance
+                }
+            }
+        };
+    }
+}
+
+/// Moves all [`FollowSvgPath`] entities along their path.
+pub fn follow_svg_paths(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    time: Res<Time>,
+    svgs: Res<Assets<Svg>>,
+    targets: Query<(&GlobalTransform, Option<&Svg2d>, Option<&Svg3d>)>,
+    mut followers: Query<(&mut FollowSvgPath, &mut Transform)>,
+) {
+    let modified = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for (mut follow, mut transform) in &mut followers {
+        let Ok((target_transform, svg_2d, svg_3d)) = targets.get(follow.target) else {
+            continue;
+        };
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        if follow.curve.as_ref().is_none_or(|(id, element_id, _)| {
+            *id != handle.id() || *element_id != follow.element_id || modified.contains(id)
+        }) {
+            follow.curve = svg
+                .path_curve(&follow.element_id)
+                .map(|curve| (handle.id(), follow.element_id.clone(), curve));
+        }
+        let Some(length) = follow.curve().map(SvgPathCurve::length) else {
+            continue;
+        };
+
+        let speed = follow.speed;
+        follow.advance(speed * time.delta_secs(), length);
+        let Some((point, tangent)) = follow
+            .curve()
+            .map(|curve| curve.sample_with_tangent(follow.distance))
+        else {
+            continue;
+        };
+
+        transform.translation = target_transform.transform_point(point.extend(0.0));
+        if follow.align {
+            let affine = target_transform.affine();
+            let tangent = affine.transform_vector3(tangent.extend(0.0) * follow.direction);
+            let normal = affine.transform_vector3(Vec3::Z);
+            if let (Some(x), Some(z)) = (tangent.try_normalize(), normal.try_normalize()) {
+                // keep the z-axis aligned with the SVG plane, so the entity never flips over
+                transform.rotation = Quat::from_mat3(&Mat3::from_cols(x, z.cross(x), z));
+            }
+        }
+    }
+}
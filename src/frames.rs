@@ -0,0 +1,173 @@
+//! Sprite animation which shows the groups of an [`Svg`] one after another.
+
+#[cfg(feature = "2d")]
+use bevy::mesh::Mesh2d;
+#[cfg(feature = "3d")]
+use bevy::mesh::Mesh3d;
+use bevy::{
+    asset::Assets,
+    ecs::{
+        component::Component,
+        system::{Query, Res},
+    },
+    time::Time,
+};
+
+use crate::follow::PathRepeat;
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
+use crate::svg::{Svg, SvgFrame};
+
+/// Plays the [`frames`](Svg::frames) of an [`Svg`] entity, by switching its mesh to the mesh of
+/// the current frame.
+///
+/// Every outermost group with an `id` is a frame, e.g. `<g id="frame_1">` or an Inkscape layer.
+/// With a [`prefix`](Self::prefix) only the groups whose `id` starts with it are frames, ordered
+/// by the number at the end of their `id`, so `frame_2` comes before `frame_10`. Without one
+/// all groups are frames in document order. Paths outside of the frames are not shown.
+///
+/// The frames are tessellated once, together with the [`Svg`]. An [`SvgLod`](crate::prelude::SvgLod)
+/// or a [`StrokeTrim`](crate::prelude::StrokeTrim) on the same entity is ignored, since they give
+/// it a mesh of its own.
+#[derive(Clone, Component, Debug)]
+pub struct SvgFrameAnimation {
+    /// Frames per second.
+    pub fps: f32,
+    /// The start of the `id` of every frame.
+    pub prefix: Option<String>,
+    /// What happens after the last frame.
+    pub repeat: PathRepeat,
+    /// Whether the animation advances, the current frame is shown either way.
+    pub playing: bool,
+    frame: usize,
+    elapsed: f32,
+    direction: isize,
+}
+
+impl SvgFrameAnimation {
+    /// Creates an animation which loops through all frames at the given frames per second.
+    pub fn new(fps: f32) -> Self {
+        Self {
+            fps,
+            prefix: None,
+            repeat: PathRepeat::Loop,
+            playing: true,
+            frame: 0,
+            elapsed: 0.0,
+            direction: 1,
+        }
+    }
+
+    /// Only uses the groups whose `id` starts with `prefix` as frames.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets what happens after the last frame.
+    pub fn with_repeat(mut self, repeat: PathRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// The index of the current frame.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Shows the frame with the given index, it is clamped to the last frame.
+    pub fn set_frame(&mut self, frame: usize) {
+        self.frame = frame;
+        self.elapsed = 0.0;
+    }
+
+    /// Returns the frames of the [`Svg`] in the order in which they are played.
+    pub fn frames<'a>(&self, svg: &'a Svg) -> Vec<&'a SvgFrame> {
+        let Some(prefix) = &self.prefix else {
+            return svg.frames.iter().collect();
+        };
+        let mut frames = svg
+            .frames
+            .iter()
+            .filter(|frame| frame.id.starts_with(prefix.as_str()))
+            .collect::<Vec<_>>();
+        frames.sort_by_key(|frame| trailing_number(&frame.id));
+        frames
+    }
+
+    /// Moves the animation forward by the given number of frames.
+    fn advance(&mut self, steps: usize, count: usize) {
+        let last = count - 1;
+        match self.repeat {
+            PathRepeat::Once => self.frame = (self.frame + steps).min(last),
+            PathRepeat::Loop => self.frame = (self.frame + steps) % count,
+            PathRepeat::PingPong if last == 0 => self.frame = 0,
+            PathRepeat::PingPong => {
+                for _ in 0..steps % (2 * last) {
+                    if (self.direction > 0 && self.frame == last)
+                        || (self.direction < 0 && self.frame == 0)
+                    {
+                        self.direction = -self.direction;
+                    }
+                    self.frame = self.frame.saturating_add_signed(self.direction);
+                }
+            }
+        }
+    }
+}
+
+/// The number at the end of an `id`, e.g. `12` for `frame_12`.
+fn trailing_number(id: &str) -> Option<u64> {
+    let digits = id.len() - id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    id[id.len() - digits..].parse().ok()
+}
+
+/// Advances all [`SvgFrameAnimation`]s and shows the mesh of their current frame.
+pub fn animate_svg_frames(
+    time: Res<Time>,
+    svgs: Res<Assets<Svg>>,
+    mut query: Query<(
+        &mut SvgFrameAnimation,
+        Option<&Svg2d>,
+        Option<&Svg3d>,
+        Option<&mut Mesh2d>,
+        Option<&mut Mesh3d>,
+    )>,
+) {
+    for (mut animation, svg_2d, svg_3d, mesh_2d, mesh_3d) in &mut query {
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        let frames = animation.frames(svg);
+        let Some(last) = frames.len().checked_sub(1) else {
+            continue;
+        };
+        if animation.frame > last {
+            animation.frame = last;
+        }
+
+        if animation.playing && animation.fps > 0.0 {
+            animation.elapsed += time.delta_secs();
+            let steps = (animation.elapsed * animation.fps) as usize;
+            if steps > 0 {
+                animation.elapsed -= steps as f32 / animation.fps;
+                animation.advance(steps, frames.len());
+            }
+        }
+
+        let frame_mesh = &frames[animation.frame].mesh;
+        #[cfg(feature = "2d")]
+        if let Some(mut mesh) = mesh_2d.filter(|mesh| mesh.0 != *frame_mesh) {
+            mesh.0 = frame_mesh.clone();
+        }
+        #[cfg(feature = "3d")]
+        if let Some(mut mesh) = mesh_3d.filter(|mesh| mesh.0 != *frame_mesh) {
+            mesh.0 = frame_mesh.clone();
+        }
+    }
+}
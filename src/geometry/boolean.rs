@@ -0,0 +1,569 @@
+use bevy::{
+    asset::Handle,
+    math::{DVec2, Vec2},
+    platform::collections::HashMap,
+};
+use lyon_path::{PathEvent, math::Point};
+use lyon_tessellation::FillRule;
+
+use crate::{
+    geometry::{Polyline, query::outlines},
+    svg::{DrawType, PathDescriptor, Svg},
+};
+
+/// A boolean operation on the filled areas of two [`Svg`]s, see [`Svg::boolean`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /// The areas of both.
+    Union,
+    /// The area covered by both.
+    Intersection,
+    /// The area of the first without the area of the second.
+    Difference,
+    /// The areas covered by exactly one of both.
+    Xor,
+}
+
+impl Svg {
+    /// Combines the filled areas of this [`Svg`] with the ones of `other` and returns the result
+    /// as a new [`Svg`], e.g. to carve holes into a level or to reveal a fog of war.
+    ///
+    /// Both are expected to be in the same SVG coordinate system, so `other` is usually built
+    /// with [`Svg::builder`] at a position converted with [`Svg::world_to_svg`]. Every path
+    /// keeps its color and `id`:
+    /// - the paths of this [`Svg`] are kept as they are for a [`BooleanOp::Union`], and are cut
+    ///   to the area of `other` for an [`BooleanOp::Intersection`] or cut by it for a
+    ///   [`BooleanOp::Difference`] or [`BooleanOp::Xor`],
+    /// - the paths of `other` are cut by the area of this [`Svg`] and added on top for a
+    ///   [`BooleanOp::Union`] or [`BooleanOp::Xor`], and dropped otherwise.
+    ///
+    /// Curves of paths which touch the other area are flattened, `tolerance` is the maximum
+    /// distance between a curve and its approximation in SVG units. Strokes are cut along their
+    /// center line and don't follow the new edges of the fills. Only the filled areas of `other`
+    /// cut, its strokes don't.
+    ///
+    /// The mesh of the result is generated when it is added to the [`Assets<Svg>`], also when
+    /// it replaces an existing [`Svg`], or with [`Svg::tessellate`].
+    ///
+    /// [`Assets<Svg>`]: bevy::asset::Assets
+    pub fn boolean(&self, op: BooleanOp, other: &Svg, tolerance: f32) -> Svg {
+        boolean(self, |_| true, op, other, |_| true, tolerance)
+    }
+
+    /// Like [`Svg::boolean`], but only the element with the given `id` or all elements in a
+    /// group with that `id` take part in the operation, the other paths are kept as they are.
+    /// With an `other_id`, only that element or group of `other` is used.
+    pub fn element_boolean(
+        &self,
+        id: &str,
+        op: BooleanOp,
+        other: &Svg,
+        other_id: Option<&str>,
+        tolerance: f32,
+    ) -> Svg {
+        boolean(
+            self,
+            |path| path.has_id(id),
+            op,
+            other,
+            |path| other_id.is_none_or(|other_id| path.has_id(other_id)),
+            tolerance,
+        )
+    }
+}
+
+fn boolean(
+    svg: &Svg,
+    filter: impl Fn(&PathDescriptor) -> bool,
+    op: BooleanOp,
+    other: &Svg,
+    other_filter: impl Fn(&PathDescriptor) -> bool,
+    tolerance: f32,
+) -> Svg {
+    let subject = Shape::new(svg.paths.iter().filter(|path| filter(path)), tolerance);
+    let clip = Shape::new(
+        other.paths.iter().filter(|path| other_filter(path)),
+        tolerance,
+    );
+
+    let mut paths = Vec::with_capacity(svg.paths.len());
+    for path in &svg.paths {
+        if !filter(path) || op == BooleanOp::Union {
+            paths.push(path.clone());
+            continue;
+        }
+        let keep_inside = op == BooleanOp::Intersection;
+        paths.extend(cut_path(path, &clip, keep_inside, tolerance));
+    }
+    if matches!(op, BooleanOp::Union | BooleanOp::Xor) {
+        for path in other.paths.iter().filter(|path| other_filter(path)) {
+            paths.extend(cut_path(path, &subject, false, tolerance));
+        }
+    }
+
+    Svg {
+        name: svg.name.clone(),
+        size: svg.size,
+        view_box: svg.view_box,
+        paths,
+        elements: svg.elements.clone(),
+        antialias: svg.antialias,
+        layer_spacing: svg.layer_spacing,
+        mesh: Handle::default(),
+        frames: Vec::new(),
+    }
+}
+
+/// Returns the part of a path inside or outside of a shape, or `None` if nothing is left.
+fn cut_path(
+    path: &PathDescriptor,
+    shape: &Shape,
+    keep_inside: bool,
+    tolerance: f32,
+) -> Option<PathDescriptor> {
+    let polylines = outlines(path, tolerance);
+    let bounds = Bounds::from_points(polylines.iter().flat_map(|line| &line.points));
+    if !bounds.overlaps(&shape.bounds) {
+        return (!keep_inside).then(|| path.clone());
+    }
+    // the result is in SVG space, it is mapped back so the stroke width stays the same
+    let inverse = path.abs_transform.invert()?;
+    let to_local = |point: Vec2| {
+        let mut point = usvg::tiny_skia_path::Point::from_xy(point.x, point.y);
+        inverse.map_point(&mut point);
+        Point::new(point.x, point.y)
+    };
+
+    let mut segments = Vec::new();
+    let draw_type = match path.draw_type {
+        DrawType::Fill(fill_rule) => {
+            let region = Region::new(&polylines, fill_rule);
+            let rings = overlay(&region, shape, |a, b| a && b == keep_inside);
+            for ring in rings {
+                push_events(&mut segments, ring.into_iter().map(to_local), true);
+            }
+            DrawType::Fill(FillRule::NonZero)
+        }
+        DrawType::Stroke(options) => {
+            for (line, closed) in clip_polylines(&polylines, shape, keep_inside) {
+                push_events(&mut segments, line.into_iter().map(to_local), closed);
+            }
+            DrawType::Stroke(options)
+        }
+    };
+    (!segments.is_empty()).then(|| PathDescriptor {
+        segments,
+        draw_type,
+        ..path.clone()
+    })
+}
+
+fn push_events(events: &mut Vec<PathEvent>, points: impl Iterator<Item = Point>, close: bool) {
+    let mut points = points;
+    let Some(first) = points.next() else {
+        return;
+    };
+    events.push(PathEvent::Begin { at: first });
+    let mut last = first;
+    for to in points {
+        events.push(PathEvent::Line { from: last, to });
+        last = to;
+    }
+    events.push(PathEvent::End { last, first, close });
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: DVec2,
+    max: DVec2,
+}
+
+impl Bounds {
+    const EMPTY: Bounds = Bounds {
+        min: DVec2::INFINITY,
+        max: DVec2::NEG_INFINITY,
+    };
+
+    fn from_points<'a>(points: impl IntoIterator<Item = &'a Vec2>) -> Self {
+        points.into_iter().fold(Self::EMPTY, |bounds, point| {
+            let point = point.as_dvec2();
+            Bounds {
+                min: bounds.min.min(point),
+                max: bounds.max.max(point),
+            }
+        })
+    }
+
+    fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    fn overlaps(&self, other: &Bounds) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+/// The filled area of a path, made of closed rings and its fill rule.
+struct Region {
+    edges: Vec<[DVec2; 2]>,
+    fill_rule: FillRule,
+    bounds: Bounds,
+}
+
+impl Region {
+    fn new(polylines: &[Polyline], fill_rule: FillRule) -> Self {
+        // fills close all sub-paths
+        let edges = polylines
+            .iter()
+            .flat_map(|line| {
+                let len = line.points.len();
+                (0..len).map(move |i| {
+                    [line.points[i], line.points[(i + 1) % len]].map(|point| point.as_dvec2())
+                })
+            })
+            .filter(|[a, b]| a != b)
+            .collect();
+        Self {
+            edges,
+            fill_rule,
+            bounds: Bounds::from_points(polylines.iter().flat_map(|line| &line.points)),
+        }
+    }
+
+    fn contains(&self, point: DVec2) -> bool {
+        let mut winding = 0;
+        for &[a, b] in &self.edges {
+            let side = (b - a).perp_dot(point - a);
+            if a.y <= point.y {
+                if b.y > point.y && side > 0.0 {
+                    winding += 1;
+                }
+            } else if b.y <= point.y && side < 0.0 {
+                winding -= 1;
+            }
+        }
+        match self.fill_rule {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// The union of the filled areas of several paths.
+struct Shape {
+    regions: Vec<Region>,
+    bounds: Bounds,
+}
+
+impl Shape {
+    fn new<'a>(paths: impl Iterator<Item = &'a PathDescriptor>, tolerance: f32) -> Self {
+        let regions = paths
+            .filter_map(|path| match path.draw_type {
+                DrawType::Fill(fill_rule) => {
+                    Some(Region::new(&outlines(path, tolerance), fill_rule))
+                }
+                DrawType::Stroke(_) => None,
+            })
+            .filter(|region| !region.edges.is_empty())
+            .collect::<Vec<_>>();
+        let bounds = regions
+            .iter()
+            .fold(Bounds::EMPTY, |bounds, region| bounds.union(&region.bounds));
+        Self { regions, bounds }
+    }
+
+    fn contains(&self, point: DVec2) -> bool {
+        self.regions.iter().any(|region| region.contains(point))
+    }
+
+    fn edges(&self) -> impl Iterator<Item = [DVec2; 2]> + '_ {
+        self.regions
+            .iter()
+            .flat_map(|region| region.edges.iter().copied())
+    }
+}
+
+/// The distances used to merge vertices and to probe both sides of an edge, relative to the
+/// size of the geometry.
+fn epsilons(bounds: &Bounds) -> (f64, f64) {
+    let extent = (bounds.max - bounds.min).max_element().max(1.0);
+    (extent * 1e-6, extent * 1e-5)
+}
+
+/// Returns the point at parameter `t` of a segment, exactly its ends for `0.0` and `1.0`.
+fn point_at(segment: [DVec2; 2], t: f64) -> DVec2 {
+    match t {
+        0.0 => segment[0],
+        1.0 => segment[1],
+        _ => segment[0].lerp(segment[1], t),
+    }
+}
+
+/// Adds the parameters at which two segments intersect or touch to their split lists.
+fn split_params(
+    p: [DVec2; 2],
+    q: [DVec2; 2],
+    snap: f64,
+    p_params: &mut Vec<f64>,
+    q_params: &mut Vec<f64>,
+) {
+    let (r, s) = (p[1] - p[0], q[1] - q[0]);
+    let (r_length, s_length) = (r.length(), s.length());
+    let denom = r.perp_dot(s);
+    let offset = q[0] - p[0];
+    // the parameter of a point projected onto a segment, if it lies on the segment
+    let on_segment = |point: DVec2, start: DVec2, dir: DVec2, length: f64| {
+        let t = (point - start).dot(dir) / (length * length);
+        let distance = (start + dir * t).distance(point);
+        (distance <= snap && (-snap / length..=1.0 + snap / length).contains(&t))
+            .then_some(t.clamp(0.0, 1.0))
+    };
+
+    if denom.abs() <= 1e-12 * r_length * s_length {
+        // parallel, only collinear overlaps split the segments
+        p_params.extend(
+            q.iter()
+                .filter_map(|&point| on_segment(point, p[0], r, r_length)),
+        );
+        q_params.extend(
+            p.iter()
+                .filter_map(|&point| on_segment(point, q[0], s, s_length)),
+        );
+        return;
+    }
+    let t = offset.perp_dot(s) / denom;
+    let u = offset.perp_dot(r) / denom;
+    let (t_snap, u_snap) = (snap / r_length, snap / s_length);
+    if (-t_snap..=1.0 + t_snap).contains(&t) && (-u_snap..=1.0 + u_snap).contains(&u) {
+        p_params.push(t.clamp(0.0, 1.0));
+        q_params.push(u.clamp(0.0, 1.0));
+    }
+}
+
+/// Splits all segments where they intersect or touch each other, and returns the parameters
+/// of the splits of every segment, sorted and including both ends.
+fn split_segments(segments: &[[DVec2; 2]], snap: f64) -> Vec<Vec<f64>> {
+    let mut params = vec![vec![0.0, 1.0]; segments.len()];
+    let mut order = (0..segments.len()).collect::<Vec<_>>();
+    let min_x = |idx: usize| segments[idx][0].x.min(segments[idx][1].x);
+    order.sort_by(|&a, &b| min_x(a).total_cmp(&min_x(b)));
+    for (i, &a) in order.iter().enumerate() {
+        let [a0, a1] = segments[a];
+        let (a_min, a_max) = (a0.min(a1) - snap, a0.max(a1) + snap);
+        for &b in &order[i + 1..] {
+            let [b0, b1] = segments[b];
+            if b0.x.min(b1.x) > a_max.x {
+                break;
+            }
+            if b0.y.max(b1.y) < a_min.y || b0.y.min(b1.y) > a_max.y {
+                continue;
+            }
+            let (mut a_params, mut b_params) = (Vec::new(), Vec::new());
+            split_params(segments[a], segments[b], snap, &mut a_params, &mut b_params);
+            params[a].extend(a_params);
+            params[b].extend(b_params);
+        }
+    }
+    for params in &mut params {
+        params.sort_by(f64::total_cmp);
+        params.dedup();
+    }
+    params
+}
+
+/// Merges points closer than `snap` into shared vertices.
+struct Vertices {
+    positions: Vec<DVec2>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+    snap: f64,
+}
+
+impl Vertices {
+    fn new(snap: f64) -> Self {
+        Self {
+            positions: Vec::new(),
+            grid: HashMap::default(),
+            snap,
+        }
+    }
+
+    fn insert(&mut self, point: DVec2) -> usize {
+        let cell = (point / self.snap).floor();
+        let (x, y) = (cell.x as i64, cell.y as i64);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let existing = self.grid.get(&(x + dx, y + dy)).into_iter().flatten();
+                for &idx in existing {
+                    if self.positions[idx].distance(point) <= self.snap {
+                        return idx;
+                    }
+                }
+            }
+        }
+        let idx = self.positions.len();
+        self.positions.push(point);
+        self.grid.entry((x, y)).or_default().push(idx);
+        idx
+    }
+}
+
+/// Returns the closed rings around the area where `keep` is `true` for the containment in the
+/// region and the shape. The rings are oriented with the kept area on their left, so they are
+/// filled with [`FillRule::NonZero`].
+fn overlay(region: &Region, shape: &Shape, keep: impl Fn(bool, bool) -> bool) -> Vec<Vec<Vec2>> {
+    let segments = region
+        .edges
+        .iter()
+        .copied()
+        .chain(shape.edges())
+        .collect::<Vec<_>>();
+    let (snap, probe) = epsilons(&region.bounds.union(&shape.bounds));
+    let params = split_segments(&segments, snap);
+
+    let mut vertices = Vertices::new(snap);
+    let mut edges = Vec::new();
+    for (segment, params) in segments.iter().zip(&params) {
+        let points = params
+            .iter()
+            .map(|&t| vertices.insert(point_at(*segment, t)))
+            .collect::<Vec<_>>();
+        for pair in points.windows(2) {
+            if pair[0] != pair[1] {
+                edges.push((pair[0].min(pair[1]), pair[0].max(pair[1])));
+            }
+        }
+    }
+    // overlapping edges of both sides are only kept once
+    edges.sort_unstable();
+    edges.dedup();
+
+    // keep the edges between a kept and a dropped area, with the kept area on the left
+    let positions = &vertices.positions;
+    let is_kept = |point: DVec2| keep(region.contains(point), shape.contains(point));
+    let mut outgoing = vec![Vec::new(); positions.len()];
+    for (a, b) in edges {
+        let (start, end) = (positions[a], positions[b]);
+        let normal = (end - start).normalize().perp();
+        let mid = start.midpoint(end);
+        let (left, right) = (is_kept(mid + normal * probe), is_kept(mid - normal * probe));
+        if left != right {
+            let (from, to) = if left { (a, b) } else { (b, a) };
+            outgoing[from].push(to);
+        }
+    }
+    link_rings(positions, &mut outgoing)
+}
+
+/// Links directed edges into closed rings. At shared vertices, the ring turns to the next
+/// edge clockwise, which keeps touching rings apart.
+fn link_rings(positions: &[DVec2], outgoing: &mut [Vec<usize>]) -> Vec<Vec<Vec2>> {
+    let mut rings = Vec::new();
+    for start in 0..positions.len() {
+        while let Some(first) = outgoing[start].pop() {
+            let mut ring = vec![start];
+            let (mut prev, mut current) = (start, first);
+            while current != start {
+                ring.push(current);
+                let back = positions[prev] - positions[current];
+                let next = outgoing[current]
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| {
+                        let angle = |to: usize| {
+                            let dir = positions[to] - positions[current];
+                            let angle = dir.angle_to(back);
+                            if angle <= 0.0 {
+                                angle + std::f64::consts::TAU
+                            } else {
+                                angle
+                            }
+                        };
+                        angle(**a).total_cmp(&angle(**b))
+                    })
+                    .map(|(idx, _)| idx);
+                // an open chain can only come from degenerate input, it is closed directly
+                let Some(next) = next else {
+                    break;
+                };
+                prev = current;
+                current = outgoing[current].swap_remove(next);
+            }
+            if ring.len() > 2 {
+                rings.push(ring.iter().map(|&idx| positions[idx].as_vec2()).collect());
+            }
+        }
+    }
+    rings
+}
+
+/// Cuts polylines by the edges of a shape and returns the parts inside or outside of it, and
+/// whether they are still closed.
+fn clip_polylines(
+    polylines: &[Polyline],
+    shape: &Shape,
+    keep_inside: bool,
+) -> Vec<(Vec<Vec2>, bool)> {
+    let (snap, _) = epsilons(&shape.bounds);
+    let shape_edges = shape.edges().collect::<Vec<_>>();
+    let mut result = Vec::new();
+    for polyline in polylines {
+        let points = &polyline.points;
+        let count = if polyline.closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let mut pieces = Vec::<Vec<DVec2>>::new();
+        let mut current = Vec::<DVec2>::new();
+        let mut starts_kept = false;
+        for i in 0..count {
+            let segment = [points[i], points[(i + 1) % points.len()]].map(|p| p.as_dvec2());
+            let mut params = vec![0.0, 1.0];
+            for &edge in &shape_edges {
+                split_params(segment, edge, snap, &mut params, &mut Vec::new());
+            }
+            params.sort_by(f64::total_cmp);
+            params.dedup();
+            for pair in params.windows(2) {
+                let [start, end] = [pair[0], pair[1]].map(|t| point_at(segment, t));
+                if shape.contains(start.midpoint(end)) == keep_inside {
+                    if current.is_empty() {
+                        starts_kept |= i == 0 && pair[0] == 0.0;
+                        current.push(start);
+                    }
+                    current.push(end);
+                } else if !current.is_empty() {
+                    pieces.push(std::mem::take(&mut current));
+                }
+            }
+        }
+        if polyline.closed && pieces.is_empty() && starts_kept {
+            // nothing was cut, the closing point is added again by `End`
+            current.pop();
+            result.push((current.iter().map(|p| p.as_vec2()).collect(), true));
+            continue;
+        }
+        if !current.is_empty() {
+            // a piece which runs over the start of a closed polyline is joined with the first
+            if polyline.closed
+                && starts_kept
+                && let Some(first) = pieces.first_mut()
+            {
+                current.extend(first.drain(1..));
+                *first = current;
+            } else {
+                pieces.push(current);
+            }
+        }
+        result.extend(
+            pieces
+                .into_iter()
+                .filter(|piece| piece.len() > 1)
+                .map(|piece| (piece.iter().map(|p| p.as_vec2()).collect(), false)),
+        );
+    }
+    result
+}
@@ -0,0 +1,99 @@
+use bevy::math::Vec2;
+
+use crate::{
+    geometry::{SvgPolygon, flatten},
+    svg::{DrawType, PathDescriptor, Svg},
+};
+
+/// An engine agnostic collision shape, created from the geometry of an [`Svg`].
+///
+/// All points are in the coordinate system of the [`Svg`] mesh, the y-axis is flipped compared
+/// to the SVG. Scale them with the [`Transform`](bevy::transform::components::Transform) of the
+/// SVG entity and apply its [`Origin`](crate::prelude::Origin) to get world space positions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColliderShape {
+    /// A filled area, created from filled paths.
+    Polygon(SvgPolygon),
+    /// An open or closed line, created from paths which are stroked but not filled.
+    Polyline {
+        /// The points of the line.
+        points: Vec<Vec2>,
+        /// `true` if the last point connects to the first one.
+        closed: bool,
+    },
+}
+
+impl Svg {
+    /// Creates collision shapes from all elements.
+    ///
+    /// Curves are flattened, `tolerance` is the maximum distance between a curve and its
+    /// approximation in SVG units.
+    pub fn collider_shapes(&self, tolerance: f32) -> Vec<ColliderShape> {
+        collider_shapes(&self.paths, |_| true, tolerance)
+    }
+
+    /// Creates collision shapes from the element with the given `id`, or from all elements in
+    /// a group with that `id`.
+    pub fn element_collider_shapes(&self, id: &str, tolerance: f32) -> Vec<ColliderShape> {
+        collider_shapes(&self.paths, |path| path.has_id(id), tolerance)
+    }
+}
+
+fn collider_shapes(
+    paths: &[PathDescriptor],
+    filter: impl Fn(&PathDescriptor) -> bool,
+    tolerance: f32,
+) -> Vec<ColliderShape> {
+    let mut shapes = Vec::new();
+    for (idx, path) in paths.iter().enumerate() {
+        if !filter(path) {
+            continue;
+        }
+        let polylines = || flatten(&path.transformed_segments(), tolerance);
+        match path.draw_type {
+            DrawType::Fill(fill_rule) => {
+                let rings = polylines()
+                    .into_iter()
+                    .map(|polyline| polyline.points)
+                    .collect();
+                shapes.extend(
+                    SvgPolygon::from_rings(rings, fill_rule)
+                        .into_iter()
+                        .map(ColliderShape::Polygon),
+                );
+            }
+            DrawType::Stroke(_) => {
+                // the fill of the same element is created right before or after the stroke
+                let is_filled = [idx.checked_sub(1), Some(idx + 1)]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|other| paths.get(other))
+                    .any(|other| {
+                        matches!(other.draw_type, DrawType::Fill(_)) && other.is_same_element(path)
+                    });
+                if is_filled {
+                    continue;
+                }
+                shapes.extend(
+                    polylines()
+                        .into_iter()
+                        .map(|polyline| ColliderShape::Polyline {
+                            points: polyline.points,
+                            closed: polyline.closed,
+                        }),
+                );
+            }
+        }
+    }
+    shapes
+}
+
+impl ColliderShape {
+    /// Splits the shape into convex polygons, polylines have no area and return nothing.
+    pub fn convex_decomposition(&self) -> Vec<Vec<Vec2>> {
+        match self {
+            ColliderShape::Polygon(polygon) => polygon.convex_decomposition(),
+            ColliderShape::Polyline { .. } => Vec::new(),
+        }
+    }
+}
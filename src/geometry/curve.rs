@@ -0,0 +1,84 @@
+use bevy::math::{
+    Vec2, Vec3,
+    curve::{Curve, Interval},
+};
+
+use crate::{geometry::PathMeasure, svg::Svg};
+
+/// A path of an [`Svg`] as an arc length parameterized [`Curve`].
+///
+/// The curve is sampled by distance, its domain goes from `0.0` to the length of the path. Points
+/// are in the local space of the mesh of the [`Svg`], which means the y-axis is flipped compared
+/// to the SVG coordinate system. The [`Origin`](crate::prelude::Origin) is applied through the
+/// [`GlobalTransform`](bevy::transform::components::GlobalTransform) of the SVG entity.
+#[derive(Debug, Clone, Default)]
+pub struct SvgPathCurve {
+    measure: PathMeasure,
+}
+
+impl SvgPathCurve {
+    /// Creates a curve from the element with the given `id`. If the `id` belongs to a group,
+    /// all paths of that group are joined in document order.
+    ///
+    /// Returns `None` if there is no element with the `id`, or if it has no length.
+    pub fn from_svg(svg: &Svg, id: &str) -> Option<Self> {
+        let segments = svg
+            .element_paths(id)
+            .flat_map(|path| path.transformed_segments())
+            .collect::<Vec<_>>();
+        let measure = PathMeasure::new(&segments);
+        (measure.length() > 0.0).then_some(Self { measure })
+    }
+
+    /// Length of the path.
+    pub fn length(&self) -> f32 {
+        self.measure.length()
+    }
+
+    /// Samples the position and the normalized tangent at the given distance.
+    ///
+    /// The distance is clamped to the domain of the curve.
+    pub fn sample_with_tangent(&self, distance: f32) -> (Vec2, Vec2) {
+        self.measure
+            .sample(distance)
+            .map(|(point, tangent)| {
+                (
+                    Vec2::new(point.x, -point.y),
+                    Vec2::new(tangent.x, -tangent.y),
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Samples the normalized tangent, pointing in the direction of travel.
+    pub fn tangent(&self, distance: f32) -> Vec2 {
+        self.sample_with_tangent(distance).1
+    }
+
+    /// Samples the normal, which is the tangent rotated counter-clockwise by 90 degrees.
+    pub fn normal(&self, distance: f32) -> Vec2 {
+        self.tangent(distance).perp()
+    }
+
+    /// Returns this curve with points in the xy-plane of 3D space.
+    pub fn to_3d(&self) -> impl Curve<Vec3> + '_ {
+        bevy::math::curve::CurveExt::map(self, |point: Vec2| point.extend(0.0))
+    }
+}
+
+impl Curve<Vec2> for SvgPathCurve {
+    fn domain(&self) -> Interval {
+        Interval::new(0.0, self.length()).unwrap_or(Interval::UNIT)
+    }
+
+    fn sample_unchecked(&self, t: f32) -> Vec2 {
+        self.sample_with_tangent(t).0
+    }
+}
+
+impl Svg {
+    /// Returns the path of the element with the given `id` as [`SvgPathCurve`].
+    pub fn path_curve(&self, id: &str) -> Option<SvgPathCurve> {
+        SvgPathCurve::from_svg(self, id)
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::math::Vec2;
+use lyon_geom::{CubicBezierSegment, QuadraticBezierSegment};
+use lyon_path::{PathEvent, math::Point};
+
+/// The smallest tolerance used by [`flatten`].
+pub const MIN_TOLERANCE: f32 = 1e-3;
+
+/// A sub-path approximated by line segments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Polyline {
+    /// The points of the sub-path, the first point is not repeated for closed sub-paths.
+    pub points: Vec<Vec2>,
+    /// `true` if the sub-path was closed.
+    pub closed: bool,
+}
+
+/// Approximates all sub-paths of the given path events with line segments.
+///
+/// The `tolerance` is the maximum distance between a curve and its approximation, it is at least
+/// [`MIN_TOLERANCE`] since smaller values make the number of segments explode. The y-axis is
+/// flipped, so the resulting points are in the same coordinate system as the mesh of an
+/// [`Svg`](crate::svg::Svg).
+pub fn flatten(events: &[PathEvent], tolerance: f32) -> Vec<Polyline> {
+    let tolerance = tolerance.max(MIN_TOLERANCE);
+    let mut polylines = Vec::new();
+    let mut current = Polyline::default();
+    let to_vec2 = |point: Point| Vec2::new(point.x, -point.y);
+    for event in events {
+        match *event {
+            PathEvent::Begin { at } => {
+                current = Polyline {
+                    points: vec![to_vec2(at)],
+                    closed: false,
+                };
+            }
+            PathEvent::Line { to, .. } => current.points.push(to_vec2(to)),
+            PathEvent::Quadratic { from, ctrl, to } => {
+                QuadraticBezierSegment { from, ctrl, to }
+                    .for_each_flattened(tolerance, &mut |line| {
+                        current.points.push(to_vec2(line.to))
+                    });
+            }
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                CubicBezierSegment {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                }
+                .for_each_flattened(tolerance, &mut |line| current.points.push(to_vec2(line.to)));
+            }
+            PathEvent::End { close, .. } => {
+                current.points.dedup();
+                if close
+                    && current.points.len() > 1
+                    && current.points.first() == current.points.last()
+                {
+                    current.points.pop();
+                }
+                current.closed = close;
+                if current.points.len() > 1 {
+                    polylines.push(std::mem::take(&mut current));
+                }
+            }
+        }
+    }
+    polylines
+}
+
+/// Signed area of a closed ring, positive if it is counter-clockwise.
+pub fn signed_area(ring: &[Vec2]) -> f32 {
+    let len = ring.len();
+    (0..len)
+        .map(|i| ring[i].perp_dot(ring[(i + 1) % len]))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Returns `true` if the point lies inside the closed ring, which must not intersect itself.
+pub fn ring_contains(ring: &[Vec2], point: Vec2) -> bool {
+    winding_number(ring, point) != 0
+}
+
+/// Winding number of a closed ring around a point.
+pub fn winding_number(ring: &[Vec2], point: Vec2) -> i32 {
+    let len = ring.len();
+    let mut winding = 0;
+    for i in 0..len {
+        let (a, b) = (ring[i], ring[(i + 1) % len]);
+        let side = (b - a).perp_dot(point - a);
+        if a.y <= point.y {
+            if b.y > point.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if b.y <= point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Moves every edge of a closed ring to its left by `distance`, a negative distance moves it
+/// to the right.
+pub(crate) fn offset_ring(ring: &[Vec2], distance: f32) -> Vec<Vec2> {
+    if distance == 0.0 {
+        return ring.to_vec();
+    }
+    let len = ring.len();
+    (0..len)
+        .map(|i| {
+            let (prev, point, next) = (ring[(i + len - 1) % len], ring[i], ring[(i + 1) % len]);
+            let n0 = (point - prev).normalize_or_zero().perp();
+            let n1 = (next - point).normalize_or_zero().perp();
+            let miter = (n0 + n1).normalize_or_zero();
+            // limit the miter length on sharp corners
+            let scale = miter.dot(n1).max(0.25);
+            point + miter * distance / scale
+        })
+        .collect()
+}
@@ -0,0 +1,273 @@
+use lyon_geom::{CubicBezierSegment, LineSegment, QuadraticBezierSegment};
+use lyon_path::{
+    PathEvent,
+    math::{Point, Vector},
+};
+
+/// Number of samples used to build the arc length lookup table of a curve.
+const CURVE_SAMPLES: usize = 32;
+
+/// A single segment of a path.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Segment {
+    Line(LineSegment<f32>),
+    Quadratic(QuadraticBezierSegment<f32>),
+    Cubic(CubicBezierSegment<f32>),
+}
+
+impl Segment {
+    fn sample(&self, t: f32) -> Point {
+        match self {
+            Segment::Line(s) => s.sample(t),
+            Segment::Quadratic(s) => s.sample(t),
+            Segment::Cubic(s) => s.sample(t),
+        }
+    }
+
+    fn derivative(&self, t: f32) -> Vector {
+        match self {
+            Segment::Line(s) => s.to_vector(),
+            Segment::Quadratic(s) => s.derivative(t),
+            Segment::Cubic(s) => s.derivative(t),
+        }
+    }
+
+    fn split_range(&self, t: std::ops::Range<f32>) -> Segment {
+        match self {
+            Segment::Line(s) => Segment::Line(s.split_range(t)),
+            Segment::Quadratic(s) => Segment::Quadratic(s.split_range(t)),
+            Segment::Cubic(s) => Segment::Cubic(s.split_range(t)),
+        }
+    }
+
+    fn to_event(self) -> PathEvent {
+        match self {
+            Segment::Line(s) => PathEvent::Line {
+                from: s.from,
+                to: s.to,
+            },
+            Segment::Quadratic(s) => PathEvent::Quadratic {
+                from: s.from,
+                ctrl: s.ctrl,
+                to: s.to,
+            },
+            Segment::Cubic(s) => PathEvent::Cubic {
+                from: s.from,
+                ctrl1: s.ctrl1,
+                ctrl2: s.ctrl2,
+                to: s.to,
+            },
+        }
+    }
+}
+
+/// A segment together with its arc length lookup table.
+#[derive(Debug, Clone)]
+struct MeasuredSegment {
+    segment: Segment,
+    /// Index of the sub-path this segment belongs to.
+    sub_path: usize,
+    /// Distance from the start of the path to the start of this segment.
+    offset: f32,
+    /// Cumulative lengths at `t = i / (lut.len() - 1)`, the last entry is the segment length.
+    lut: Vec<f32>,
+}
+
+impl MeasuredSegment {
+    fn length(&self) -> f32 {
+        self.lut.last().copied().unwrap_or_default()
+    }
+
+    /// Maps a distance along this segment to the curve parameter `t`.
+    fn t_at(&self, distance: f32) -> f32 {
+        let length = self.length();
+        if length <= 0.0 || self.lut.len() < 2 {
+            return 0.0;
+        }
+        let distance = distance.clamp(0.0, length);
+        let idx = self.lut.partition_point(|&l| l < distance).max(1);
+        let (l0, l1) = (self.lut[idx - 1], self.lut[idx]);
+        let local = if l1 > l0 {
+            (distance - l0) / (l1 - l0)
+        } else {
+            0.0
+        };
+        (idx as f32 - 1.0 + local) / (self.lut.len() - 1) as f32
+    }
+}
+
+/// Measures a path by arc length, which allows sampling and cutting it at a given distance.
+///
+/// All sub-paths are measured one after another, a closed sub-path includes its closing line.
+#[derive(Debug, Clone, Default)]
+pub struct PathMeasure {
+    segments: Vec<MeasuredSegment>,
+    length: f32,
+}
+
+impl PathMeasure {
+    /// Measures the given path events.
+    pub fn new<'a>(events: impl IntoIterator<Item = &'a PathEvent>) -> Self {
+        let mut measure = Self::default();
+        let mut sub_path = 0;
+        let (mut first, mut current) = (Point::zero(), Point::zero());
+        for event in events {
+            let segment = match *event {
+                PathEvent::Begin { at } => {
+                    first = at;
+                    current = at;
+                    continue;
+                }
+                PathEvent::Line { from, to } => Segment::Line(LineSegment { from, to }),
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    Segment::Quadratic(QuadraticBezierSegment { from, ctrl, to })
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => Segment::Cubic(CubicBezierSegment {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                }),
+                // `last` is not reliable for closed sub-paths, so the current position is used
+                PathEvent::End { close, .. } => {
+                    if close && current != first {
+                        measure.push(
+                            Segment::Line(LineSegment {
+                                from: current,
+                                to: first,
+                            }),
+                            sub_path,
+                        );
+                    }
+                    sub_path += 1;
+                    continue;
+                }
+            };
+            current = segment.sample(1.0);
+            measure.push(segment, sub_path);
+        }
+        measure
+    }
+
+    fn push(&mut self, segment: Segment, sub_path: usize) {
+        let lut = match segment {
+            Segment::Line(line) => vec![0.0, line.length()],
+            _ => {
+                let mut lut = Vec::with_capacity(CURVE_SAMPLES + 1);
+                let mut prev = segment.sample(0.0);
+                let mut length = 0.0;
+                lut.push(0.0);
+                for i in 1..=CURVE_SAMPLES {
+                    let point = segment.sample(i as f32 / CURVE_SAMPLES as f32);
+                    length += (point - prev).length();
+                    lut.push(length);
+                    prev = point;
+                }
+                lut
+            }
+        };
+        let measured = MeasuredSegment {
+            segment,
+            sub_path,
+            offset: self.length,
+            lut,
+        };
+        self.length += measured.length();
+        self.segments.push(measured);
+    }
+
+    /// Total length of the path.
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// Returns `true` if the path has no measurable segments.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    fn segment_at(&self, distance: f32) -> Option<&MeasuredSegment> {
+        let idx = self
+            .segments
+            .partition_point(|s| s.offset + s.length() < distance);
+        self.segments.get(idx).or_else(|| self.segments.last())
+    }
+
+    /// Samples the position and the normalized tangent at the given distance along the path.
+    ///
+    /// The distance is clamped to the length of the path.
+    pub fn sample(&self, distance: f32) -> Option<(Point, Vector)> {
+        let distance = distance.clamp(0.0, self.length);
+        let measured = self.segment_at(distance)?;
+        let t = measured.t_at(distance - measured.offset);
+        let tangent = measured.segment.derivative(t);
+        let tangent = if tangent.square_length() > 0.0 {
+            tangent.normalize()
+        } else {
+            Vector::zero()
+        };
+        Some((measured.segment.sample(t), tangent))
+    }
+
+    /// Returns the part of the path between the two distances as path events.
+    ///
+    /// Every sub-path touched by the range becomes an open sub-path in the result.
+    pub fn sub_path_events(&self, from: f32, to: f32) -> Vec<PathEvent> {
+        let from = from.clamp(0.0, self.length);
+        let to = to.clamp(0.0, self.length);
+        let mut events = Vec::new();
+        if to <= from {
+            return events;
+        }
+
+        let mut current: Option<(usize, Point, Point)> = None;
+        for measured in &self.segments {
+            let start = measured.offset;
+            let end = start + measured.length();
+            if end < from || start > to {
+                continue;
+            }
+            let t0 = measured.t_at(from - start);
+            let t1 = measured.t_at(to - start);
+            if t1 <= t0 && measured.length() > 0.0 {
+                continue;
+            }
+            let piece = measured.segment.split_range(t0..t1);
+            let piece_start = piece.sample(0.0);
+            match current {
+                Some((sub_path, ..)) if sub_path == measured.sub_path => {}
+                Some((_, first, last)) => {
+                    events.push(PathEvent::End {
+                        last,
+                        first,
+                        close: false,
+                    });
+                    events.push(PathEvent::Begin { at: piece_start });
+                    current = Some((measured.sub_path, piece_start, piece_start));
+                }
+                None => {
+                    events.push(PathEvent::Begin { at: piece_start });
+                    current = Some((measured.sub_path, piece_start, piece_start));
+                }
+            }
+            let piece_end = piece.sample(1.0);
+            events.push(piece.to_event());
+            if let Some((_, _, last)) = current.as_mut() {
+                *last = piece_end;
+            }
+        }
+        if let Some((_, first, last)) = current {
+            events.push(PathEvent::End {
+                last,
+                first,
+                close: false,
+            });
+        }
+        events
+    }
+}
@@ -0,0 +1,20 @@
+//! Helpers to measure and process the geometry of [`Svg`](crate::svg::Svg) paths.
+
+mod boolean;
+mod collider;
+mod curve;
+mod flatten;
+mod measure;
+mod navmesh;
+mod polygon;
+mod query;
+
+pub use boolean::BooleanOp;
+pub use collider::ColliderShape;
+pub use curve::SvgPathCurve;
+pub(crate) use flatten::offset_ring;
+pub use flatten::{MIN_TOLERANCE, Polyline, flatten, ring_contains, signed_area, winding_number};
+pub use measure::PathMeasure;
+pub use navmesh::{NavMesh, NavMeshOptions};
+pub use polygon::{SvgPolygon, Triangulation};
+pub(crate) use query::path_bounds;
@@ -0,0 +1,340 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use bevy::{math::Vec2, platform::collections::HashMap};
+use lyon_path::math::Point;
+use lyon_tessellation::{BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex};
+
+use crate::{
+    geometry::{ColliderShape, SvgPolygon, offset_ring, polygon::ring_events, signed_area},
+    svg::Svg,
+};
+
+/// Options to build a [`NavMesh`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NavMeshOptions {
+    /// Radius of the agents, walkable areas shrink and obstacles grow by this amount.
+    pub agent_radius: f32,
+    /// Maximum distance between a curve and its approximation, in SVG units.
+    pub tolerance: f32,
+}
+
+impl Default for NavMeshOptions {
+    fn default() -> Self {
+        Self {
+            agent_radius: 0.0,
+            tolerance: 0.5,
+        }
+    }
+}
+
+impl NavMeshOptions {
+    /// Sets the agent radius.
+    pub fn with_agent_radius(mut self, agent_radius: f32) -> Self {
+        self.agent_radius = agent_radius;
+        self
+    }
+
+    /// Sets the tolerance used to flatten curves.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// A 2D navigation mesh, made of counter-clockwise triangles.
+///
+/// All positions are in the coordinate system of the [`Svg`] mesh, the y-axis is flipped
+/// compared to the SVG.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NavMesh {
+    /// The vertices of the mesh.
+    pub vertices: Vec<Vec2>,
+    /// The walkable triangles, as indices into `vertices`.
+    pub triangles: Vec<[u32; 3]>,
+    /// The neighbor across each edge of a triangle, edge `i` goes from vertex `i` to `i + 1`.
+    pub neighbors: Vec<[Option<u32>; 3]>,
+}
+
+impl Svg {
+    /// Builds a [`NavMesh`] from the filled areas of the `walkable` elements, minus the filled
+    /// areas of the `obstacles` elements. Both are given as element `id`s and may be groups.
+    ///
+    /// Walkable areas should not overlap each other, obstacles may overlap anything.
+    pub fn navmesh(
+        &self,
+        walkable: &[&str],
+        obstacles: &[&str],
+        options: NavMeshOptions,
+    ) -> NavMesh {
+        let polygons = |ids: &[&str]| {
+            ids.iter()
+                .flat_map(|id| self.element_collider_shapes(id, options.tolerance))
+                .filter_map(|shape| match shape {
+                    ColliderShape::Polygon(polygon) => Some(polygon),
+                    ColliderShape::Polyline { .. } => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        NavMesh::new(
+            &polygons(walkable),
+            &polygons(obstacles),
+            options.agent_radius,
+        )
+    }
+}
+
+impl NavMesh {
+    /// Builds a navigation mesh from walkable polygons minus the obstacle polygons.
+    ///
+    /// Holes of obstacles are ignored, obstacles are treated as solid.
+    pub fn new(walkable: &[SvgPolygon], obstacles: &[SvgPolygon], agent_radius: f32) -> Self {
+        // Every ring gets offset towards the walkable side, which is left of its direction.
+        // Areas narrower than the agent turn inside out and are dropped.
+        let walkable = walkable
+            .iter()
+            .map(|polygon| SvgPolygon {
+                exterior: offset_ring(&polygon.exterior, agent_radius),
+                holes: polygon
+                    .holes
+                    .iter()
+                    .map(|hole| offset_ring(hole, agent_radius))
+                    .collect(),
+            })
+            .filter(|polygon| signed_area(&polygon.exterior) > 0.0)
+            .collect::<Vec<_>>();
+        let obstacles = obstacles
+            .iter()
+            .map(|polygon| {
+                // obstacles are clockwise, so they are subtracted with the non-zero fill rule
+                let mut ring = offset_ring(&polygon.exterior, -agent_radius);
+                ring.reverse();
+                ring
+            })
+            .collect::<Vec<_>>();
+
+        let mut events = Vec::new();
+        for polygon in &walkable {
+            for ring in std::iter::once(&polygon.exterior).chain(&polygon.holes) {
+                ring_events(ring, &mut events);
+            }
+        }
+        for ring in &obstacles {
+            ring_events(ring, &mut events);
+        }
+
+        let mut buffers = lyon_tessellation::VertexBuffers::<Point, u32>::new();
+        let result = FillTessellator::new().tessellate(
+            events,
+            &FillOptions::tolerance(0.01).with_fill_rule(FillRule::NonZero),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position()),
+        );
+        if let Err(e) = result {
+            bevy::log::error!("FillTessellator error: {:?}", e);
+        }
+
+        let vertices = buffers
+            .vertices
+            .iter()
+            .map(|point| Vec2::new(point.x, point.y))
+            .collect::<Vec<_>>();
+        let triangles = buffers
+            .indices
+            .chunks_exact(3)
+            .filter_map(|tri| {
+                let [a, b, c] = [tri[0], tri[1], tri[2]];
+                let [pa, pb, pc] = [a, b, c].map(|i| vertices[i as usize]);
+                let centroid = (pa + pb + pc) / 3.0;
+                let is_walkable = walkable.iter().any(|polygon| polygon.contains(centroid))
+                    && !obstacles
+                        .iter()
+                        .any(|ring| crate::geometry::ring_contains(ring, centroid));
+                is_walkable.then_some(if (pb - pa).perp_dot(pc - pa) < 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut edges = HashMap::<(u32, u32), u32>::default();
+        for (idx, tri) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                edges.insert((tri[i], tri[(i + 1) % 3]), idx as u32);
+            }
+        }
+        let neighbors = triangles
+            .iter()
+            .map(|tri| [0, 1, 2].map(|i| edges.get(&(tri[(i + 1) % 3], tri[i])).copied()))
+            .collect();
+
+        NavMesh {
+            vertices,
+            triangles,
+            neighbors,
+        }
+    }
+
+    fn corners(&self, triangle: usize) -> [Vec2; 3] {
+        self.triangles[triangle].map(|i| self.vertices[i as usize])
+    }
+
+    fn centroid(&self, triangle: usize) -> Vec2 {
+        let [a, b, c] = self.corners(triangle);
+        (a + b + c) / 3.0
+    }
+
+    /// Returns the index of the triangle which contains the point.
+    pub fn triangle_at(&self, point: Vec2) -> Option<usize> {
+        (0..self.triangles.len()).find(|&idx| {
+            let [a, b, c] = self.corners(idx);
+            (b - a).perp_dot(point - a) >= 0.0
+                && (c - b).perp_dot(point - b) >= 0.0
+                && (a - c).perp_dot(point - c) >= 0.0
+        })
+    }
+
+    /// Returns `true` if the point is on the navigation mesh.
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.triangle_at(point).is_some()
+    }
+
+    /// Finds the shortest path between two points.
+    ///
+    /// The triangles to cross are searched with A*, afterwards the path is straightened with
+    /// the funnel algorithm. Returns `None` if one of the points is not on the mesh or if there
+    /// is no connection between them. The path contains both `start` and `end`.
+    pub fn find_path(&self, start: Vec2, end: Vec2) -> Option<Vec<Vec2>> {
+        let from = self.triangle_at(start)?;
+        let to = self.triangle_at(end)?;
+        let corridor = self.find_corridor(from, to, end)?;
+
+        // the portals between the triangles, as (left, right) seen in the direction of travel
+        let mut portals = Vec::with_capacity(corridor.len() + 1);
+        portals.push((start, start));
+        for pair in corridor.windows(2) {
+            let tri = self.triangles[pair[0]];
+            let edge = self.neighbors[pair[0]]
+                .iter()
+                .position(|&n| n == Some(pair[1] as u32))?;
+            let right = self.vertices[tri[edge] as usize];
+            let left = self.vertices[tri[(edge + 1) % 3] as usize];
+            portals.push((left, right));
+        }
+        portals.push((end, end));
+
+        Some(string_pull(&portals))
+    }
+
+    /// A* search over the triangles.
+    fn find_corridor(&self, from: usize, to: usize, end: Vec2) -> Option<Vec<usize>> {
+        #[derive(PartialEq)]
+        struct Node {
+            cost: f32,
+            triangle: usize,
+        }
+        impl Eq for Node {}
+        impl Ord for Node {
+            fn cmp(&self, other: &Self) -> Ordering {
+                other.cost.total_cmp(&self.cost)
+            }
+        }
+        impl PartialOrd for Node {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        let mut open = BinaryHeap::new();
+        let mut came_from = HashMap::<usize, usize>::default();
+        let mut costs = HashMap::<usize, f32>::default();
+        costs.insert(from, 0.0);
+        open.push(Node {
+            cost: self.centroid(from).distance(end),
+            triangle: from,
+        });
+
+        while let Some(Node { triangle, .. }) = open.pop() {
+            if triangle == to {
+                let mut corridor = vec![to];
+                let mut current = to;
+                while let Some(&prev) = came_from.get(&current) {
+                    corridor.push(prev);
+                    current = prev;
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            let cost = costs[&triangle];
+            let center = self.centroid(triangle);
+            for neighbor in self.neighbors[triangle].iter().flatten() {
+                let neighbor = *neighbor as usize;
+                let neighbor_center = self.centroid(neighbor);
+                let new_cost = cost + center.distance(neighbor_center);
+                if costs.get(&neighbor).is_none_or(|&old| new_cost < old) {
+                    costs.insert(neighbor, new_cost);
+                    came_from.insert(neighbor, triangle);
+                    open.push(Node {
+                        cost: new_cost + neighbor_center.distance(end),
+                        triangle: neighbor,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Straightens a path through a list of portals with the "simple stupid funnel algorithm".
+fn string_pull(portals: &[(Vec2, Vec2)]) -> Vec<Vec2> {
+    // positive if `c` is left of the line from `a` to `b`
+    let cross = |a: Vec2, b: Vec2, c: Vec2| (b - a).perp_dot(c - a);
+
+    let mut path = vec![portals[0].0];
+    let (mut apex, mut left, mut right) = (portals[0].0, portals[0].0, portals[0].1);
+    let (mut left_idx, mut right_idx) = (0, 0);
+    let mut push = |point: Vec2| {
+        if path.last() != Some(&point) {
+            path.push(point);
+        }
+    };
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (portal_left, portal_right) = portals[i];
+
+        // tighten the funnel on the right side
+        if cross(apex, right, portal_right) >= 0.0 {
+            if apex == right || cross(apex, left, portal_right) < 0.0 {
+                right = portal_right;
+                right_idx = i;
+            } else {
+                // the right side crossed the left side, so the left side becomes the new apex
+                push(left);
+                apex = left;
+                right = apex;
+                right_idx = left_idx;
+                i = left_idx + 1;
+                continue;
+            }
+        }
+
+        // tighten the funnel on the left side
+        if cross(apex, left, portal_left) <= 0.0 {
+            if apex == left || cross(apex, right, portal_left) > 0.0 {
+                left = portal_left;
+                left_idx = i;
+            } else {
+                push(right);
+                apex = right;
+                left = apex;
+                left_idx = right_idx;
+                i = right_idx + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    push(portals[portals.len() - 1].0);
+    path
+}
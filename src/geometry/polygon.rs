@@ -0,0 +1,302 @@
+use bevy::math::Vec2;
+use lyon_path::{PathEvent, math::Point};
+use lyon_tessellation::{BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex};
+
+use crate::geometry::flatten::{ring_contains, signed_area};
+
+/// Points closer than this to a ring are on the ring.
+const BOUNDARY_DISTANCE: f32 = 1e-4;
+
+/// A polygon with holes.
+///
+/// The exterior ring is counter-clockwise and all holes are clockwise, the first point of a ring
+/// is not repeated at its end.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SvgPolygon {
+    /// The outer boundary of the polygon.
+    pub exterior: Vec<Vec2>,
+    /// The holes inside the polygon.
+    pub holes: Vec<Vec<Vec2>>,
+}
+
+/// A triangle mesh, as produced by [`SvgPolygon::triangulate`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Triangulation {
+    /// The vertices of the mesh.
+    pub vertices: Vec<Vec2>,
+    /// Counter-clockwise triangles, as indices into `vertices`.
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl SvgPolygon {
+    /// Creates polygons from closed rings which are filled with the given [`FillRule`].
+    ///
+    /// Rings are expected to not cross each other, but may touch. Each ring which bounds a filled
+    /// area becomes a polygon and each ring which bounds an unfilled area inside of it becomes a
+    /// hole. A ring is inside of another ring if more of its points are inside than outside of
+    /// it, points on the other ring are not counted.
+    pub fn from_rings(rings: Vec<Vec<Vec2>>, fill_rule: FillRule) -> Vec<SvgPolygon> {
+        let mut rings = rings
+            .into_iter()
+            .filter(|ring| ring.len() > 2)
+            .map(|ring| (signed_area(&ring), ring))
+            .filter(|(area, _)| *area != 0.0)
+            .collect::<Vec<_>>();
+        // outer rings first, so the parent of a ring is always classified before the ring itself
+        rings.sort_by(|(a, _), (b, _)| b.abs().total_cmp(&a.abs()));
+
+        let is_filled = |winding: i32| match fill_rule {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        };
+        let contribution = |area: f32| match fill_rule {
+            FillRule::EvenOdd => 1,
+            FillRule::NonZero => area.signum() as i32,
+        };
+
+        let mut polygons = Vec::<SvgPolygon>::new();
+        // index of the polygon for every ring which became an exterior
+        let mut exteriors = Vec::<Option<usize>>::with_capacity(rings.len());
+        for (idx, (area, ring)) in rings.iter().enumerate() {
+            let containing = (0..idx)
+                .filter(|&other| ring_inside(ring, &rings[other].1))
+                .collect::<Vec<_>>();
+            let outside = containing
+                .iter()
+                .map(|&other| contribution(rings[other].0))
+                .sum::<i32>();
+            let inside = outside + contribution(*area);
+
+            let mut exterior = None;
+            if !is_filled(outside) && is_filled(inside) {
+                exterior = Some(polygons.len());
+                polygons.push(SvgPolygon {
+                    exterior: oriented(ring, true),
+                    holes: Vec::new(),
+                });
+            } else if is_filled(outside) && !is_filled(inside) {
+                // the innermost containing exterior is the parent of the hole
+                let parent = containing.iter().rev().find_map(|&other| exteriors[other]);
+                if let Some(parent) = parent {
+                    polygons[parent].holes.push(oriented(ring, false));
+                }
+            }
+            exteriors.push(exterior);
+        }
+        polygons
+    }
+
+    /// Returns the path events of all rings of the polygon.
+    pub fn path_events(&self) -> Vec<PathEvent> {
+        let mut events = Vec::new();
+        for ring in std::iter::once(&self.exterior).chain(&self.holes) {
+            ring_events(ring, &mut events);
+        }
+        events
+    }
+
+    /// Area of the polygon, without the area of its holes.
+    pub fn area(&self) -> f32 {
+        signed_area(&self.exterior) + self.holes.iter().map(|h| signed_area(h)).sum::<f32>()
+    }
+
+    /// Returns `true` if the point is inside the polygon and not inside one of its holes.
+    pub fn contains(&self, point: Vec2) -> bool {
+        ring_contains(&self.exterior, point)
+            && !self.holes.iter().any(|hole| ring_contains(hole, point))
+    }
+
+    /// Splits the polygon into triangles.
+    pub fn triangulate(&self) -> Triangulation {
+        let mut buffers = lyon_tessellation::VertexBuffers::<Point, u32>::new();
+        let result = FillTessellator::new().tessellate(
+            self.path_events(),
+            &FillOptions::tolerance(0.01).with_fill_rule(FillRule::EvenOdd),
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| vertex.position()),
+        );
+        if let Err(e) = result {
+            bevy::log::error!("FillTessellator error: {:?}", e);
+        }
+
+        let vertices = buffers
+            .vertices
+            .iter()
+            .map(|point| Vec2::new(point.x, point.y))
+            .collect::<Vec<_>>();
+        let triangles = buffers
+            .indices
+            .chunks_exact(3)
+            .map(|tri| {
+                let [a, b, c] = [tri[0], tri[1], tri[2]];
+                let (pa, pb, pc) = (
+                    vertices[a as usize],
+                    vertices[b as usize],
+                    vertices[c as usize],
+                );
+                if (pb - pa).perp_dot(pc - pa) < 0.0 {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                }
+            })
+            .collect();
+        Triangulation {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Splits the polygon into convex, counter-clockwise polygons.
+    ///
+    /// The polygon is triangulated first, afterwards neighboring parts are merged as long as the
+    /// result stays convex (Hertel-Mehlhorn).
+    pub fn convex_decomposition(&self) -> Vec<Vec<Vec2>> {
+        let Triangulation {
+            vertices,
+            triangles,
+        } = self.triangulate();
+
+        let mut parts = triangles.iter().map(|tri| tri.to_vec()).collect::<Vec<_>>();
+        // every merged part points to the part it was merged into
+        let mut merged_into = (0..parts.len()).collect::<Vec<_>>();
+        fn find(merged_into: &mut [usize], mut idx: usize) -> usize {
+            while merged_into[idx] != idx {
+                merged_into[idx] = merged_into[merged_into[idx]];
+                idx = merged_into[idx];
+            }
+            idx
+        }
+
+        let mut edges = bevy::platform::collections::HashMap::<(u32, u32), Vec<usize>>::default();
+        for (idx, tri) in triangles.iter().enumerate() {
+            for i in 0..3 {
+                let (a, b) = (tri[i], tri[(i + 1) % 3]);
+                edges.entry((a.min(b), a.max(b))).or_default().push(idx);
+            }
+        }
+        let mut diagonals = edges
+            .into_iter()
+            .filter(|(_, owners)| owners.len() == 2)
+            .collect::<Vec<_>>();
+        // deterministic and longest diagonals first, which tends to produce fewer parts
+        diagonals.sort_by(|((a0, a1), _), ((b0, b1), _)| {
+            let len_a = vertices[*a0 as usize].distance_squared(vertices[*a1 as usize]);
+            let len_b = vertices[*b0 as usize].distance_squared(vertices[*b1 as usize]);
+            len_b.total_cmp(&len_a).then((a0, a1).cmp(&(b0, b1)))
+        });
+
+        for ((a, b), owners) in diagonals {
+            let (p, q) = (
+                find(&mut merged_into, owners[0]),
+                find(&mut merged_into, owners[1]),
+            );
+            if p == q {
+                continue;
+            }
+            let Some(merged) = merge_parts(&parts[p], &parts[q], a, b) else {
+                continue;
+            };
+            if is_convex(&merged, &vertices) {
+                parts[p] = merged;
+                parts[q].clear();
+                merged_into[q] = p;
+            }
+        }
+
+        parts
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .map(|part| part.iter().map(|&i| vertices[i as usize]).collect())
+            .collect()
+    }
+}
+
+/// Returns `true` if more points of `inner` are inside of `outer` than outside of it, points on
+/// the boundary of `outer` are ignored.
+fn ring_inside(inner: &[Vec2], outer: &[Vec2]) -> bool {
+    let len = outer.len();
+    let on_boundary = |point: Vec2| {
+        (0..len).any(|i| {
+            let (a, b) = (outer[i], outer[(i + 1) % len]);
+            let ab = b - a;
+            let t = ((point - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+            (a + ab * t).distance_squared(point) <= BOUNDARY_DISTANCE * BOUNDARY_DISTANCE
+        })
+    };
+    let (inside, outside) = inner.iter().filter(|&&point| !on_boundary(point)).fold(
+        (0, 0),
+        |(inside, outside), &point| {
+            if ring_contains(outer, point) {
+                (inside + 1, outside)
+            } else {
+                (inside, outside + 1)
+            }
+        },
+    );
+    inside > outside
+}
+
+/// Appends the events of a closed ring.
+pub(crate) fn ring_events(ring: &[Vec2], events: &mut Vec<PathEvent>) {
+    let to_point = |v: Vec2| Point::new(v.x, v.y);
+    let Some((&first, rest)) = ring.split_first() else {
+        return;
+    };
+    events.push(PathEvent::Begin {
+        at: to_point(first),
+    });
+    let mut prev = first;
+    for &next in rest {
+        events.push(PathEvent::Line {
+            from: to_point(prev),
+            to: to_point(next),
+        });
+        prev = next;
+    }
+    events.push(PathEvent::End {
+        last: to_point(prev),
+        first: to_point(first),
+        close: true,
+    });
+}
+
+/// Returns the ring with the requested orientation.
+fn oriented(ring: &[Vec2], counter_clockwise: bool) -> Vec<Vec2> {
+    let mut ring = ring.to_vec();
+    if (signed_area(&ring) > 0.0) != counter_clockwise {
+        ring.reverse();
+    }
+    ring
+}
+
+/// Merges two counter-clockwise parts which share the edge `a`-`b`.
+fn merge_parts(p: &[u32], q: &[u32], a: u32, b: u32) -> Option<Vec<u32>> {
+    // rotate `p`, so it starts with `b` and ends with `a`
+    let rotate = |part: &[u32], start: u32, end: u32| {
+        let idx = part.iter().position(|&v| v == start)?;
+        let rotated = part[idx..]
+            .iter()
+            .chain(&part[..idx])
+            .copied()
+            .collect::<Vec<_>>();
+        (rotated.last() == Some(&end)).then_some(rotated)
+    };
+    let (p, q) = match (rotate(p, b, a), rotate(q, a, b)) {
+        (Some(p), Some(q)) => (p, q),
+        _ => (rotate(p, a, b)?, rotate(q, b, a)?),
+    };
+    let mut merged = p;
+    merged.extend_from_slice(&q[1..q.len() - 1]);
+    Some(merged)
+}
+
+/// Returns `true` if the counter-clockwise part is convex.
+fn is_convex(part: &[u32], vertices: &[Vec2]) -> bool {
+    let len = part.len();
+    (0..len).all(|i| {
+        let a = vertices[part[i] as usize];
+        let b = vertices[part[(i + 1) % len] as usize];
+        let c = vertices[part[(i + 2) % len] as usize];
+        (b - a).perp_dot(c - b) >= -1e-6
+    })
+}
@@ -0,0 +1,300 @@
+use bevy::{
+    math::{Rect, Vec2, Vec3, Vec3Swizzles},
+    transform::components::Transform,
+};
+use lyon_geom::{CubicBezierSegment, QuadraticBezierSegment};
+use lyon_path::PathEvent;
+use lyon_tessellation::FillRule;
+
+use crate::{
+    geometry::{PathMeasure, Polyline, SvgPolygon, flatten, winding_number},
+    origin::Origin,
+    svg::{DrawType, PathDescriptor, Svg},
+};
+
+/// Geometry queries on the painted paths of an [`Svg`].
+///
+/// All positions are in the coordinate system of the SVG, with the y-axis pointing down. Use
+/// [`Svg::svg_to_world`] and [`Svg::world_to_svg`] to convert them from and to the world space
+/// of an SVG entity. Queries which flatten curves take a `tolerance`, the maximum distance between
+/// a curve and its approximation in SVG units.
+impl Svg {
+    /// Returns the exact bounds of all paths, including the stroke width.
+    ///
+    /// Returns `None` if the [`Svg`] has no paths.
+    pub fn bounds(&self) -> Option<Rect> {
+        bounds(self.paths.iter())
+    }
+
+    /// Returns the exact bounds of the element with the given `id`, or of all elements in a group
+    /// with that `id`, including the stroke width.
+    pub fn element_bounds(&self, id: &str) -> Option<Rect> {
+        bounds(self.paths.iter().filter(|path| path.has_id(id)))
+    }
+
+    /// Returns `true` if the point is inside the fill of a path, following its fill rule, or
+    /// within half the stroke width of a stroked path. Caps and joins are treated as round.
+    pub fn contains_point(&self, point: Vec2, tolerance: f32) -> bool {
+        self.paths
+            .iter()
+            .any(|path| path_contains(path, point, tolerance))
+    }
+
+    /// Returns `true` if the point is inside the element with the given `id`, or inside one of
+    /// the elements in a group with that `id`. See [`Svg::contains_point`].
+    pub fn element_contains_point(&self, id: &str, point: Vec2, tolerance: f32) -> bool {
+        self.paths
+            .iter()
+            .filter(|path| path.has_id(id))
+            .any(|path| path_contains(path, point, tolerance))
+    }
+
+    /// Returns the point on the outline of all paths which is closest to the given point. The
+    /// outline of a stroke is its center line.
+    pub fn nearest_point(&self, point: Vec2, tolerance: f32) -> Option<Vec2> {
+        nearest_point(self.unique_paths(), point, tolerance)
+    }
+
+    /// Returns the point on the outline of the element with the given `id`, or of the elements in
+    /// a group with that `id`, which is closest to the given point.
+    pub fn element_nearest_point(&self, id: &str, point: Vec2, tolerance: f32) -> Option<Vec2> {
+        nearest_point(self.element_paths(id), point, tolerance)
+    }
+
+    /// Returns the length of all paths, closed sub-paths include their closing line.
+    pub fn path_length(&self) -> f32 {
+        self.unique_paths().map(path_length).sum()
+    }
+
+    /// Returns the length of the element with the given `id`, or of all elements in a group with
+    /// that `id`.
+    pub fn element_path_length(&self, id: &str) -> f32 {
+        self.element_paths(id).map(path_length).sum()
+    }
+
+    /// Returns the filled area of all paths, following their fill rule. Strokes have no area and
+    /// the areas of overlapping paths are added up.
+    pub fn area(&self, tolerance: f32) -> f32 {
+        fill_moments(self.paths.iter(), tolerance).0
+    }
+
+    /// Returns the filled area of the element with the given `id`, or of all elements in a group
+    /// with that `id`. See [`Svg::area`].
+    pub fn element_area(&self, id: &str, tolerance: f32) -> f32 {
+        fill_moments(self.paths.iter().filter(|path| path.has_id(id)), tolerance).0
+    }
+
+    /// Returns the center of mass of the filled area of all paths.
+    ///
+    /// Returns `None` if no path has a filled area.
+    pub fn centroid(&self, tolerance: f32) -> Option<Vec2> {
+        centroid(self.paths.iter(), tolerance)
+    }
+
+    /// Returns the center of mass of the filled area of the element with the given `id`, or of
+    /// all elements in a group with that `id`.
+    pub fn element_centroid(&self, id: &str, tolerance: f32) -> Option<Vec2> {
+        centroid(self.paths.iter().filter(|path| path.has_id(id)), tolerance)
+    }
+
+    /// Converts a point from SVG space into the local space of the mesh, where the y-axis is
+    /// flipped.
+    pub fn svg_to_local(point: Vec2) -> Vec3 {
+        Vec3::new(point.x, -point.y, 0.0)
+    }
+
+    /// Converts a point from the local space of the mesh into SVG space.
+    pub fn local_to_svg(point: Vec3) -> Vec2 {
+        Vec2::new(point.x, -point.y)
+    }
+
+    /// Converts a point from SVG space into the world space of an SVG entity without a parent,
+    /// with the given [`Transform`] and [`Origin`].
+    ///
+    /// The [`GlobalTransform`](bevy::transform::components::GlobalTransform) of an SVG entity
+    /// contains the offset of its [`Origin`] once it was applied, so for entities with a parent
+    /// use `global_transform.transform_point(Svg::svg_to_local(point))` instead.
+    pub fn svg_to_world(&self, point: Vec2, origin: Origin, transform: &Transform) -> Vec3 {
+        transform.transform_point(Self::svg_to_local(point)) + self.origin_offset(origin, transform)
+    }
+
+    /// Converts a point from the world space of an SVG entity without a parent into SVG space,
+    /// the inverse of [`Svg::svg_to_world`]. The point is projected onto the plane of the
+    /// [`Svg`].
+    pub fn world_to_svg(&self, point: Vec3, origin: Origin, transform: &Transform) -> Vec2 {
+        let local = transform
+            .compute_affine()
+            .inverse()
+            .transform_point3(point - self.origin_offset(origin, transform));
+        Self::local_to_svg(local)
+    }
+
+    /// The translation which is added to the entity by its [`Origin`].
+    fn origin_offset(&self, origin: Origin, transform: &Transform) -> Vec3 {
+        origin.compute_translation(self.size * transform.scale.xy())
+    }
+}
+
+/// Flattens a path into polylines in SVG space.
+pub(crate) fn outlines(path: &PathDescriptor, tolerance: f32) -> Vec<Polyline> {
+    let mut polylines = flatten(&path.transformed_segments(), tolerance);
+    for point in polylines.iter_mut().flat_map(|line| line.points.iter_mut()) {
+        point.y = -point.y;
+    }
+    polylines
+}
+
+/// The segments of a polyline, including the closing one.
+fn polyline_segments(polyline: &Polyline) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    let points = &polyline.points;
+    let count = if polyline.closed {
+        points.len()
+    } else {
+        points.len().saturating_sub(1)
+    };
+    (0..count).map(|i| (points[i], points[(i + 1) % points.len()]))
+}
+
+fn closest_on_segment(a: Vec2, b: Vec2, point: Vec2) -> Vec2 {
+    let ab = b - a;
+    let length_squared = ab.length_squared();
+    if length_squared == 0.0 {
+        return a;
+    }
+    a + ab * ((point - a).dot(ab) / length_squared).clamp(0.0, 1.0)
+}
+
+/// Half of the stroke width in SVG units, the stroke scales with the transform of the path.
+fn half_stroke_width(path: &PathDescriptor) -> Option<f32> {
+    match path.draw_type {
+        DrawType::Stroke(options) => {
+            let (sx, sy) = path.abs_transform.get_scale();
+            Some(options.line_width / 2.0 * sx.max(sy))
+        }
+        DrawType::Fill(_) => None,
+    }
+}
+
+fn bounds<'a>(paths: impl Iterator<Item = &'a PathDescriptor>) -> Option<Rect> {
+    paths
+        .filter_map(|path| {
+            let bounds = path_bounds(path)?;
+            Some(bounds.inflate(half_stroke_width(path).unwrap_or_default()))
+        })
+        .reduce(|a, b| a.union(b))
+}
+
+/// Returns the exact bounds of a path in SVG space, without the stroke width.
+pub(crate) fn path_bounds(path: &PathDescriptor) -> Option<Rect> {
+    path.transformed_segments()
+        .iter()
+        .filter_map(|event| {
+            let (min, max) = match *event {
+                PathEvent::Begin { at } => (at, at),
+                PathEvent::Line { from, to } => (from.min(to), from.max(to)),
+                PathEvent::Quadratic { from, ctrl, to } => {
+                    let bounds = QuadraticBezierSegment { from, ctrl, to }.bounding_box();
+                    (bounds.min, bounds.max)
+                }
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => {
+                    let bounds = CubicBezierSegment {
+                        from,
+                        ctrl1,
+                        ctrl2,
+                        to,
+                    }
+                    .bounding_box();
+                    (bounds.min, bounds.max)
+                }
+                PathEvent::End { .. } => return None,
+            };
+            Some(Rect::new(min.x, min.y, max.x, max.y))
+        })
+        .reduce(|a, b| a.union(b))
+}
+
+fn path_contains(path: &PathDescriptor, point: Vec2, tolerance: f32) -> bool {
+    let polylines = outlines(path, tolerance);
+    match path.draw_type {
+        DrawType::Fill(fill_rule) => {
+            let winding = polylines
+                .iter()
+                .map(|polyline| winding_number(&polyline.points, point))
+                .sum::<i32>();
+            match fill_rule {
+                FillRule::EvenOdd => winding % 2 != 0,
+                FillRule::NonZero => winding != 0,
+            }
+        }
+        DrawType::Stroke(_) => {
+            let half_width = half_stroke_width(path).unwrap_or_default();
+            polylines
+                .iter()
+                .flat_map(polyline_segments)
+                .any(|(a, b)| closest_on_segment(a, b, point).distance(point) <= half_width)
+        }
+    }
+}
+
+fn nearest_point<'a>(
+    paths: impl Iterator<Item = &'a PathDescriptor>,
+    point: Vec2,
+    tolerance: f32,
+) -> Option<Vec2> {
+    paths
+        .flat_map(|path| outlines(path, tolerance))
+        .flat_map(|polyline| {
+            polyline_segments(&polyline)
+                .map(|(a, b)| closest_on_segment(a, b, point))
+                .collect::<Vec<_>>()
+        })
+        .min_by(|a, b| {
+            a.distance_squared(point)
+                .total_cmp(&b.distance_squared(point))
+        })
+}
+
+fn path_length(path: &PathDescriptor) -> f32 {
+    PathMeasure::new(&path.transformed_segments()).length()
+}
+
+/// Returns the filled area and its first moment, the sum of the centroids weighted by area.
+fn fill_moments<'a>(
+    paths: impl Iterator<Item = &'a PathDescriptor>,
+    tolerance: f32,
+) -> (f32, Vec2) {
+    let mut area = 0.0;
+    let mut moment = Vec2::ZERO;
+    for path in paths {
+        let DrawType::Fill(fill_rule) = path.draw_type else {
+            continue;
+        };
+        let rings = outlines(path, tolerance)
+            .into_iter()
+            .map(|polyline| polyline.points)
+            .collect();
+        // holes are oriented opposite to their exterior, so their area is subtracted
+        for polygon in SvgPolygon::from_rings(rings, fill_rule) {
+            for ring in std::iter::once(&polygon.exterior).chain(&polygon.holes) {
+                let len = ring.len();
+                for i in 0..len {
+                    let (a, b) = (ring[i], ring[(i + 1) % len]);
+                    let cross = a.perp_dot(b);
+                    area += cross * 0.5;
+                    moment += (a + b) * cross / 6.0;
+                }
+            }
+        }
+    }
+    (area, moment)
+}
+
+fn centroid<'a>(paths: impl Iterator<Item = &'a PathDescriptor>, tolerance: f32) -> Option<Vec2> {
+    let (area, moment) = fill_moments(paths, tolerance);
+    (area > 0.0).then(|| moment / area)
+}
@@ -13,26 +13,74 @@
 
 #![allow(clippy::type_complexity)]
 
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod atlas;
+mod baked;
+mod builder;
+#[cfg(feature = "3d")]
+mod decal;
+mod element;
+mod export;
+mod extrude;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod follow;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod frames;
+pub mod geometry;
 mod loader;
 #[cfg(any(feature = "2d", feature = "3d"))]
+mod lod;
+#[cfg(any(feature = "2d", feature = "3d"))]
 mod origin;
 #[cfg(any(feature = "2d", feature = "3d"))]
 mod plugin;
+mod profile;
+mod raster;
 mod render;
 mod resources;
+mod saver;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod spawn;
 mod svg;
+#[cfg(any(feature = "2d", feature = "3d"))]
+mod tint;
+mod trim;
 mod util;
 
 /// Import this module as `use bevy_svg::prelude::*` to get convenient imports.
 pub mod prelude {
     pub use super::{SvgPlugin, SvgSet};
     #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::atlas::{SvgAtlas, SvgAtlasBuilder, SvgAtlasError};
+    pub use crate::baked::{BakedSvgError, BakedSvgLoader, BakedSvgSaver, SvgBakeProcessor};
+    pub use crate::builder::{SvgBuilder, SvgStyle};
+    #[cfg(feature = "3d")]
+    pub use crate::decal::{SvgDecal, SvgDecalMode};
+    pub use crate::element::{SvgElement, SvgSelector};
+    pub use crate::extrude::SvgExtrusion;
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::follow::{FollowSvgPath, PathRepeat};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::frames::SvgFrameAnimation;
+    pub use crate::geometry::SvgPathCurve;
+    pub use crate::loader::{SvgAssetLoader, SvgLoaderSettings};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::lod::SvgLod;
+    #[cfg(any(feature = "2d", feature = "3d"))]
     pub use crate::origin::Origin;
+    pub use crate::profile::{SvgLathe, SvgSweep};
+    pub use crate::render::specialize_svg_pipeline;
     #[cfg(feature = "2d")]
-    pub use crate::render::Svg2d;
+    pub use crate::render::{SVG_2D_SHADER_HANDLE, Svg2d, SvgMaterial2d};
     #[cfg(feature = "3d")]
-    pub use crate::render::Svg3d;
-    pub use crate::svg::Svg;
+    pub use crate::render::{SVG_3D_SHADER_HANDLE, Svg3d, SvgBillboard, SvgLit, SvgMaterial3d};
+    pub use crate::saver::SvgAssetSaver;
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::spawn::{SvgElementsSpawned, SvgSpawnContext, SvgSpawners};
+    pub use crate::svg::{Svg, SvgFrame};
+    #[cfg(any(feature = "2d", feature = "3d"))]
+    pub use crate::tint::SvgTint;
+    pub use crate::trim::{StrokeTrim, TrimRange};
     pub use lyon_tessellation::{
         FillOptions, FillRule, LineCap, LineJoin, Orientation, StrokeOptions,
     };
@@ -42,20 +90,31 @@ pub use plugin::SvgSet;
 
 #[cfg(any(feature = "2d", feature = "3d"))]
 use crate::plugin::SvgRenderPlugin;
-use crate::{loader::SvgAssetLoader, svg::Svg};
+use crate::{
+    baked::{BakedSvgLoader, BakedSvgSaver, SvgBakeProcessor},
+    loader::SvgAssetLoader,
+    svg::Svg,
+};
 use bevy::{
     app::{App, Plugin},
     asset::AssetApp,
 };
 
 /// A plugin that provides resources and a system to draw [`Svg`]s.
+///
+/// When the [`AssetPlugin`](bevy::asset::AssetPlugin) runs in processed mode, SVG files are
+/// baked by the [`SvgBakeProcessor`], so they are neither parsed nor tessellated at runtime.
 pub struct SvgPlugin;
 
 impl Plugin for SvgPlugin {
     #[inline]
     fn build(&self, app: &mut App) {
         app.init_asset::<Svg>()
-            .init_asset_loader::<SvgAssetLoader>();
+            .init_asset_loader::<SvgAssetLoader>()
+            .init_asset_loader::<BakedSvgLoader>()
+            .register_asset_processor::<SvgBakeProcessor>(BakedSvgSaver.into())
+            .set_default_asset_processor::<SvgBakeProcessor>("svg")
+            .set_default_asset_processor::<SvgBakeProcessor>("svgz");
         #[cfg(any(feature = "2d", feature = "3d"))]
         app.add_plugins(SvgRenderPlugin);
     }
@@ -4,6 +4,7 @@ use bevy::{
     reflect::TypePath,
     tasks::ConditionalSendFuture,
 };
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::svg::Svg;
@@ -11,15 +12,25 @@ use crate::svg::Svg;
 #[derive(Default, TypePath)]
 pub struct SvgAssetLoader;
 
+/// Settings for loading SVG files, e.g. with
+/// [`AssetServer::load_with_settings`](bevy::asset::AssetServer::load_with_settings).
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SvgLoaderSettings {
+    /// Adds an anti-aliasing fringe to the mesh, see [`Svg::antialias`].
+    pub antialias: bool,
+    /// Distance between the layers of the paths, see [`Svg::layer_spacing`].
+    pub layer_spacing: f32,
+}
+
 impl AssetLoader for SvgAssetLoader {
     type Asset = Svg;
-    type Settings = ();
+    type Settings = SvgLoaderSettings;
     type Error = FileSvgError;
 
     fn load(
         &self,
         reader: &mut dyn Reader,
-        _settings: &(),
+        settings: &SvgLoaderSettings,
         load_context: &mut LoadContext<'_>,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
@@ -45,11 +56,18 @@ impl AssetLoader for SvgAssetLoader {
                 })?
                 .to_string_lossy();
             svg.name = name.to_string();
+            svg.antialias = settings.antialias;
+            svg.layer_spacing = settings.layer_spacing;
             debug!("Parsing SVG: {} ... Done", load_context.path());
 
             debug!("Tessellating SVG: {} ...", load_context.path());
-            let mesh = svg.tessellate();
+            let mesh = svg.tessellate_frames();
             debug!("Tessellating SVG: {} ... Done", load_context.path());
+            for frame in &mut svg.frames {
+                let frame_mesh = frame.extract_mesh(&mesh);
+                frame.mesh =
+                    load_context.add_labeled_asset(format!("frame/{}", frame.id), frame_mesh);
+            }
             let mesh_handle = load_context.add_labeled_asset("mesh".to_string(), mesh);
             svg.mesh = mesh_handle;
 
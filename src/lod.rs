@@ -0,0 +1,278 @@
+//! Level of detail, the tessellation tolerance follows the size of an [`Svg`] on screen.
+
+#[cfg(feature = "2d")]
+use bevy::mesh::Mesh2d;
+#[cfg(feature = "3d")]
+use bevy::mesh::Mesh3d;
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets},
+    camera::Camera,
+    ecs::{
+        component::Component,
+        entity::Entity,
+        lifecycle::RemovedComponents,
+        message::MessageReader,
+        query::Without,
+        system::{Query, Res, ResMut},
+    },
+    math::Vec3,
+    mesh::Mesh,
+    tasks::{AsyncComputeTaskPool, Task, futures::check_ready},
+    transform::components::GlobalTransform,
+};
+
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
+use crate::{
+    Convert,
+    frames::SvgFrameAnimation,
+    render::{tessellation, vertex_buffer},
+    svg::Svg,
+    trim::StrokeTrim,
+};
+
+/// Re-tessellates an [`Svg`] entity with a tolerance that matches its size on screen.
+///
+/// The tolerance is `pixel_tolerance` converted to SVG units, using the projection of the camera
+/// and the [`GlobalTransform`] of the entity. It is rounded to a power of two, so the mesh only
+/// changes when the on-screen scale changes by a factor of two, plus `hysteresis` to avoid
+/// switching back and forth at a threshold. The new mesh is tessellated in the background on the
+/// [`AsyncComputeTaskPool`] and replaces the old one once it is done.
+///
+/// The entity gets its own mesh, so the component is ignored on entities with a
+/// [`StrokeTrim`](crate::prelude::StrokeTrim) or an
+/// [`SvgFrameAnimation`](crate::prelude::SvgFrameAnimation). Once the component is removed, the
+/// entity gets the shared mesh of the [`Svg`] back.
+#[derive(Component, Debug)]
+pub struct SvgLod {
+    /// The maximum distance between a curve and its approximation, in pixels.
+    pub pixel_tolerance: f32,
+    /// How far the scale has to move past a threshold before the mesh changes, as a fraction of
+    /// a factor of two.
+    pub hysteresis: f32,
+    /// The smallest tolerance, in SVG units.
+    pub min_tolerance: f32,
+    /// The largest tolerance, in SVG units.
+    pub max_tolerance: f32,
+    /// The camera which views the entity, the first active camera is used if it is `None`.
+    pub camera: Option<Entity>,
+    /// The [`Svg`] and level of the current mesh, where the tolerance is `2^level`.
+    level: Option<(AssetId<Svg>, i32)>,
+    task: Option<(AssetId<Svg>, i32, Task<Mesh>)>,
+}
+
+impl Default for SvgLod {
+    fn default() -> Self {
+        Self {
+            pixel_tolerance: 0.25,
+            hysteresis: 0.25,
+            min_tolerance: 0.001,
+            max_tolerance: 8.0,
+            camera: None,
+            level: None,
+            task: None,
+        }
+    }
+}
+
+impl SvgLod {
+    /// Sets the maximum distance between a curve and its approximation, in pixels.
+    pub fn with_pixel_tolerance(mut self, pixel_tolerance: f32) -> Self {
+        self.pixel_tolerance = pixel_tolerance;
+        self
+    }
+
+    /// Sets the hysteresis, as a fraction of a factor of two.
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Sets the camera which views the entity.
+    pub fn with_camera(mut self, camera: Entity) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// The tolerance of the current mesh in SVG units, `None` until the first mesh is ready.
+    pub fn tolerance(&self) -> Option<f32> {
+        self.level.map(|(_, level)| (level as f32).exp2())
+    }
+
+    /// Returns the level for the given on-screen scale in pixels per SVG unit, or `None` if the
+    /// current level is still good enough.
+    fn next_level(&self, id: AssetId<Svg>, pixels_per_unit: f32) -> Option<i32> {
+        let tolerance = (self.pixel_tolerance / pixels_per_unit)
+            .clamp(self.min_tolerance, self.max_tolerance)
+            .log2();
+        let current = self
+            .task
+            .as_ref()
+            .map(|(task_id, level, _)| (*task_id, *level))
+            .or(self.level)
+            .filter(|(current_id, _)| *current_id == id);
+        match current {
+            Some((_, level)) if (tolerance - level as f32).abs() <= 0.5 + self.hysteresis => None,
+            _ => Some(tolerance.round() as i32),
+        }
+    }
+}
+
+/// Computes the level of detail of all [`SvgLod`] entities and swaps in finished meshes, and
+/// resets the mesh of entities whose [`SvgLod`] was removed.
+pub fn update_svg_lod(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    mut query: Query<
+        (
+            &mut SvgLod,
+            &GlobalTransform,
+            Option<&Svg2d>,
+            Option<&Svg3d>,
+            Option<&mut Mesh2d>,
+            Option<&mut Mesh3d>,
+        ),
+        (Without<StrokeTrim>, Without<SvgFrameAnimation>),
+    >,
+    mut removed: RemovedComponents<SvgLod>,
+    mut without_lod: Query<
+        (
+            Option<&Svg2d>,
+            Option<&Svg3d>,
+            Option<&mut Mesh2d>,
+            Option<&mut Mesh3d>,
+        ),
+        (
+            Without<SvgLod>,
+            Without<StrokeTrim>,
+            Without<SvgFrameAnimation>,
+        ),
+    >,
+) {
+    for entity in removed.read() {
+        let Ok((svg_2d, svg_3d, mesh_2d, mesh_3d)) = without_lod.get_mut(entity) else {
+            continue;
+        };
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        #[cfg(feature = "2d")]
+        if let Some(mut mesh) = mesh_2d
+            && mesh.0 != svg.mesh
+        {
+            meshes.remove(&mesh.0);
+            mesh.0 = svg.mesh.clone();
+        }
+        #[cfg(feature = "3d")]
+        if let Some(mut mesh) = mesh_3d
+            && mesh.0 != svg.mesh
+        {
+            meshes.remove(&mesh.0);
+            mesh.0 = svg.mesh.clone();
+        }
+    }
+
+    let modified = svg_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (mut lod, transform, svg_2d, svg_3d, mesh_2d, mesh_3d) in &mut query {
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        if modified.contains(&handle.id()) {
+            // a running task tessellates the old paths, dropping it cancels it
+            lod.task = None;
+            lod.level = None;
+        }
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        let current = mesh_2d
+            .as_ref()
+            .map(|mesh| mesh.0.clone())
+            .or_else(|| mesh_3d.as_ref().map(|mesh| mesh.0.clone()));
+        let Some(current) = current else {
+            continue;
+        };
+        let is_shared = current == svg.mesh;
+        if is_shared {
+            // the mesh was reset, e.g. because the asset changed
+            lod.level = None;
+        }
+
+        if let Some((id, level, task)) = lod.task.as_mut()
+            && let Some(mesh) = check_ready(task)
+        {
+            let (id, level) = (*id, *level);
+            lod.task = None;
+            if id == handle.id() {
+                if is_shared {
+                    let handle = meshes.add(mesh);
+                    #[cfg(feature = "2d")]
+                    if let Some(mut mesh) = mesh_2d {
+                        mesh.0 = handle.clone();
+                    }
+                    #[cfg(feature = "3d")]
+                    if let Some(mut mesh) = mesh_3d {
+                        mesh.0 = handle;
+                    }
+                } else if meshes.insert(&current, mesh).is_err() {
+                    bevy::log::warn!("Svg `{}`: failed to update level of detail.", svg.name);
+                }
+                lod.level = Some((id, level));
+            }
+        }
+
+        let camera = match lod.camera {
+            Some(camera) => cameras.get(camera).ok(),
+            None => cameras.iter().find(|(_, camera, _)| camera.is_active),
+        };
+        let Some((_, camera, camera_transform)) = camera else {
+            continue;
+        };
+        let affine = transform.affine();
+        let origin = transform.translation();
+        let Ok(screen_origin) = camera.world_to_viewport(camera_transform, origin) else {
+            continue;
+        };
+        let pixels_per_unit = [Vec3::X, Vec3::Y]
+            .into_iter()
+            .filter_map(|axis| {
+                let point = origin + affine.transform_vector3(axis);
+                camera.world_to_viewport(camera_transform, point).ok()
+            })
+            .map(|point| point.distance(screen_origin))
+            .fold(0.0, f32::max);
+        if pixels_per_unit <= 0.0 {
+            continue;
+        }
+
+        if let Some(level) = lod.next_level(handle.id(), pixels_per_unit) {
+            let paths = svg.paths.clone();
+            let tolerance = (level as f32).exp2();
+            let (antialias, layer_spacing, size) = (svg.antialias, svg.layer_spacing, svg.size);
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                let mut mesh =
+                    tessellation::generate_buffer_parallel(&paths, Some(tolerance), antialias)
+                        .convert();
+                vertex_buffer::insert_surface_attributes(&mut mesh, size);
+                vertex_buffer::apply_layer_spacing(&mut mesh, layer_spacing);
+                mesh
+            });
+            // dropping a running task cancels it
+            lod.task = Some((handle.id(), level, task));
+        }
+    }
+}
@@ -13,17 +13,18 @@
 
 use bevy::{
     app::{App, Plugin},
-    asset::{AssetEvent, Assets},
+    asset::{AssetEvent, Assets, Handle},
     ecs::{
         entity::Entity,
         message::MessageReader,
-        query::{Added, Changed, Or},
+        query::{Added, Changed, Has, Or},
         schedule::{IntoScheduleConfigs, SystemSet},
         system::{Commands, Query, Res, ResMut},
     },
-    log::debug,
+    log::{debug, warn},
     mesh::Mesh,
     prelude::{Last, PostUpdate},
+    transform::TransformSystems,
 };
 
 #[cfg(feature = "2d")]
@@ -32,10 +33,18 @@ use bevy::mesh::Mesh2d;
 #[cfg(feature = "3d")]
 use bevy::mesh::Mesh3d;
 
+#[cfg(feature = "3d")]
+use crate::decal;
 use crate::{
+    atlas, follow,
+    frames::{self, SvgFrameAnimation},
+    lod::{self, SvgLod},
     origin,
     render::{self, Svg2d, Svg3d},
+    spawn,
     svg::Svg,
+    tint,
+    trim::{self, StrokeTrim},
 };
 
 /// Set in which [`Svg`](crate::prelude::Svg2d)s get drawn.
@@ -47,9 +56,49 @@ pub struct SvgRenderPlugin;
 
 impl Plugin for SvgRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(PostUpdate, origin::add_origin_state.in_set(SvgSet))
-            .add_systems(Last, (origin::apply_origin, svg_mesh_linker.in_set(SvgSet)))
+        app.init_resource::<spawn::SvgSpawners>()
+            .init_resource::<trim::SvgTrimFills>()
+            .add_systems(
+                PostUpdate,
+                (
+                    origin::add_origin_state.in_set(SvgSet),
+                    follow::follow_svg_paths
+                        .in_set(SvgSet)
+                        .before(TransformSystems::Propagate),
+                ),
+            )
+            .add_systems(
+                Last,
+                (
+                    origin::apply_origin,
+                    svg_mesh_generator.in_set(SvgSet).before(svg_mesh_linker),
+                    svg_mesh_linker.in_set(SvgSet),
+                    // all of them replace the mesh of an entity
+                    (
+                        warn_mesh_conflicts,
+                        trim::reset_stroke_trim,
+                        trim::apply_stroke_trim,
+                        lod::update_svg_lod,
+                        frames::animate_svg_frames,
+                    )
+                        .chain()
+                        .in_set(SvgSet)
+                        .after(svg_mesh_linker),
+                    spawn::spawn_svg_elements
+                        .in_set(SvgSet)
+                        .after(origin::apply_origin),
+                    tint::apply_svg_tint.in_set(SvgSet),
+                    atlas::update_svg_atlases.in_set(SvgSet),
+                ),
+            )
             .add_plugins(render::SvgPlugin);
+        #[cfg(feature = "3d")]
+        app.init_resource::<decal::SvgDecals>().add_systems(
+            Last,
+            (decal::remove_svg_decals, decal::update_svg_decals)
+                .chain()
+                .in_set(SvgSet),
+        );
     }
 }
 
@@ -78,6 +127,54 @@ type SvgMeshComponents = (
     Option<&'static mut Mesh3d>,
 );
 
+/// Warns about entities with more than one component which replaces their mesh. An
+/// [`SvgFrameAnimation`] takes precedence over a [`StrokeTrim`], which takes precedence over an
+/// [`SvgLod`].
+fn warn_mesh_conflicts(
+    query: Query<
+        (Entity, Has<StrokeTrim>, Has<SvgLod>, Has<SvgFrameAnimation>),
+        Or<(Added<StrokeTrim>, Added<SvgLod>, Added<SvgFrameAnimation>)>,
+    >,
+) {
+    for (entity, trim, lod, animation) in &query {
+        if [trim, lod, animation].into_iter().filter(|x| *x).count() > 1 {
+            warn!(
+                "Entity {entity} has more than one of SvgFrameAnimation, StrokeTrim and SvgLod, \
+                only the first one is applied."
+            );
+        }
+    }
+}
+
+/// Bevy system which generates the [`Mesh`] of [`Svg`]s which were added or replaced without
+/// one, e.g. when they were created with an [`SvgBuilder`](crate::prelude::SvgBuilder) or by
+/// [`Svg::boolean`].
+fn svg_mesh_generator(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut svgs: ResMut<Assets<Svg>>,
+) {
+    for event in svg_events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+        if svgs
+            .get(*id)
+            .is_none_or(|svg| svg.mesh != Handle::default())
+        {
+            continue;
+        }
+        if let Some(svg) = svgs.get_mut(*id) {
+            debug!("Svg `{}` added without mesh. Tessellating it.", svg.name);
+            let mesh = svg.tessellate_frames();
+            for frame in &mut svg.frames {
+                frame.mesh = meshes.add(frame.extract_mesh(&mesh));
+            }
+            svg.mesh = meshes.add(mesh);
+        }
+    }
+}
+
 /// Bevy system which queries for all [`Svg`] bundles and adds the correct [`Mesh`] to them.
 fn svg_mesh_linker(
     mut commands: Commands,
@@ -0,0 +1,337 @@
+//! Builds 3D meshes from the profiles of an [`Svg`], by revolving them around an axis or by
+//! sweeping them along a path.
+
+use std::f32::consts::TAU;
+
+use bevy::{
+    color::ColorToComponents,
+    math::{Rect, Vec2, Vec3},
+    mesh::Mesh,
+};
+
+use crate::{
+    extrude::MeshBuilder,
+    geometry::{Polyline, flatten, signed_area},
+    svg::Svg,
+};
+
+/// Options to revolve a profile around a vertical axis, see [`Svg::lathe`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgLathe {
+    /// The x coordinate of the axis in SVG units.
+    pub axis: f32,
+    /// The number of segments around the axis.
+    pub segments: u32,
+    /// The angle of the revolution in radians, a full turn by default.
+    pub angle: f32,
+    /// The maximum distance between a curve of the profile and its approximation.
+    pub tolerance: f32,
+    /// The largest angle in radians between neighboring edges of the profile which are shaded
+    /// smoothly, sharper corners get a hard edge.
+    pub smoothing_angle: f32,
+}
+
+impl Default for SvgLathe {
+    fn default() -> Self {
+        Self {
+            axis: 0.0,
+            segments: 32,
+            angle: TAU,
+            tolerance: 0.1,
+            smoothing_angle: 30f32.to_radians(),
+        }
+    }
+}
+
+impl SvgLathe {
+    /// Creates a full revolution around the vertical axis at the given x coordinate.
+    pub fn new(axis: f32) -> Self {
+        Self {
+            axis,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the number of segments around the axis.
+    pub fn with_segments(mut self, segments: u32) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the angle of the revolution in radians.
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Sets the maximum distance between a curve of the profile and its approximation.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the largest angle in radians between edges which are shaded smoothly.
+    pub fn with_smoothing_angle(mut self, smoothing_angle: f32) -> Self {
+        self.smoothing_angle = smoothing_angle;
+        self
+    }
+}
+
+/// Options to sweep a cross section along a path, see [`Svg::sweep`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SvgSweep {
+    /// The number of segments along the path, which is sampled at equal distances.
+    pub segments: u32,
+    /// The maximum distance between a curve of the cross section and its approximation.
+    pub tolerance: f32,
+    /// The largest angle in radians between neighboring edges of the cross section which are
+    /// shaded smoothly, sharper corners get a hard edge.
+    pub smoothing_angle: f32,
+}
+
+impl Default for SvgSweep {
+    fn default() -> Self {
+        Self {
+            segments: 64,
+            tolerance: 0.1,
+            smoothing_angle: 30f32.to_radians(),
+        }
+    }
+}
+
+impl SvgSweep {
+    /// Sets the number of segments along the path.
+    pub fn with_segments(mut self, segments: u32) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the maximum distance between a curve of the cross section and its approximation.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the largest angle in radians between edges which are shaded smoothly.
+    pub fn with_smoothing_angle(mut self, smoothing_angle: f32) -> Self {
+        self.smoothing_angle = smoothing_angle;
+        self
+    }
+}
+
+impl Svg {
+    /// Revolves the profile with the given `id` around a vertical axis, e.g. into a vase, a
+    /// bottle or a column.
+    ///
+    /// The profile is the first sub-path of the element, in the coordinate system of the [`Svg`]
+    /// mesh where the y-axis is flipped compared to the SVG. The axis of the [`Mesh`] is the
+    /// y-axis, at `x = 0` and `z = 0`. The normals of a closed profile point out of it, the
+    /// normals of an open profile away from the axis. In `u` the UVs go around the axis, in `v`
+    /// along the profile. The mesh has the color of the profile as vertex color.
+    ///
+    /// Returns `None` if there is no element with the `id`.
+    pub fn lathe(&self, id: &str, lathe: &SvgLathe) -> Option<Mesh> {
+        let (polyline, color) = element_profile(self, id, lathe.tolerance)?;
+        let points = polyline
+            .points
+            .iter()
+            .map(|point| Vec2::new(point.x - lathe.axis, point.y))
+            .collect::<Vec<_>>();
+        let (mut vertices, edges) = section(&points, polyline.closed, lathe.smoothing_angle);
+        let flip = if polyline.closed {
+            signed_area(&points) > 0.0
+        } else {
+            vertices
+                .iter()
+                .map(|vertex| vertex.normal.x * vertex.point.x.signum())
+                .sum::<f32>()
+                < 0.0
+        };
+        if flip {
+            vertices
+                .iter_mut()
+                .for_each(|vertex| vertex.normal = -vertex.normal);
+        }
+
+        let segments = lathe.segments.max(1);
+        let mut mesh = MeshBuilder::default();
+        for segment in 0..=segments {
+            let fraction = segment as f32 / segments as f32;
+            let (sin, cos) = (lathe.angle * fraction).sin_cos();
+            let rotate = |v: Vec2| Vec3::new(v.x * cos, v.y, -v.x * sin);
+            for vertex in &vertices {
+                mesh.vertex(
+                    rotate(vertex.point),
+                    rotate(vertex.normal),
+                    Vec2::new(fraction, vertex.distance),
+                    color,
+                );
+            }
+        }
+        add_strips(&mut mesh, vertices.len(), &edges, segments);
+        Some(mesh.build())
+    }
+
+    /// Sweeps the cross section with the id `section_id` along the path with the id `path_id`,
+    /// e.g. into a road, a rail or a pipe.
+    ///
+    /// The cross section is the first sub-path of its element, centered on its bounds. Its
+    /// x-axis points to the left of the path and its y-axis along the z-axis of the [`Mesh`],
+    /// so a mesh which is rotated by -90° around the x-axis lies on the ground with the cross
+    /// section standing upright. The normals of a closed cross section point out of it, the
+    /// normals of an open cross section to its left, e.g. up for a line drawn from left to
+    /// right. In `u` the UVs go along the cross section, in `v` along the path, scaled so that
+    /// textures keep their aspect ratio. The mesh has the color of the cross section as vertex
+    /// color, and is in the coordinate system of the [`Svg`] mesh.
+    ///
+    /// Returns `None` if one of the elements does not exist or the path has no length.
+    pub fn sweep(&self, section_id: &str, path_id: &str, sweep: &SvgSweep) -> Option<Mesh> {
+        let (polyline, color) = element_profile(self, section_id, sweep.tolerance)?;
+        let curve = self.path_curve(path_id)?;
+        let bounds = polyline
+            .points
+            .iter()
+            .map(|point| Rect::from_center_size(*point, Vec2::ZERO))
+            .reduce(|a, b| a.union(b))?;
+        let points = polyline
+            .points
+            .iter()
+            .map(|point| *point - bounds.center())
+            .collect::<Vec<_>>();
+        let (mut vertices, edges) = section(&points, polyline.closed, sweep.smoothing_angle);
+        if polyline.closed && signed_area(&points) > 0.0 {
+            vertices
+                .iter_mut()
+                .for_each(|vertex| vertex.normal = -vertex.normal);
+        }
+        let section_length = edges
+            .iter()
+            .map(|[a, b]| vertices[*a].point.distance(vertices[*b].point))
+            .sum::<f32>()
+            .max(f32::EPSILON);
+
+        let length = curve.length();
+        let (start, start_tangent) = curve.sample_with_tangent(0.0);
+        let (end, end_tangent) = curve.sample_with_tangent(length);
+        // a closed path gets the same frame at both ends, so they line up
+        let is_loop = start.distance(end) <= length * 1e-4;
+        let segments = sweep.segments.max(1);
+        let mut mesh = MeshBuilder::default();
+        for segment in 0..=segments {
+            let distance = length * segment as f32 / segments as f32;
+            let (point, mut tangent) = curve.sample_with_tangent(distance);
+            if is_loop && (segment == 0 || segment == segments) {
+                tangent = (start_tangent + end_tangent)
+                    .try_normalize()
+                    .unwrap_or(tangent);
+            }
+            let left = tangent.perp();
+            for vertex in &vertices {
+                mesh.vertex(
+                    (point + left * vertex.point.x).extend(vertex.point.y),
+                    (left * vertex.normal.x).extend(vertex.normal.y),
+                    Vec2::new(vertex.distance, distance / section_length),
+                    color,
+                );
+            }
+        }
+        add_strips(&mut mesh, vertices.len(), &edges, segments);
+        Some(mesh.build())
+    }
+}
+
+/// The first sub-path of an element with the color of its path.
+fn element_profile(svg: &Svg, id: &str, tolerance: f32) -> Option<(Polyline, [f32; 4])> {
+    let path = svg.element_paths(id).next()?;
+    let polyline = flatten(&path.transformed_segments(), tolerance)
+        .into_iter()
+        .next()?;
+    Some((polyline, path.color.to_linear().to_f32_array()))
+}
+
+/// A vertex of a profile or cross section.
+#[derive(Clone, Copy, Debug)]
+struct SectionVertex {
+    point: Vec2,
+    /// The normal, on the left side of the profile.
+    normal: Vec2,
+    /// The distance along the profile, from `0.0` at the start to `1.0` at the end.
+    distance: f32,
+}
+
+/// Splits a profile into vertices and edges between them. Sharp corners get one vertex per
+/// edge, and a closed profile gets a second vertex at its start for the end of the last edge.
+fn section(
+    points: &[Vec2],
+    closed: bool,
+    smoothing_angle: f32,
+) -> (Vec<SectionVertex>, Vec<[usize; 2]>) {
+    let mut points = points.to_vec();
+    if closed && let Some(first) = points.first() {
+        points.push(*first);
+    }
+    let count = points.len();
+    if count < 2 {
+        return (Vec::new(), Vec::new());
+    }
+    let normals = points
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).normalize_or_zero().perp())
+        .collect::<Vec<_>>();
+    let mut distances = vec![0.0];
+    for pair in points.windows(2) {
+        distances.push(distances.last().unwrap() + pair[0].distance(pair[1]));
+    }
+    let length = distances.last().unwrap().max(f32::EPSILON);
+    let min_cos = smoothing_angle.cos();
+
+    let mut vertices = Vec::new();
+    // the vertex which ends the previous edge and the vertex which starts the next edge
+    let mut ends = Vec::with_capacity(count);
+    for (idx, point) in points.iter().enumerate() {
+        let previous = match idx {
+            0 if closed => normals.last().copied(),
+            0 => None,
+            _ => Some(normals[idx - 1]),
+        };
+        let next = match normals.get(idx) {
+            Some(normal) => Some(*normal),
+            None if closed => normals.first().copied(),
+            None => None,
+        };
+        let mut vertex = |normal: Vec2| {
+            vertices.push(SectionVertex {
+                point: *point,
+                normal,
+                distance: distances[idx] / length,
+            });
+            vertices.len() - 1
+        };
+        ends.push(match (previous, next) {
+            (Some(previous), Some(next)) if previous.dot(next) < min_cos => {
+                (vertex(previous), vertex(next))
+            }
+            (previous, next) => {
+                let normal = previous.unwrap_or_default() + next.unwrap_or_default();
+                let idx = vertex(normal.normalize_or_zero());
+                (idx, idx)
+            }
+        });
+    }
+    let edges = (0..count - 1)
+        .map(|idx| [ends[idx].1, ends[idx + 1].0])
+        .collect();
+    (vertices, edges)
+}
+
+/// Connects consecutive copies of a profile with `vertex_count` vertices by quads.
+fn add_strips(mesh: &mut MeshBuilder, vertex_count: usize, edges: &[[usize; 2]], segments: u32) {
+    for segment in 0..segments as usize {
+        let (this, next) = (segment * vertex_count, (segment + 1) * vertex_count);
+        for [a, b] in edges {
+            let [a0, b0, a1, b1] = [this + a, this + b, next + a, next + b].map(|idx| idx as u32);
+            mesh.facing_triangle([a0, b0, b1]);
+            mesh.facing_triangle([a0, b1, a1]);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Rasterizes [`Svg`]s into [`Image`]s on the CPU.
+
+use bevy::{
+    asset::RenderAssetUsages,
+    color::{ColorToPacked, Srgba},
+    image::Image,
+    math::{Rect, UVec2, Vec2},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use lyon_path::PathEvent;
+use lyon_tessellation::{FillRule, LineCap, LineJoin};
+use tiny_skia::{Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+use crate::{
+    geometry::path_bounds,
+    svg::{DrawType, PathDescriptor, Svg},
+};
+
+impl Svg {
+    /// Rasterizes the [`Svg`] into an [`Image`] of the given size in pixels.
+    ///
+    /// The [`Svg`] keeps its aspect ratio, it is scaled to fit the image and centered. The image
+    /// has the [`TextureFormat::Rgba8UnormSrgb`] format and is at least one pixel wide and high.
+    pub fn rasterize(&self, size: UVec2) -> Image {
+        let bounds = Rect::from_corners(Vec2::ZERO, self.size);
+        rasterize_paths(&self.paths, bounds, size)
+    }
+
+    /// Rasterizes the element with the given `id`, or all elements in a group with that `id`,
+    /// cropped to their bounds including the strokes. See [`Svg::rasterize`].
+    ///
+    /// Returns `None` if no path belongs to the element.
+    pub fn rasterize_element(&self, id: &str, size: UVec2) -> Option<Image> {
+        let paths = self
+            .paths
+            .iter()
+            .filter(|path| path.has_id(id))
+            .cloned()
+            .collect::<Vec<_>>();
+        let bounds = paths
+            .iter()
+            .filter_map(|path| {
+                let bounds = path_bounds(path)?;
+                Some(match path.draw_type {
+                    DrawType::Stroke(options) => {
+                        let (sx, sy) = path.abs_transform.get_scale();
+                        bounds.inflate(options.line_width / 2.0 * sx.max(sy))
+                    }
+                    DrawType::Fill(_) => bounds,
+                })
+            })
+            .reduce(|a, b| a.union(b))?;
+        Some(rasterize_paths(&paths, bounds, size))
+    }
+}
+
+/// Draws the paths into an image, `bounds` in SVG coordinates is scaled to fit the image.
+fn rasterize_paths(paths: &[PathDescriptor], bounds: Rect, size: UVec2) -> Image {
+    let size = size.max(UVec2::ONE);
+    let mut pixmap = Pixmap::new(size.x, size.y).expect("the size is not zero");
+
+    let scale = (size.as_vec2() / bounds.size().max(Vec2::splat(f32::EPSILON))).min_element();
+    let offset = (size.as_vec2() - bounds.size() * scale) / 2.0 - bounds.min * scale;
+    let view = Transform::from_scale(scale, scale).post_translate(offset.x, offset.y);
+
+    for path in paths {
+        let Some(skia_path) = skia_path(&path.segments) else {
+            continue;
+        };
+        let [r, g, b, a] = Srgba::from(path.color).to_u8_array();
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(r, g, b, a);
+        paint.anti_alias = true;
+        let transform = path.abs_transform.post_concat(view);
+        match path.draw_type {
+            DrawType::Fill(fill_rule) => {
+                let fill_rule = match fill_rule {
+                    FillRule::EvenOdd => tiny_skia::FillRule::EvenOdd,
+                    FillRule::NonZero => tiny_skia::FillRule::Winding,
+                };
+                pixmap.fill_path(&skia_path, &paint, fill_rule, transform, None);
+            }
+            DrawType::Stroke(options) => {
+                let stroke = Stroke {
+                    width: options.line_width,
+                    miter_limit: options.miter_limit,
+                    line_cap: match options.start_cap {
+                        LineCap::Butt => tiny_skia::LineCap::Butt,
+                        LineCap::Square => tiny_skia::LineCap::Square,
+                        LineCap::Round => tiny_skia::LineCap::Round,
+                    },
+                    line_join: match options.line_join {
+                        LineJoin::Miter => tiny_skia::LineJoin::Miter,
+                        LineJoin::MiterClip => tiny_skia::LineJoin::MiterClip,
+                        LineJoin::Round => tiny_skia::LineJoin::Round,
+                        LineJoin::Bevel => tiny_skia::LineJoin::Bevel,
+                    },
+                    dash: None,
+                };
+                pixmap.stroke_path(&skia_path, &paint, &stroke, transform, None);
+            }
+        }
+    }
+
+    // the pixmap is premultiplied, images are not
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+fn skia_path(events: &[PathEvent]) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for event in events {
+        match *event {
+            PathEvent::Begin { at } => builder.move_to(at.x, at.y),
+            PathEvent::Line { to, .. } => builder.line_to(to.x, to.y),
+            PathEvent::Quadratic { ctrl, to, .. } => builder.quad_to(ctrl.x, ctrl.y, to.x, to.y),
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y),
+            PathEvent::End { close: true, .. } => builder.close(),
+            PathEvent::End { close: false, .. } => {}
+        }
+    }
+    builder.finish()
+}
@@ -0,0 +1,110 @@
+//! Anti-aliasing fringe, a band of feathered triangles along the outline of every path.
+//!
+//! The fringe vertices sit on the outline and are moved outwards by the vertex shader, about one
+//! pixel in screen space. Their coverage is `0.0`, so the fragment shader fades the edge out.
+
+use bevy::{
+    math::Vec2,
+    mesh::{MeshVertexAttribute, VertexFormat},
+    platform::collections::HashMap,
+};
+
+use crate::render::vertex_buffer::VertexBuffers;
+
+/// Direction in mesh space in which a fringe vertex is moved, scaled by the miter length.
+pub const ATTRIBUTE_AA_NORMAL: MeshVertexAttribute =
+    MeshVertexAttribute::new("SvgAaNormal", 2_370_658_191, VertexFormat::Float32x2);
+
+/// Coverage of a vertex, `1.0` inside the shape and `0.0` on the outside of the fringe.
+pub const ATTRIBUTE_COVERAGE: MeshVertexAttribute =
+    MeshVertexAttribute::new("SvgCoverage", 2_370_658_192, VertexFormat::Float32);
+
+/// Limits the miter length at sharp corners, as the cosine of half the corner angle.
+const MIN_MITER_COS: f32 = 0.5;
+
+/// Adds a fringe to the tessellation of a single path.
+///
+/// Edges which belong to only one triangle are on the outline, vertices at the same position
+/// are treated as one, since the tessellators may emit the same point more than once.
+pub(crate) fn add_fringe(buffer: &mut VertexBuffers) {
+    let mut by_position = HashMap::new();
+    let canonical = buffer
+        .vertices
+        .iter()
+        .enumerate()
+        .map(|(idx, vertex)| {
+            // `-0.0 + 0.0` is `0.0`, so both zeros have the same bits
+            let key = [vertex.position[0] + 0.0, vertex.position[1] + 0.0].map(f32::to_bits);
+            *by_position.entry(key).or_insert(idx as u32)
+        })
+        .collect::<Vec<_>>();
+    let position = |idx: u32| {
+        let [x, y, _] = buffer.vertices[idx as usize].position;
+        Vec2::new(x, y)
+    };
+
+    // the number of triangles of every edge, with the last edge and its opposite vertex
+    let mut edges: HashMap<(u32, u32), (u32, [u32; 3])> = HashMap::new();
+    for triangle in buffer.indices.chunks_exact(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| canonical[i as usize]);
+        for edge in [[a, b, c], [b, c, a], [c, a, b]] {
+            if edge[0] == edge[1] {
+                continue;
+            }
+            let key = (edge[0].min(edge[1]), edge[0].max(edge[1]));
+            let entry = edges.entry(key).or_insert((0, edge));
+            entry.0 += 1;
+        }
+    }
+    let mut outline = edges
+        .into_values()
+        .filter(|(count, _)| *count == 1)
+        .map(|(_, edge)| edge)
+        .collect::<Vec<_>>();
+    if outline.is_empty() {
+        return;
+    }
+    // hash maps are not ordered, but the mesh should be the same every time
+    outline.sort_unstable();
+
+    let mut normals: HashMap<u32, Vec<Vec2>> = HashMap::new();
+    let outline = outline
+        .into_iter()
+        .filter_map(|[a, b, c]| {
+            let (pa, pb) = (position(a), position(b));
+            let normal = (pb - pa).perp().try_normalize()?;
+            // the normal points away from the triangle
+            let normal = if normal.dot(position(c) - pa) > 0.0 {
+                -normal
+            } else {
+                normal
+            };
+            normals.entry(a).or_default().push(normal);
+            normals.entry(b).or_default().push(normal);
+            Some((a, b))
+        })
+        .collect::<Vec<_>>();
+
+    let mut fringe = HashMap::with_capacity(normals.len());
+    let mut sorted = normals.into_iter().collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|(idx, _)| *idx);
+    for (idx, normals) in sorted {
+        let sum = normals.iter().copied().sum::<Vec2>();
+        let direction = sum.try_normalize().unwrap_or(normals[0]);
+        let cos = normals
+            .iter()
+            .map(|normal| normal.dot(direction))
+            .fold(1.0, f32::min)
+            .max(MIN_MITER_COS);
+        let mut vertex = buffer.vertices[idx as usize];
+        vertex.aa_normal = (direction / cos).to_array();
+        vertex.coverage = 0.0;
+        fringe.insert(idx, buffer.vertices.len() as u32);
+        buffer.vertices.push(vertex);
+    }
+
+    for (a, b) in outline {
+        let (fa, fb) = (fringe[&a], fringe[&b]);
+        buffer.indices.extend_from_slice(&[a, b, fb, a, fb, fa]);
+    }
+}
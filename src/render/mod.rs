@@ -1,6 +1,8 @@
+pub(crate) mod antialias;
+mod pipeline;
 mod plugin;
 pub mod tessellation;
-mod vertex_buffer;
+pub(crate) mod vertex_buffer;
 
 #[cfg(feature = "2d")]
 mod svg2d;
@@ -8,8 +10,24 @@ mod svg2d;
 mod svg3d;
 
 #[cfg(feature = "2d")]
-pub use svg2d::Svg2d;
+pub use svg2d::{SVG_2D_SHADER_HANDLE, Svg2d, SvgMaterial2d};
 #[cfg(feature = "3d")]
-pub use svg3d::Svg3d;
+pub use svg3d::{SVG_3D_SHADER_HANDLE, Svg3d, SvgBillboard, SvgLit, SvgMaterial3d};
 
+pub use pipeline::specialize as specialize_svg_pipeline;
 pub use plugin::SvgPlugin;
+
+use bevy::{
+    asset::{Handle, uuid_handle},
+    ecs::component::Component,
+    shader::Shader,
+};
+
+/// Handle to the shader functions shared by the 2D and 3D shaders, `bevy_svg::svg_functions`.
+pub const SVG_FUNCTIONS_SHADER_HANDLE: Handle<Shader> =
+    uuid_handle!("00000000-0000-0000-762a-bdb5f2c1e0a1");
+
+/// Marks an [`Svg2d`] or [`Svg3d`] entity which is rendered with a custom material instead of
+/// the [`Svg`](crate::svg::Svg) itself.
+#[derive(Component, Default)]
+pub(crate) struct SvgCustomMaterial;
@@ -0,0 +1,59 @@
+//! Vertex layout and shader definitions of the 2D and 3D [`Svg`](crate::svg::Svg) materials.
+
+use bevy::{
+    mesh::{Mesh, MeshVertexBufferLayoutRef},
+    render::render_resource::{RenderPipelineDescriptor, SpecializedMeshPipelineError},
+};
+
+use crate::render::{
+    antialias::{ATTRIBUTE_AA_NORMAL, ATTRIBUTE_COVERAGE},
+    vertex_buffer::ATTRIBUTE_LAYER,
+};
+
+/// Shader definition which enables the anti-aliasing fringe in `svg_2d.wgsl` and `svg_3d.wgsl`.
+const ANTIALIAS_SHADER_DEF: &str = "SVG_ANTIALIAS";
+
+/// Shader definition which enables the per-path depth bias in `svg_3d.wgsl`.
+const LAYERS_SHADER_DEF: &str = "SVG_LAYERS";
+
+/// Sets the vertex layout of the `vertex` function in `svg_2d.wgsl` and `svg_3d.wgsl`.
+///
+/// Custom materials of an [`SvgMaterial2d`](crate::prelude::SvgMaterial2d) or
+/// [`SvgMaterial3d`](crate::prelude::SvgMaterial3d) which use these vertex shaders call this in
+/// their `specialize`. It also adds the `SVG_ANTIALIAS` shader definition to the fragment shader
+/// if the mesh has an anti-aliasing fringe, which `svg_functions::fragment_color` needs.
+///
+/// Prepass and shadow pipelines keep their shaders and layout, they only need the positions.
+pub fn specialize(
+    descriptor: &mut RenderPipelineDescriptor,
+    layout: &MeshVertexBufferLayoutRef,
+) -> Result<(), SpecializedMeshPipelineError> {
+    if descriptor
+        .vertex
+        .shader_defs
+        .contains(&"PREPASS_PIPELINE".into())
+    {
+        return Ok(());
+    }
+    let mut attributes = vec![
+        Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+        Mesh::ATTRIBUTE_COLOR.at_shader_location(4),
+    ];
+    if layout.0.contains(ATTRIBUTE_COVERAGE) {
+        attributes.push(ATTRIBUTE_AA_NORMAL.at_shader_location(8));
+        attributes.push(ATTRIBUTE_COVERAGE.at_shader_location(9));
+        descriptor
+            .vertex
+            .shader_defs
+            .push(ANTIALIAS_SHADER_DEF.into());
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader_defs.push(ANTIALIAS_SHADER_DEF.into());
+        }
+    }
+    if layout.0.contains(ATTRIBUTE_LAYER) {
+        attributes.push(ATTRIBUTE_LAYER.at_shader_location(10));
+        descriptor.vertex.shader_defs.push(LAYERS_SHADER_DEF.into());
+    }
+    descriptor.vertex.buffers = vec![layout.0.get_layout(&attributes)?];
+    Ok(())
+}
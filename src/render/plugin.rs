@@ -1,5 +1,12 @@
-use crate::resources::{FillTessellator, StrokeTessellator};
-use bevy::app::{App, Plugin};
+use crate::{
+    render::SVG_FUNCTIONS_SHADER_HANDLE,
+    resources::{FillTessellator, StrokeTessellator},
+};
+use bevy::{
+    app::{App, Plugin},
+    asset::load_internal_asset,
+    shader::Shader,
+};
 
 #[cfg(feature = "2d")]
 use crate::render::svg2d;
@@ -14,6 +21,12 @@ impl Plugin for SvgPlugin {
         let fill_tess = FillTessellator::default();
         let stroke_tess = StrokeTessellator::default();
         app.insert_resource(fill_tess).insert_resource(stroke_tess);
+        load_internal_asset!(
+            app,
+            SVG_FUNCTIONS_SHADER_HANDLE,
+            "svg_functions.wgsl",
+            Shader::from_wgsl
+        );
 
         #[cfg(feature = "2d")]
         app.add_plugins(svg2d::RenderPlugin);
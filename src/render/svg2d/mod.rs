@@ -1,10 +1,10 @@
-use crate::{origin::Origin, svg::Svg};
+use crate::{origin::Origin, render::SvgCustomMaterial, svg::Svg};
 use bevy::{
     asset::{Handle, uuid_handle},
     ecs::{component::Component, lifecycle::HookContext, world::DeferredWorld},
     mesh::Mesh2d,
     shader::Shader,
-    sprite_render::MeshMaterial2d,
+    sprite_render::{Material2d, MeshMaterial2d},
 };
 
 mod plugin;
@@ -22,9 +22,64 @@ pub use plugin::RenderPlugin;
 pub struct Svg2d(pub Handle<Svg>);
 
 fn svg_2d_on_insert(mut world: DeferredWorld, ctx: HookContext) {
+    if world.entity(ctx.entity).contains::<SvgCustomMaterial>() {
+        return;
+    }
     let component = world.entity(ctx.entity).get_components::<&Svg2d>().unwrap();
     let handle = component.0.clone();
     let entity = world.entity(ctx.entity).id();
     let mut commands = world.commands();
     commands.entity(entity).insert(MeshMaterial2d(handle));
 }
+
+/// Renders an [`Svg2d`] with a custom [`Material2d`] instead of the SVG material, e.g. for
+/// dissolve, hologram or outline effects.
+///
+/// The material has to be added with a
+/// [`Material2dPlugin`](bevy::sprite_render::Material2dPlugin). Like the
+/// [`SvgMaterial3d`](crate::prelude::SvgMaterial3d), a material which returns
+/// [`SVG_2D_SHADER_HANDLE`] as vertex shader and calls
+/// [`specialize_svg_pipeline`](crate::prelude::specialize_svg_pipeline) in its `specialize` keeps
+/// the [`SvgTint`](crate::prelude::SvgTint) and the anti-aliasing fringe.
+///
+/// Removing the component renders the [`Svg2d`] with the SVG material again.
+#[derive(Component)]
+#[require(SvgCustomMaterial)]
+#[component(on_insert = svg_material_on_insert::<M>, on_remove = svg_material_on_remove::<M>)]
+pub struct SvgMaterial2d<M: Material2d>(pub Handle<M>);
+
+impl<M: Material2d> Clone for SvgMaterial2d<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+fn svg_material_on_insert<M: Material2d>(mut world: DeferredWorld, ctx: HookContext) {
+    let material = world
+        .entity(ctx.entity)
+        .get::<SvgMaterial2d<M>>()
+        .unwrap()
+        .0
+        .clone();
+    world
+        .commands()
+        .entity(ctx.entity)
+        .remove::<MeshMaterial2d<Svg>>()
+        .insert(MeshMaterial2d(material));
+}
+
+fn svg_material_on_remove<M: Material2d>(mut world: DeferredWorld, ctx: HookContext) {
+    let Some(handle) = world
+        .entity(ctx.entity)
+        .get::<Svg2d>()
+        .map(|svg| svg.0.clone())
+    else {
+        return;
+    };
+    // the entity may be despawned
+    world
+        .commands()
+        .entity(ctx.entity)
+        .try_remove::<(MeshMaterial2d<M>, SvgCustomMaterial)>()
+        .try_insert(MeshMaterial2d(handle));
+}
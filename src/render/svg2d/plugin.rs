@@ -1,9 +1,14 @@
-use crate::{render::svg2d::SVG_2D_SHADER_HANDLE, svg::Svg};
+use crate::{
+    render::{pipeline, svg2d::SVG_2D_SHADER_HANDLE},
+    svg::Svg,
+};
 use bevy::{
     app::{App, Plugin},
     asset::{AssetApp, load_internal_asset},
+    mesh::MeshVertexBufferLayoutRef,
+    render::render_resource::{RenderPipelineDescriptor, SpecializedMeshPipelineError},
     shader::{Shader, ShaderRef},
-    sprite_render::{Material2d, Material2dPlugin},
+    sprite_render::{AlphaMode2d, Material2d, Material2dKey, Material2dPlugin},
 };
 
 /// Plugin that renders [`Svg`](crate::svg::Svg)s in 2D
@@ -19,7 +24,27 @@ impl Plugin for RenderPlugin {
 }
 
 impl Material2d for Svg {
+    fn vertex_shader() -> ShaderRef {
+        SVG_2D_SHADER_HANDLE.into()
+    }
+
     fn fragment_shader() -> ShaderRef {
         SVG_2D_SHADER_HANDLE.into()
     }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        if self.antialias {
+            AlphaMode2d::Blend
+        } else {
+            AlphaMode2d::Opaque
+        }
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        pipeline::specialize(descriptor, layout)
+    }
 }
@@ -1,8 +1,9 @@
 #import bevy_sprite::{
+    mesh2d_functions as mesh_functions,
     mesh2d_types::Mesh2d,
     mesh2d_view_bindings::view,
-    mesh2d_vertex_output::VertexOutput,
 }
+#import bevy_svg::svg_functions
 
 #ifdef TONEMAP_IN_SHADER
 #import bevy_core_pipeline::tonemapping
@@ -12,15 +13,51 @@
 @group(2) @binding(0)
 var<uniform> mesh: Mesh2d;
 
+// Width of the anti-aliasing fringe in pixels.
+const AA_WIDTH: f32 = 1.0;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(4) color: vec4<f32>,
+#ifdef SVG_ANTIALIAS
+    @location(8) aa_normal: vec2<f32>,
+    @location(9) coverage: f32,
+#endif
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> svg_functions::SvgVertexOutput {
+    var out: svg_functions::SvgVertexOutput;
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    var world_position = mesh_functions::mesh2d_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0)
+    );
+#ifdef SVG_ANTIALIAS
+    let world_normal = (world_from_local * vec4<f32>(vertex.aa_normal, 0.0, 0.0)).xyz;
+    let offset = svg_functions::antialias_offset(
+        view.clip_from_world,
+        view.viewport.zw,
+        world_position,
+        world_normal,
+        length(vertex.aa_normal),
+        AA_WIDTH,
+    );
+    world_position = vec4<f32>(world_position.xyz + offset, world_position.w);
+    out.coverage = vertex.coverage;
+#endif
+    out.position = mesh_functions::mesh2d_position_world_to_clip(world_position);
+    let tag = mesh_functions::get_tag(vertex.instance_index);
+    out.color = vertex.color * svg_functions::unpack_tint(tag);
+    return out;
+}
+
 @fragment
-fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
-#ifdef VERTEX_COLORS
-    var color = in.color;
+fn fragment(in: svg_functions::SvgVertexOutput) -> @location(0) vec4<f32> {
+    var color = svg_functions::fragment_color(in);
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
 #endif
     return color;
-#else
-    return vec4<f32>(1.0, 0.0, 1.0, 1.0);
-#endif
 }
@@ -0,0 +1,247 @@
+//! Camera facing [`Svg3d`]s, rotated in the vertex shader.
+
+use bevy::{
+    asset::{Asset, AssetEvent, AssetId, Assets, Handle},
+    camera::{primitives::Aabb, visibility::NoAutoAabb},
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        component::Component,
+        entity::Entity,
+        lifecycle::RemovedComponents,
+        message::MessageReader,
+        resource::Resource,
+        system::{Commands, Query, Res, ResMut},
+    },
+    math::{Vec2, Vec3A},
+    mesh::MeshVertexBufferLayoutRef,
+    pbr::{Material, MaterialPipeline, MaterialPipelineKey},
+    platform::collections::HashMap,
+    reflect::TypePath,
+    render::{
+        alpha::AlphaMode,
+        render_resource::{AsBindGroup, RenderPipelineDescriptor, SpecializedMeshPipelineError},
+    },
+    shader::ShaderRef,
+};
+
+use super::{SVG_3D_SHADER_HANDLE, Svg3d, SvgMaterial3d, plugin::ALPHA_MASK_SHADER_DEF};
+use crate::{
+    origin::Origin,
+    render::pipeline,
+    svg::{Svg, SvgTransparency},
+};
+
+/// Shader definition which turns the mesh towards the camera in `svg_3d.wgsl`, together with the
+/// definition of the [`SvgBillboard`] mode.
+const BILLBOARD_SHADER_DEF: &str = "SVG_BILLBOARD";
+
+/// Turns an [`Svg3d`] towards the camera, e.g. for markers and status icons above units.
+///
+/// The rotation is done in the vertex shader, so the transforms of the entities are not touched
+/// and entities which show the same [`Svg`] with the same mode and [`Origin`] are still drawn
+/// with a single instanced draw call. The [`Origin`] is the pivot of the billboard, and the
+/// scale of the transform is kept.
+///
+/// Billboards are rendered with their own material instead of the SVG material, so they can't
+/// be combined with an [`SvgLit`](super::SvgLit) or [`SvgMaterial3d`]. They don't cast shadows
+/// and are not drawn in depth prepasses. Removing the component renders the [`Svg3d`] with the
+/// SVG material again.
+#[derive(Clone, Component, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SvgBillboard {
+    /// Faces the camera, the SVG is parallel to the view plane.
+    #[default]
+    Spherical,
+    /// Only rotates around the y-axis of the entity, e.g. to keep signs upright. Rotate the
+    /// entity to lock the SVG to another axis.
+    Cylindrical,
+}
+
+impl SvgBillboard {
+    fn shader_def(&self) -> &'static str {
+        match self {
+            SvgBillboard::Spherical => "SVG_BILLBOARD_SPHERICAL",
+            SvgBillboard::Cylindrical => "SVG_BILLBOARD_CYLINDRICAL",
+        }
+    }
+}
+
+/// Material of [`SvgBillboard`] entities, shared by all entities with the same [`Svg`], mode
+/// and [`Origin`].
+#[derive(Asset, AsBindGroup, TypePath, Clone, Debug)]
+#[bind_group_data(SvgBillboardKey)]
+pub(crate) struct SvgBillboardMaterial {
+    /// The pivot of the billboard in the coordinates of the mesh.
+    #[uniform(0)]
+    pivot: Vec2,
+    mode: SvgBillboard,
+    transparency: SvgTransparency,
+}
+
+impl SvgBillboardMaterial {
+    fn new(svg: &Svg, mode: SvgBillboard, origin: Origin) -> Self {
+        Self {
+            pivot: billboard_pivot(svg, origin),
+            mode,
+            transparency: svg.transparency(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct SvgBillboardKey {
+    mode: SvgBillboard,
+    transparency: SvgTransparency,
+}
+
+impl From<&SvgBillboardMaterial> for SvgBillboardKey {
+    fn from(material: &SvgBillboardMaterial) -> Self {
+        Self {
+            mode: material.mode,
+            transparency: material.transparency,
+        }
+    }
+}
+
+impl Material for SvgBillboardMaterial {
+    fn vertex_shader() -> ShaderRef {
+        SVG_3D_SHADER_HANDLE.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        SVG_3D_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        match self.transparency {
+            SvgTransparency::Opaque => AlphaMode::Opaque,
+            SvgTransparency::Mask => AlphaMode::Mask(0.5),
+            SvgTransparency::Blend => AlphaMode::Blend,
+        }
+    }
+
+    // the prepass and shadow shaders don't rotate the mesh
+    fn enable_prepass() -> bool {
+        false
+    }
+
+    fn enable_shadows() -> bool {
+        false
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
+    ) -> bevy::prelude::Result<(), SpecializedMeshPipelineError> {
+        descriptor.primitive.cull_mode = None;
+        descriptor.vertex.shader_defs.extend([
+            BILLBOARD_SHADER_DEF.into(),
+            key.bind_group_data.mode.shader_def().into(),
+        ]);
+        if key.bind_group_data.transparency == SvgTransparency::Mask
+            && let Some(fragment) = descriptor.fragment.as_mut()
+        {
+            fragment.shader_defs.push(ALPHA_MASK_SHADER_DEF.into());
+        }
+
+        pipeline::specialize(descriptor, layout)
+    }
+}
+
+/// The billboard materials by [`Svg`], mode and the bits of the pivot fraction of the [`Origin`].
+#[derive(Default, Resource)]
+pub(crate) struct SvgBillboardMaterials(
+    HashMap<(AssetId<Svg>, SvgBillboard, [u32; 2]), Handle<SvgBillboardMaterial>>,
+);
+
+/// Returns where the [`Origin`] is in the coordinates of the mesh, which has its y-axis flipped.
+fn billboard_pivot(svg: &Svg, origin: Origin) -> Vec2 {
+    // the origin moves the entity by minus the scaled pivot
+    -origin.compute_translation(svg.size).truncate()
+}
+
+/// Returns the bounds of a billboard in any rotation around its pivot.
+fn billboard_aabb(svg: &Svg, pivot: Vec2) -> Aabb {
+    let radius = [Vec2::ZERO, Vec2::new(svg.size.x, -svg.size.y)]
+        .into_iter()
+        .map(|corner| (corner - pivot).abs())
+        .reduce(Vec2::max)
+        .unwrap_or_default()
+        .length();
+    Aabb {
+        center: Vec3A::from(pivot.extend(0.0)),
+        half_extents: Vec3A::splat(radius),
+    }
+}
+
+/// Gives [`SvgBillboard`] entities the billboard material of their [`Svg`], and the SVG
+/// material back when the component is removed.
+pub(crate) fn apply_svg_billboards(
+    mut commands: Commands,
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut materials: ResMut<Assets<SvgBillboardMaterial>>,
+    mut billboard_materials: ResMut<SvgBillboardMaterials>,
+    billboards: Query<(Entity, Ref<Svg3d>, Ref<SvgBillboard>, Ref<Origin>)>,
+    mut removed: RemovedComponents<SvgBillboard>,
+) {
+    let mut modified = Vec::new();
+    for event in svg_events.read() {
+        match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => {
+                modified.push(*id);
+            }
+            AssetEvent::Removed { id } => {
+                billboard_materials.0.retain(|(svg, ..), _| svg != id);
+            }
+            _ => (),
+        }
+    }
+
+    for entity in removed.read() {
+        if billboards.contains(entity) {
+            continue;
+        }
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.try_remove::<(SvgMaterial3d<SvgBillboardMaterial>, Aabb, NoAutoAabb)>();
+        }
+    }
+
+    for (entity, svg_3d, billboard, origin) in &billboards {
+        let changed = svg_3d.is_changed() || billboard.is_changed() || origin.is_changed();
+        if !changed && !modified.contains(&svg_3d.0.id()) {
+            continue;
+        }
+        let Some(svg) = svgs.get(&svg_3d.0) else {
+            continue;
+        };
+        let (mode, origin) = (*billboard, *origin);
+        let pivot = billboard_pivot(svg, origin);
+        let key = (
+            svg_3d.0.id(),
+            mode,
+            // an empty Svg would divide by zero
+            (pivot / svg.size.max(Vec2::splat(f32::EPSILON)))
+                .to_array()
+                .map(f32::to_bits),
+        );
+        let material = billboard_materials
+            .0
+            .entry(key)
+            .or_insert_with(|| materials.add(SvgBillboardMaterial::new(svg, mode, origin)))
+            .clone();
+        if modified.contains(&svg_3d.0.id())
+            && let Some(material) = materials.get_mut(&material)
+        {
+            *material = SvgBillboardMaterial::new(svg, mode, origin);
+        }
+        commands.entity(entity).try_insert((
+            SvgMaterial3d(material),
+            billboard_aabb(svg, pivot),
+            NoAutoAabb,
+        ));
+    }
+}
@@ -2,30 +2,169 @@ use bevy::{
     asset::{Handle, uuid_handle},
     ecs::{component::Component, lifecycle::HookContext, world::DeferredWorld},
     mesh::Mesh3d,
-    pbr::MeshMaterial3d,
+    pbr::{Material, MeshMaterial3d, StandardMaterial},
     shader::Shader,
 };
 
+mod billboard;
 mod plugin;
 
 /// Handle to the custom shader with a unique random ID
 pub const SVG_3D_SHADER_HANDLE: Handle<Shader> =
     uuid_handle!("00000000-0000-0000-762a-bdb74c2a5c66");
 
+/// Handle to the prepass fragment shader, which cuts transparent paths out of depth and shadow
+/// maps.
+pub const SVG_3D_PREPASS_SHADER_HANDLE: Handle<Shader> =
+    uuid_handle!("00000000-0000-0000-762a-bdb74c2a5c67");
+
+pub use billboard::SvgBillboard;
 pub use plugin::RenderPlugin;
 
-use crate::{origin::Origin, svg::Svg};
+use crate::{origin::Origin, render::SvgCustomMaterial, svg::Svg};
 
 /// A component for 3D SVGs.
+///
+/// The alpha mode of the SVG material follows the [`Svg::transparency`]: opaque SVGs are drawn
+/// with depth writes, fully transparent paths are cut out with an alpha mask and semi-transparent
+/// or anti-aliased SVGs are alpha blended and sorted back to front. The SVG casts shadows with
+/// transparent paths cut out. Add a [`NotShadowCaster`](bevy::light::NotShadowCaster) to an
+/// entity to turn that off, and a [`NotShadowReceiver`](bevy::light::NotShadowReceiver) to keep
+/// shadows off an entity with a lit material like [`SvgLit`], the SVG material itself is unlit.
+///
+/// All paths of an [`Svg`] are in one plane, the SVG material keeps them in paint order with a
+/// small depth bias per path. Other materials don't, an [`Svg::layer_spacing`] separates the
+/// paths for them.
 #[derive(Component, Default)]
 #[require(Mesh3d, Origin, MeshMaterial3d<Svg>)]
 #[component(on_insert = svg_3d_on_insert)]
 pub struct Svg3d(pub Handle<Svg>);
 
 fn svg_3d_on_insert(mut world: DeferredWorld, ctx: HookContext) {
+    if world.entity(ctx.entity).contains::<SvgCustomMaterial>() {
+        return;
+    }
     let component = world.entity(ctx.entity).get_components::<&Svg3d>().unwrap();
     let handle = component.0.clone();
     let entity = world.entity(ctx.entity).id();
     let mut commands = world.commands();
     commands.entity(entity).insert(MeshMaterial3d(handle));
 }
+
+/// Renders an [`Svg3d`] with a custom [`Material`] instead of the SVG material, e.g. for
+/// dissolve, hologram or outline effects.
+///
+/// The material has to be added with a [`MaterialPlugin`](bevy::pbr::MaterialPlugin). It gets
+/// the mesh of the [`Svg`], with positions, vertex colors, normals facing the positive z-axis,
+/// UVs across the size of the [`Svg`] and tangents. A material which returns
+/// [`SVG_3D_SHADER_HANDLE`] as vertex shader and calls
+/// [`specialize_svg_pipeline`](crate::prelude::specialize_svg_pipeline) in its `specialize` keeps
+/// the [`SvgTint`](crate::prelude::SvgTint) and the anti-aliasing fringe. Its fragment shader
+/// takes a `bevy_svg::svg_functions::SvgVertexOutput`, whose color is returned by
+/// `svg_functions::fragment_color`:
+///
+/// ```wgsl
+/// #import bevy_svg::svg_functions
+///
+/// @group(#{MATERIAL_BIND_GROUP}) @binding(0) var<uniform> progress: f32;
+///
+/// @fragment
+/// fn fragment(in: svg_functions::SvgVertexOutput) -> @location(0) vec4<f32> {
+///     var color = svg_functions::fragment_color(in);
+///     color.a *= progress;
+///     return color;
+/// }
+/// ```
+///
+/// Removing the component renders the [`Svg3d`] with the SVG material again.
+#[derive(Component)]
+#[require(SvgCustomMaterial)]
+#[component(on_insert = svg_material_on_insert::<M>, on_remove = svg_material_on_remove::<M>)]
+pub struct SvgMaterial3d<M: Material>(pub Handle<M>);
+
+impl<M: Material> Clone for SvgMaterial3d<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+fn svg_material_on_insert<M: Material>(mut world: DeferredWorld, ctx: HookContext) {
+    let material = world
+        .entity(ctx.entity)
+        .get::<SvgMaterial3d<M>>()
+        .unwrap()
+        .0
+        .clone();
+    replace_material(&mut world, ctx, material);
+}
+
+fn svg_material_on_remove<M: Material>(mut world: DeferredWorld, ctx: HookContext) {
+    restore_material::<M>(&mut world, ctx);
+}
+
+/// Replaces the SVG material of an entity with the given material.
+fn replace_material<M: Material>(world: &mut DeferredWorld, ctx: HookContext, material: Handle<M>) {
+    world
+        .commands()
+        .entity(ctx.entity)
+        .remove::<MeshMaterial3d<Svg>>()
+        .insert(MeshMaterial3d(material));
+}
+
+/// Replaces the material `M` of an entity with the SVG material of its [`Svg3d`].
+fn restore_material<M: Material>(world: &mut DeferredWorld, ctx: HookContext) {
+    let Some(handle) = world
+        .entity(ctx.entity)
+        .get::<Svg3d>()
+        .map(|svg| svg.0.clone())
+    else {
+        return;
+    };
+    // the entity may be despawned
+    world
+        .commands()
+        .entity(ctx.entity)
+        .try_remove::<(MeshMaterial3d<M>, SvgCustomMaterial)>()
+        .try_insert(MeshMaterial3d(handle));
+}
+
+/// Renders an [`Svg3d`] with a [`StandardMaterial`] instead of the unlit SVG material, so it is
+/// lit and affected by fog and shadows.
+///
+/// The colors of the SVG are vertex colors, which are multiplied with the `base_color` of the
+/// material. An [`SvgTint`](crate::prelude::SvgTint) has no effect and the anti-aliasing fringe
+/// is not drawn. The mesh of an [`Svg`] is a plane facing the positive z-axis, so a material
+/// with `double_sided: true` and `cull_mode: None` also lights its back.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_svg::prelude::*;
+/// fn setup(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+///     mut materials: ResMut<Assets<StandardMaterial>>,
+/// ) {
+///     commands.spawn((
+///         Svg3d(asset_server.load("box.svg")),
+///         SvgLit(materials.add(StandardMaterial {
+///             perceptual_roughness: 0.8,
+///             double_sided: true,
+///             cull_mode: None,
+///             ..Default::default()
+///         })),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default)]
+#[require(SvgCustomMaterial)]
+#[component(on_insert = svg_lit_on_insert, on_remove = svg_lit_on_remove)]
+pub struct SvgLit(pub Handle<StandardMaterial>);
+
+fn svg_lit_on_insert(mut world: DeferredWorld, ctx: HookContext) {
+    let material = world.entity(ctx.entity).get::<SvgLit>().unwrap().0.clone();
+    replace_material(&mut world, ctx, material);
+}
+
+fn svg_lit_on_remove(mut world: DeferredWorld, ctx: HookContext) {
+    restore_material::<StandardMaterial>(&mut world, ctx);
+}
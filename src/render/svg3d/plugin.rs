@@ -1,39 +1,87 @@
-use super::SVG_3D_SHADER_HANDLE;
-use crate::svg::Svg;
+use super::{
+    SVG_3D_PREPASS_SHADER_HANDLE, SVG_3D_SHADER_HANDLE,
+    billboard::{SvgBillboardMaterial, SvgBillboardMaterials, apply_svg_billboards},
+};
+use crate::{
+    plugin::SvgSet,
+    render::pipeline,
+    svg::{Svg, SvgTransparency},
+};
 use bevy::{
-    app::{App, Plugin},
+    app::{App, Last, Plugin},
     asset::{AssetApp, load_internal_asset},
+    ecs::schedule::IntoScheduleConfigs,
     mesh::MeshVertexBufferLayoutRef,
     pbr::{Material, MaterialPipeline, MaterialPipelineKey, MaterialPlugin},
-    render::render_resource::{RenderPipelineDescriptor, SpecializedMeshPipelineError},
+    render::{
+        alpha::AlphaMode,
+        render_resource::{RenderPipelineDescriptor, SpecializedMeshPipelineError},
+    },
     shader::{Shader, ShaderRef},
 };
 
+/// Shader definition which makes the 3D shaders discard transparent fragments of an
+/// [`SvgTransparency::Mask`] [`Svg`].
+pub(super) const ALPHA_MASK_SHADER_DEF: &str = "SVG_ALPHA_MASK";
+
 /// Plugin that renders [`Svg`](crate::svg::Svg)s in 2D
 pub struct RenderPlugin;
 
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(app, SVG_3D_SHADER_HANDLE, "svg_3d.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            SVG_3D_PREPASS_SHADER_HANDLE,
+            "svg_3d_prepass.wgsl",
+            Shader::from_wgsl
+        );
 
-        app.add_plugins(MaterialPlugin::<Svg>::default())
-            .register_asset_reflect::<Svg>();
+        app.add_plugins((
+            MaterialPlugin::<Svg>::default(),
+            MaterialPlugin::<SvgBillboardMaterial>::default(),
+        ))
+        .register_asset_reflect::<Svg>()
+        .init_resource::<SvgBillboardMaterials>()
+        .add_systems(Last, apply_svg_billboards.in_set(SvgSet));
     }
 }
 
 impl Material for Svg {
+    fn vertex_shader() -> ShaderRef {
+        SVG_3D_SHADER_HANDLE.into()
+    }
+
     fn fragment_shader() -> ShaderRef {
         SVG_3D_SHADER_HANDLE.into()
     }
 
+    fn prepass_fragment_shader() -> ShaderRef {
+        SVG_3D_PREPASS_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode {
+        match self.transparency() {
+            SvgTransparency::Opaque => AlphaMode::Opaque,
+            SvgTransparency::Mask => AlphaMode::Mask(0.5),
+            SvgTransparency::Blend => AlphaMode::Blend,
+        }
+    }
+
     fn specialize(
         _pipeline: &MaterialPipeline,
         descriptor: &mut RenderPipelineDescriptor,
-        _layout: &MeshVertexBufferLayoutRef,
-        _key: MaterialPipelineKey<Self>,
+        layout: &MeshVertexBufferLayoutRef,
+        key: MaterialPipelineKey<Self>,
     ) -> bevy::prelude::Result<(), SpecializedMeshPipelineError> {
         descriptor.primitive.cull_mode = None;
 
-        Ok(())
+        if key.bind_group_data == SvgTransparency::Mask
+            && let Some(fragment) = descriptor.fragment.as_mut()
+        {
+            fragment.shader_defs.push(ALPHA_MASK_SHADER_DEF.into());
+        }
+
+        pipeline::specialize(descriptor, layout)
     }
 }
@@ -1,18 +1,124 @@
 #import bevy_pbr::{
-    forward_io::{VertexOutput, FragmentOutput}
+    forward_io::FragmentOutput,
+    mesh_functions,
     mesh_types::Mesh,
-    mesh_view_bindings,
+    mesh_view_bindings::view,
+    view_transformations::position_world_to_clip,
 }
+#import bevy_svg::svg_functions
 
 @group(2) @binding(0)
 var<uniform> mesh: Mesh;
 
+#ifdef SVG_BILLBOARD
+// The pivot of the billboard in the coordinates of the mesh.
+@group(#{MATERIAL_BIND_GROUP}) @binding(0)
+var<uniform> billboard_pivot: vec2<f32>;
+#endif
+
+// Width of the anti-aliasing fringe in pixels.
+const AA_WIDTH: f32 = 1.0;
+
+// Relative depth offset between a path and the path below it, a few steps of 32 bit float depth.
+const LAYER_DEPTH_BIAS: f32 = 2.4e-7;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(4) color: vec4<f32>,
+#ifdef SVG_ANTIALIAS
+    @location(8) aa_normal: vec2<f32>,
+    @location(9) coverage: f32,
+#endif
+#ifdef SVG_LAYERS
+    @location(10) layer: f32,
+#endif
+};
+
+#ifdef SVG_BILLBOARD
+// Replaces the rotation of the mesh with one which turns it towards the camera, around the pivot.
+fn billboard(world_from_local: mat4x4<f32>) -> mat4x4<f32> {
+    let scale = vec3<f32>(
+        length(world_from_local[0].xyz),
+        length(world_from_local[1].xyz),
+        length(world_from_local[2].xyz),
+    );
+    // the origin of the entity is moved by minus the scaled pivot in world space
+    let pivot = world_from_local[3].xyz + vec3<f32>(billboard_pivot * scale.xy, 0.0);
+    let backward = view.world_from_view[2].xyz;
+#ifdef SVG_BILLBOARD_CYLINDRICAL
+    let up = world_from_local[1].xyz / scale.y;
+    var right = cross(up, backward);
+    if dot(right, right) < 1e-8 {
+        // looking along the axis
+        right = view.world_from_view[0].xyz;
+    }
+    right = normalize(right);
+    let forward = cross(right, up);
+#else
+    let right = view.world_from_view[0].xyz;
+    let up = view.world_from_view[1].xyz;
+    let forward = backward;
+#endif
+    let x = right * scale.x;
+    let y = up * scale.y;
+    return mat4x4<f32>(
+        vec4<f32>(x, 0.0),
+        vec4<f32>(y, 0.0),
+        vec4<f32>(forward * scale.z, 0.0),
+        vec4<f32>(pivot - x * billboard_pivot.x - y * billboard_pivot.y, 1.0),
+    );
+}
+#endif
+
+@vertex
+fn vertex(vertex: Vertex) -> svg_functions::SvgVertexOutput {
+    var out: svg_functions::SvgVertexOutput;
+#ifdef SVG_BILLBOARD
+    let world_from_local = billboard(mesh_functions::get_world_from_local(vertex.instance_index));
+#else
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+#endif
+    var world_position = mesh_functions::mesh_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0)
+    );
+#ifdef SVG_ANTIALIAS
+    let world_normal = (world_from_local * vec4<f32>(vertex.aa_normal, 0.0, 0.0)).xyz;
+    let offset = svg_functions::antialias_offset(
+        view.clip_from_world,
+        view.viewport.zw,
+        world_position,
+        world_normal,
+        length(vertex.aa_normal),
+        AA_WIDTH,
+    );
+    world_position = vec4<f32>(world_position.xyz + offset, world_position.w);
+    out.coverage = vertex.coverage;
+#endif
+    out.position = position_world_to_clip(world_position.xyz);
+#ifdef SVG_LAYERS
+    // all paths are in one plane, so later paths are moved towards the camera to stay on top of
+    // earlier ones, relative to the depth which is `1 / distance` with reverse-z
+    out.position.z *= 1.0 + vertex.layer * LAYER_DEPTH_BIAS;
+#endif
+    let tag = mesh_functions::get_tag(vertex.instance_index);
+    out.color = vertex.color * svg_functions::unpack_tint(tag);
+    return out;
+}
+
 @fragment
 fn fragment(
-    in: VertexOutput,
+    in: svg_functions::SvgVertexOutput,
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
     var out: FragmentOutput;
-    out.color = in.color;
+    out.color = svg_functions::fragment_color(in);
+#ifdef SVG_ALPHA_MASK
+    if out.color.a < 0.5 {
+        discard;
+    }
+    out.color.a = 1.0;
+#endif
     return out;
 }
@@ -0,0 +1,59 @@
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    mesh_view_bindings::view,
+}
+
+#ifdef MOTION_VECTOR_PREPASS
+#import bevy_pbr::prepass_bindings
+#endif
+
+#ifdef SVG_ALPHA_MASK
+// Cutoff of `AlphaMode::Mask`, the paths are either fully opaque or fully transparent.
+const ALPHA_CUTOFF: f32 = 0.5;
+#else
+// Cutoff of `AlphaMode::Blend`, like the standard material every visible path casts a shadow.
+const ALPHA_CUTOFF: f32 = 0.05;
+#endif
+
+// Discards the fragments of transparent paths, so they are cut out of depth and shadow maps.
+fn alpha_discard(in: VertexOutput) {
+#ifdef MAY_DISCARD
+#ifdef VERTEX_COLORS
+    if in.color.a < ALPHA_CUTOFF {
+        discard;
+    }
+#endif
+#endif
+}
+
+#ifdef PREPASS_FRAGMENT
+@fragment
+fn fragment(in: VertexOutput) -> FragmentOutput {
+    alpha_discard(in);
+
+    var out: FragmentOutput;
+
+#ifdef NORMAL_PREPASS
+    out.normal = vec4(in.world_normal * 0.5 + vec3(0.5), 1.0);
+#endif
+
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.frag_depth = in.unclipped_depth;
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    let clip_position_t = view.unjittered_clip_from_world * in.world_position;
+    let clip_position = clip_position_t.xy / clip_position_t.w;
+    let previous_clip_position_t = prepass_bindings::previous_view_uniforms.clip_from_world * in.previous_world_position;
+    let previous_clip_position = previous_clip_position_t.xy / previous_clip_position_t.w;
+    out.motion_vector = (clip_position - previous_clip_position) * vec2(0.5, -0.5);
+#endif
+
+    return out;
+}
+#else
+@fragment
+fn fragment(in: VertexOutput) {
+    alpha_discard(in);
+}
+#endif
@@ -0,0 +1,53 @@
+#define_import_path bevy_svg::svg_functions
+
+// Output of the vertex shaders of `svg_2d.wgsl` and `svg_3d.wgsl`, the input of every fragment
+// shader of an SVG material.
+struct SvgVertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+#ifdef SVG_ANTIALIAS
+    @location(1) coverage: f32,
+#endif
+};
+
+// The color of a fragment, the tinted vertex color faded out by the anti-aliasing fringe.
+fn fragment_color(in: SvgVertexOutput) -> vec4<f32> {
+    var color = in.color;
+#ifdef SVG_ANTIALIAS
+    color.a *= in.coverage;
+#endif
+    return color;
+}
+
+// Unpacks the tint of an instance from its mesh tag, see `SvgTint`.
+//
+// The tag is the inverted sRGB color, so the default tag `0` is opaque white.
+fn unpack_tint(tag: u32) -> vec4<f32> {
+    let srgba = unpack4x8unorm(~tag);
+    let linear = select(
+        pow((srgba.rgb + 0.055) / 1.055, vec3<f32>(2.4)),
+        srgba.rgb / 12.92,
+        srgba.rgb <= vec3<f32>(0.04045),
+    );
+    return vec4<f32>(linear, srgba.a);
+}
+
+// Offset of an anti-aliasing fringe vertex, which moves it `width` pixels along its normal.
+fn antialias_offset(
+    clip_from_world: mat4x4<f32>,
+    viewport_size: vec2<f32>,
+    world_position: vec4<f32>,
+    world_normal: vec3<f32>,
+    miter: f32,
+    width: f32,
+) -> vec3<f32> {
+    let normal_length = length(world_normal);
+    if normal_length == 0.0 {
+        return vec3<f32>(0.0);
+    }
+    let direction = world_normal / normal_length;
+    let start = clip_from_world * world_position;
+    let end = clip_from_world * vec4<f32>(world_position.xyz + direction, 1.0);
+    let pixels = length((end.xy / end.w - start.xy / start.w) * 0.5 * viewport_size);
+    return direction * miter * width / max(pixels, 1e-4);
+}
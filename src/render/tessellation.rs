@@ -1,39 +1,129 @@
-use bevy::log::{debug, error};
+use std::cell::RefCell;
+
+use bevy::{
+    log::{debug, error},
+    tasks::{ComputeTaskPool, TaskPool},
+};
 use lyon_tessellation::{BuffersBuilder, FillOptions, FillTessellator, StrokeTessellator};
 
 use crate::{
-    render::vertex_buffer::{BufferExt, VertexBuffers, VertexConstructor},
-    svg::{DrawType, Svg},
+    render::{
+        antialias,
+        vertex_buffer::{BufferExt, VertexBuffers, VertexConstructor},
+    },
+    svg::{DrawType, PathDescriptor, Svg},
 };
 
-pub fn generate_buffer(
-    svg: &Svg,
+/// Number of paths below which a document is tessellated on the calling thread.
+const PATHS_PER_TASK: usize = 64;
+
+/// The tolerance of fills, unless the tessellation is given one.
+const FILL_TOLERANCE: f32 = 0.001;
+
+thread_local! {
+    static TESSELLATORS: RefCell<(FillTessellator, StrokeTessellator)> =
+        RefCell::new((FillTessellator::new(), StrokeTessellator::new()));
+}
+
+pub fn generate_buffer(svg: &Svg) -> VertexBuffers {
+    debug!("Tessellating SVG: {}", svg.name);
+    let buffers = generate_buffer_parallel(&svg.paths, None, svg.antialias);
+    debug!("Tessellating SVG: {} ... Done", svg.name);
+
+    buffers
+}
+
+/// Tessellates the given paths in parallel on the [`ComputeTaskPool`], with one pair of
+/// tessellators per thread.
+///
+/// The paths are split into consecutive chunks whose buffers are merged in order, so the result
+/// is the same as tessellating all paths one after another.
+pub fn generate_buffer_parallel(
+    paths: &[PathDescriptor],
+    tolerance: Option<f32>,
+    antialias: bool,
+) -> VertexBuffers {
+    let tessellate = |paths: &[PathDescriptor], first_layer: usize| {
+        TESSELLATORS.with_borrow_mut(|(fill_tess, stroke_tess)| {
+            let mut buffer =
+                generate_buffer_for_paths(paths, fill_tess, stroke_tess, tolerance, antialias);
+            if first_layer > 0 {
+                offset_layers(&mut buffer, first_layer);
+            }
+            buffer
+        })
+    };
+    if paths.len() <= PATHS_PER_TASK {
+        return tessellate(paths, 0);
+    }
+
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    // a few chunks per thread, so threads which finish early can pick up more work
+    let chunk_size = paths
+        .len()
+        .div_ceil(pool.thread_num() * 4)
+        .max(PATHS_PER_TASK);
+    // the results of a scope are in the order in which the tasks were spawned
+    let chunks = pool.scope(|scope| {
+        for (idx, chunk) in paths.chunks(chunk_size).enumerate() {
+            scope.spawn(async move { tessellate(chunk, idx * chunk_size) });
+        }
+    });
+
+    let mut buffers = VertexBuffers::new();
+    buffers.extend(chunks);
+    buffers
+}
+
+/// Adds `first_layer` to the layer of every vertex, for a buffer whose paths start at that index
+/// of a document.
+pub(crate) fn offset_layers(buffer: &mut VertexBuffers, first_layer: usize) {
+    for vertex in &mut buffer.vertices {
+        vertex.layer += first_layer as f32;
+    }
+}
+
+/// Tessellates the given paths in order into a single buffer.
+///
+/// If a `tolerance` in SVG units is given, it replaces the tolerance of every path. With
+/// `antialias`, every path gets an anti-aliasing fringe along its outline. The vertices of each
+/// path are on the layer of its index.
+pub fn generate_buffer_for_paths<'a>(
+    paths: impl IntoIterator<Item = &'a PathDescriptor>,
     fill_tess: &mut FillTessellator,
     stroke_tess: &mut StrokeTessellator,
+    tolerance: Option<f32>,
+    antialias: bool,
 ) -> VertexBuffers {
-    debug!("Tessellating SVG: {}", svg.name);
-
     let mut buffers = VertexBuffers::new();
 
     let mut color = None;
-    for path in &svg.paths {
+    for (layer, path) in paths.into_iter().enumerate() {
         let mut buffer = VertexBuffers::new();
 
         if color.is_none() {
             color = Some(path.color);
         }
 
+        // the segments are in the coordinate system of the path, so the tolerance is scaled
+        let tolerance = tolerance.map(|tolerance| {
+            let (sx, sy) = path.abs_transform.get_scale();
+            tolerance / sx.max(sy).max(f32::EPSILON)
+        });
         let segments = path.segments.clone();
         match path.draw_type {
-            DrawType::Fill => {
+            DrawType::Fill(fill_rule) => {
+                let opts = FillOptions::tolerance(tolerance.unwrap_or(FILL_TOLERANCE))
+                    .with_fill_rule(fill_rule);
                 if let Err(e) = fill_tess.tessellate(
                     segments,
-                    &FillOptions::tolerance(0.001),
+                    &opts,
                     &mut BuffersBuilder::new(
                         &mut buffer,
                         VertexConstructor {
                             color: path.color,
                             transform: path.abs_transform,
+                            layer: layer as f32,
                         },
                     ),
                 ) {
@@ -41,6 +131,7 @@ pub fn generate_buffer(
                 }
             }
             DrawType::Stroke(opts) => {
+                let opts = tolerance.map_or(opts, |tolerance| opts.with_tolerance(tolerance));
                 if let Err(e) = stroke_tess.tessellate(
                     segments,
                     &opts,
@@ -49,6 +140,7 @@ pub fn generate_buffer(
                         VertexConstructor {
                             color: path.color,
                             transform: path.abs_transform,
+                            layer: layer as f32,
                         },
                     ),
                 ) {
@@ -56,9 +148,11 @@ pub fn generate_buffer(
                 }
             }
         }
+        if antialias {
+            antialias::add_fringe(&mut buffer);
+        }
         buffers.extend_one(buffer);
     }
-    debug!("Tessellating SVG: {} ... Done", svg.name);
 
     buffers
 }
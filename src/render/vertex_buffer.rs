@@ -1,7 +1,8 @@
 use bevy::{
     asset::RenderAssetUsages,
     color::{Color, ColorToComponents},
-    mesh::{Indices, Mesh, VertexAttributeValues},
+    math::Vec2,
+    mesh::{Indices, Mesh, MeshVertexAttribute, VertexAttributeValues, VertexFormat},
     render::render_resource::PrimitiveTopology,
 };
 use copyless::VecHelper;
@@ -10,16 +11,27 @@ use lyon_tessellation::{
     self, FillVertex, FillVertexConstructor, StrokeVertex, StrokeVertexConstructor,
 };
 
-use crate::Convert;
+use crate::{Convert, render::antialias};
 
 /// A vertex with all the necessary attributes to be inserted into a Bevy
 /// [`Mesh`](bevy::render::mesh::Mesh).
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vertex {
-    position: [f32; 3],
-    color: [f32; 4],
+    pub(crate) position: [f32; 3],
+    pub(crate) color: [f32; 4],
+    /// Direction in which an anti-aliasing fringe vertex is moved, zero for all other vertices.
+    pub(crate) aa_normal: [f32; 2],
+    /// Coverage of the vertex, `0.0` on the outside of an anti-aliasing fringe.
+    pub(crate) coverage: f32,
+    /// Index of the path the vertex belongs to, in paint order.
+    pub(crate) layer: f32,
 }
 
+/// Index of the path a vertex belongs to, in paint order, which keeps the paths of an
+/// [`Svg`](crate::svg::Svg) in order when they are drawn in 3D.
+pub const ATTRIBUTE_LAYER: MeshVertexAttribute =
+    MeshVertexAttribute::new("SvgLayer", 2_370_658_193, VertexFormat::Float32);
+
 /// The index type of a Bevy [`Mesh`](bevy::render::mesh::Mesh).
 pub type IndexType = u32;
 
@@ -36,16 +48,78 @@ fn flip_mesh_vertically(mesh: &mut Mesh) {
             position[1] = -position[1];
         }
     }
+    if let Some(VertexAttributeValues::Float32x2(normals)) =
+        mesh.attribute_mut(antialias::ATTRIBUTE_AA_NORMAL)
+    {
+        for normal in normals.iter_mut() {
+            normal[1] = -normal[1];
+        }
+    }
+}
+
+/// Adds normals facing the positive z-axis, UVs and tangents to a mesh of an
+/// [`Svg`](crate::svg::Svg), so it can be lit.
+///
+/// The UVs map the given size in SVG units to `0.0..1.0`, with `v` going down like the y-axis of
+/// the SVG.
+pub(crate) fn insert_surface_attributes(mesh: &mut Mesh, size: Vec2) {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    let size = size.max(Vec2::splat(f32::EPSILON));
+    let uvs = positions
+        .iter()
+        .map(|[x, y, _]| [x / size.x, -y / size.y])
+        .collect::<Vec<_>>();
+    let count = positions.len();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vec![[1.0, 0.0, 0.0, 1.0]; count]);
+}
+
+/// Moves every path of a mesh of an [`Svg`](crate::svg::Svg) `spacing` units along the positive
+/// z-axis from the path below it, see [`Svg::layer_spacing`](crate::svg::Svg::layer_spacing).
+///
+/// Tessellated meshes are flat, so nothing has to be done without spacing.
+pub(crate) fn apply_layer_spacing(mesh: &mut Mesh, spacing: f32) {
+    if spacing == 0.0 {
+        return;
+    }
+    let Some(VertexAttributeValues::Float32(layers)) = mesh.attribute(ATTRIBUTE_LAYER) else {
+        return;
+    };
+    let depths = layers
+        .iter()
+        .map(|layer| layer * spacing)
+        .collect::<Vec<_>>();
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        for (position, depth) in positions.iter_mut().zip(depths) {
+            position[2] = depth;
+        }
+    }
 }
 
 impl Convert<Mesh> for VertexBuffers {
     fn convert(self) -> Mesh {
+        let has_fringe = self.vertices.iter().any(|vert| vert.coverage < 1.0);
         let mut positions = Vec::with_capacity(self.vertices.len());
         let mut colors = Vec::with_capacity(self.vertices.len());
+        let mut layers = Vec::with_capacity(self.vertices.len());
+        let mut aa_normals = Vec::new();
+        let mut coverages = Vec::new();
 
         for vert in self.vertices {
             positions.alloc().init(vert.position);
             colors.alloc().init(vert.color);
+            layers.alloc().init(vert.layer);
+            if has_fringe {
+                aa_normals.alloc().init(vert.aa_normal);
+                coverages.alloc().init(vert.coverage);
+            }
         }
 
         let mut mesh = Mesh::new(
@@ -54,6 +128,11 @@ impl Convert<Mesh> for VertexBuffers {
         );
         mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
         mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        mesh.insert_attribute(ATTRIBUTE_LAYER, layers);
+        if has_fringe {
+            mesh.insert_attribute(antialias::ATTRIBUTE_AA_NORMAL, aa_normals);
+            mesh.insert_attribute(antialias::ATTRIBUTE_COVERAGE, coverages);
+        }
         mesh.insert_indices(Indices::U32(self.indices));
 
         // Bevy has a different y-axis origin, so we need to flip that axis
@@ -67,6 +146,7 @@ impl Convert<Mesh> for VertexBuffers {
 pub struct VertexConstructor {
     pub(crate) color: Color,
     pub(crate) transform: usvg::Transform,
+    pub(crate) layer: f32,
 }
 
 impl VertexConstructor {
@@ -79,6 +159,9 @@ impl VertexConstructor {
         Vertex {
             position: [pos.x, pos.y, 0.0],
             color: self.color.to_linear().to_f32_array(),
+            aa_normal: [0.0; 2],
+            coverage: 1.0,
+            layer: self.layer,
         }
     }
 }
@@ -99,7 +182,6 @@ impl StrokeVertexConstructor<Vertex> for VertexConstructor {
 
 pub trait BufferExt<A> {
     fn extend_one(&mut self, item: A);
-    #[allow(dead_code)]
     fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T);
 }
 
@@ -0,0 +1,39 @@
+use bevy::{
+    asset::{AsyncWriteExt, io::Writer, saver::AssetSaver, saver::SavedAsset},
+    log::debug,
+    reflect::TypePath,
+    tasks::ConditionalSendFuture,
+};
+
+use crate::{
+    loader::{SvgAssetLoader, SvgLoaderSettings},
+    svg::Svg,
+};
+
+/// Saves an [`Svg`] as SVG document, see [`Svg::to_svg_string`].
+#[derive(Default, TypePath)]
+pub struct SvgAssetSaver;
+
+impl AssetSaver for SvgAssetSaver {
+    type Asset = Svg;
+    type Settings = ();
+    type OutputLoader = SvgAssetLoader;
+    type Error = std::io::Error;
+
+    fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _settings: &(),
+    ) -> impl ConditionalSendFuture<Output = Result<SvgLoaderSettings, Self::Error>> {
+        Box::pin(async move {
+            debug!("Saving SVG: {} ...", asset.name);
+            writer.write_all(asset.to_svg_string().as_bytes()).await?;
+            debug!("Saving SVG: {} ... Done", asset.name);
+            Ok(SvgLoaderSettings {
+                antialias: asset.antialias,
+                layer_spacing: asset.layer_spacing,
+            })
+        })
+    }
+}
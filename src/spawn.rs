@@ -0,0 +1,126 @@
+//! Spawns entities from the elements of an [`Svg`], to use SVG files as level files.
+
+use bevy::{
+    asset::{AssetId, Assets},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        resource::Resource,
+        system::{Commands, Query, Res},
+    },
+    math::{Vec3, Vec3Swizzles},
+    transform::components::Transform,
+};
+
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
+use crate::{
+    element::{SvgElement, SvgSelector},
+    origin::Origin,
+    svg::Svg,
+};
+
+/// The element a spawn function is called for.
+pub struct SvgSpawnContext<'a> {
+    /// The entity which displays the [`Svg`].
+    pub svg_entity: Entity,
+    /// The [`Svg`] the element belongs to.
+    pub svg: &'a Svg,
+    /// The matched element.
+    pub element: &'a SvgElement,
+    /// The center of the element in world space, `None` if the element has no bounds.
+    ///
+    /// Computed from the [`Transform`] and [`Origin`] of the SVG entity, which should not be a
+    /// child of another entity.
+    pub world_position: Option<Vec3>,
+}
+
+type SpawnFn = Box<dyn Fn(&mut Commands, &SvgSpawnContext) + Send + Sync>;
+
+/// Maps [`SvgSelector`]s to functions which spawn entities for matching elements.
+///
+/// Once the [`Svg`] of an entity with [`Svg2d`] or [`Svg3d`] is loaded, every function is called
+/// for all elements its selector matches, in the order the functions were added. This happens
+/// once per entity and asset, reloading the asset does not spawn the elements again.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_svg::prelude::*;
+/// fn setup(mut spawners: ResMut<SvgSpawners>) {
+///     spawners.add("circle[data-kind=goblin]", |commands, ctx| {
+///         if let Some(position) = ctx.world_position {
+///             commands.spawn(Transform::from_translation(position));
+///         }
+///     });
+/// }
+/// ```
+#[derive(Default, Resource)]
+pub struct SvgSpawners {
+    spawners: Vec<(SvgSelector, SpawnFn)>,
+}
+
+impl SvgSpawners {
+    /// Adds a function which is called for every element matching the selector.
+    pub fn add(
+        &mut self,
+        selector: impl Into<SvgSelector>,
+        spawn: impl Fn(&mut Commands, &SvgSpawnContext) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.spawners.push((selector.into(), Box::new(spawn)));
+        self
+    }
+}
+
+/// Marks an entity whose elements were spawned, with the [`Svg`] they were spawned from.
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+pub struct SvgElementsSpawned(pub AssetId<Svg>);
+
+/// Calls the [`SvgSpawners`] for every entity whose [`Svg`] finished loading.
+pub fn spawn_svg_elements(
+    mut commands: Commands,
+    spawners: Res<SvgSpawners>,
+    svgs: Res<Assets<Svg>>,
+    query: Query<(
+        Entity,
+        Option<&Svg2d>,
+        Option<&Svg3d>,
+        &Transform,
+        &Origin,
+        Option<&SvgElementsSpawned>,
+    )>,
+) {
+    for (entity, svg_2d, svg_3d, transform, origin, spawned) in &query {
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        if spawned.is_some_and(|spawned| spawned.0 == handle.id()) {
+            continue;
+        }
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        commands
+            .entity(entity)
+            .insert(SvgElementsSpawned(handle.id()));
+
+        let origin_translation = origin.compute_translation(svg.size * transform.scale.xy());
+        for (selector, spawn) in &spawners.spawners {
+            for element in svg.elements.iter().filter(|e| selector.matches(e)) {
+                let world_position = element.position().map(|position| {
+                    transform.transform_point(position.extend(0.0)) + origin_translation
+                });
+                spawn(
+                    &mut commands,
+                    &SvgSpawnContext {
+                        svg_entity: entity,
+                        svg,
+                        element,
+                        world_position,
+                    },
+                );
+            }
+        }
+    }
+}
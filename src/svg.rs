@@ -1,26 +1,37 @@
 use bevy::{
     asset::{Asset, Handle},
-    color::Color,
+    color::{Alpha, Color},
     log::{debug, trace, warn},
     math::Vec2,
-    mesh::Mesh,
+    mesh::{Indices, Mesh, VertexAttributeValues},
     reflect::{Reflect, std_traits::ReflectDefault},
     render::render_resource::AsBindGroup,
 };
 use copyless::VecHelper;
 use lyon_path::PathEvent;
-use lyon_tessellation::{FillTessellator, StrokeTessellator, math::Point};
-use std::{collections::VecDeque, iter::Peekable, path::PathBuf, sync::Arc};
+use lyon_tessellation::math::Point;
+use std::{collections::VecDeque, iter::Peekable, ops::Range, path::PathBuf, sync::Arc};
 use svgtypes::ViewBox;
 use usvg::{
     PaintOrder,
     tiny_skia_path::{PathSegment, PathSegmentsIter},
 };
 
-use crate::{Convert, loader::FileSvgError, render::tessellation, util};
+use crate::{
+    Convert,
+    element::{self, SvgElement, SvgSelector},
+    loader::FileSvgError,
+    render::{
+        tessellation,
+        vertex_buffer::{self, BufferExt, VertexBuffers},
+    },
+    trim::{StrokeTrim, TrimFills},
+    util,
+};
 
 /// A loaded and deserialized SVG file.
 #[derive(AsBindGroup, Reflect, Debug, Clone, Asset)]
+#[bind_group_data(SvgTransparency)]
 #[reflect(Default, Debug)]
 pub struct Svg {
     /// The name of the file.
@@ -28,13 +39,107 @@ pub struct Svg {
     /// Size of the SVG.
     pub size: Vec2,
     #[reflect(ignore)]
-    /// ViewBox of the SVG.
+    /// The `viewBox` of the source document, or `0 0 width height` if it has none.
+    ///
+    /// The paths are already transformed from the view box to the [`size`](Svg::size) of the
+    /// [`Svg`], it is kept to write the document back with [`Svg::to_svg_string`].
     pub view_box: ViewBox,
     #[reflect(ignore)]
     /// All paths that make up the SVG.
     pub paths: Vec<PathDescriptor>,
+    #[reflect(ignore)]
+    /// All elements with an `id`, a `class` or `data-*` attributes.
+    pub elements: Vec<SvgElement>,
+    /// Whether the mesh has an anti-aliasing fringe, which smooths the edges without MSAA.
+    ///
+    /// The fringe is faded out over about one pixel on screen and needs alpha blending, so the
+    /// [`Svg`] is rendered as a transparent material. It only applies to meshes tessellated after
+    /// it was set, see also [`SvgLoaderSettings`](crate::prelude::SvgLoaderSettings).
+    pub antialias: bool,
+    /// Distance along the positive z-axis between a path and the path below it, for an exploded
+    /// view of the layers of an [`Svg3d`](crate::prelude::Svg3d).
+    ///
+    /// With the default of `0.0`, all paths are in one plane and the SVG material keeps them in
+    /// paint order with a depth bias. Like [`antialias`](Svg::antialias), it only applies to
+    /// meshes tessellated after it was set.
+    pub layer_spacing: f32,
     /// The fully tessellated paths as [`Mesh`].
     pub mesh: Handle<Mesh>,
+    #[reflect(ignore)]
+    /// The outermost groups with an `id`, e.g. the frames of an animation or Inkscape layers, in
+    /// document order. Empty if there are fewer than two of them.
+    pub frames: Vec<SvgFrame>,
+}
+
+/// A group of an [`Svg`] with its own mesh, see
+/// [`SvgFrameAnimation`](crate::prelude::SvgFrameAnimation).
+///
+/// The mesh is a copy of the part of the [`Svg::mesh`] which belongs to the group, so loaded
+/// frames are also available as the labeled asset `frame/<id>`, e.g. `walk.svg#frame/frame_1`.
+#[derive(Debug, Clone)]
+pub struct SvgFrame {
+    /// The `id` of the group.
+    pub id: String,
+    /// The tessellated paths of the group.
+    pub mesh: Handle<Mesh>,
+    /// The vertices of the group in the mesh of the [`Svg`].
+    pub(crate) vertices: Range<u32>,
+    /// The indices of the group in the mesh of the [`Svg`].
+    pub(crate) indices: Range<u32>,
+}
+
+impl SvgFrame {
+    /// Copies the vertices and indices of the frame out of the mesh of its [`Svg`].
+    pub(crate) fn extract_mesh(&self, mesh: &Mesh) -> Mesh {
+        let vertices = self.vertices.start as usize..self.vertices.end as usize;
+        let mut frame_mesh = Mesh::new(mesh.primitive_topology(), mesh.asset_usage);
+        for (attribute, values) in mesh.attributes() {
+            let values = match values {
+                VertexAttributeValues::Float32(values) => {
+                    VertexAttributeValues::Float32(values[vertices.clone()].to_vec())
+                }
+                VertexAttributeValues::Float32x2(values) => {
+                    VertexAttributeValues::Float32x2(values[vertices.clone()].to_vec())
+                }
+                VertexAttributeValues::Float32x3(values) => {
+                    VertexAttributeValues::Float32x3(values[vertices.clone()].to_vec())
+                }
+                VertexAttributeValues::Float32x4(values) => {
+                    VertexAttributeValues::Float32x4(values[vertices.clone()].to_vec())
+                }
+                _ => continue,
+            };
+            frame_mesh.insert_attribute(*attribute, values);
+        }
+        let indices = mesh
+            .indices()
+            .into_iter()
+            .flat_map(|indices| indices.iter())
+            .skip(self.indices.start as usize)
+            .take(self.indices.len())
+            .map(|idx| idx as u32 - self.vertices.start)
+            .collect();
+        frame_mesh.insert_indices(Indices::U32(indices));
+        frame_mesh
+    }
+}
+
+/// How the colors of an [`Svg`] cover what is behind it, which decides how it is rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SvgTransparency {
+    /// All paths are fully opaque.
+    Opaque,
+    /// All paths are either fully opaque or fully transparent, the transparent ones are cut out.
+    Mask,
+    /// Some paths are semi-transparent or the mesh has an anti-aliasing fringe, so the [`Svg`] is
+    /// alpha blended.
+    Blend,
+}
+
+impl From<&Svg> for SvgTransparency {
+    fn from(svg: &Svg) -> Self {
+        svg.transparency()
+    }
 }
 
 impl Default for Svg {
@@ -49,7 +154,11 @@ impl Default for Svg {
                 h: 0.,
             },
             paths: Default::default(),
+            elements: Default::default(),
+            antialias: false,
+            layer_spacing: 0.0,
             mesh: Default::default(),
+            frames: Vec::new(),
         }
     }
 }
@@ -69,41 +178,162 @@ impl Svg {
 
         let fontdb = Arc::new(fontdb);
 
-        let svg_tree = usvg::Tree::from_data(
-            bytes,
-            &usvg::Options {
-                fontdb,
-                ..Default::default()
-            },
-        )
-        .map_err(|err| FileSvgError {
+        let options = usvg::Options {
+            fontdb,
+            ..Default::default()
+        };
+        let svg_tree = usvg::Tree::from_data(bytes, &options).map_err(|err| FileSvgError {
             error: err.into(),
             path: format!("{}", path.into().display()),
         })?;
 
-        Ok(Svg::from_tree(svg_tree))
+        let source = element::parse_source(bytes, &svg_tree, &options);
+        let mut svg = Svg::from_tree(svg_tree);
+        svg.elements = source.elements;
+        if let Some(view_box) = source.view_box {
+            svg.view_box = view_box;
+        }
+        Ok(svg)
+    }
+
+    /// Returns how the colors of the paths cover what is behind the [`Svg`].
+    pub fn transparency(&self) -> SvgTransparency {
+        if self.antialias {
+            return SvgTransparency::Blend;
+        }
+        let mut transparency = SvgTransparency::Opaque;
+        for path in &self.paths {
+            match path.color.alpha() {
+                1.0 => (),
+                0.0 => transparency = SvgTransparency::Mask,
+                _ => return SvgTransparency::Blend,
+            }
+        }
+        transparency
+    }
+
+    /// Returns the element with the given `id`.
+    pub fn element(&self, id: &str) -> Option<&SvgElement> {
+        self.elements.iter().find(|element| element.id == id)
+    }
+
+    /// Returns all elements which match the selector, in document order.
+    pub fn select(&self, selector: impl Into<SvgSelector>) -> impl Iterator<Item = &SvgElement> {
+        let selector = selector.into();
+        self.elements
+            .iter()
+            .filter(move |element| selector.matches(element))
     }
 
     /// Creates a bevy mesh from the SVG data.
     pub fn tessellate(&self) -> Mesh {
-        let buffer = tessellation::generate_buffer(
-            self,
-            &mut FillTessellator::new(),
-            &mut StrokeTessellator::new(),
-        );
-        buffer.convert()
+        let buffer = tessellation::generate_buffer(self);
+        self.to_mesh(buffer)
+    }
+
+    /// Converts tessellated paths of the SVG into a bevy mesh, with normals, UVs and tangents.
+    pub(crate) fn to_mesh(&self, buffer: VertexBuffers) -> Mesh {
+        let mut mesh = buffer.convert();
+        vertex_buffer::insert_surface_attributes(&mut mesh, self.size);
+        vertex_buffer::apply_layer_spacing(&mut mesh, self.layer_spacing);
+        mesh
+    }
+
+    /// Creates a bevy mesh from the SVG data and sets up the [`frames`](Svg::frames), whose
+    /// meshes still have to be created with [`SvgFrame::extract_mesh`].
+    ///
+    /// Every outermost group with an `id` is tessellated on its own and merged into the mesh, so
+    /// the result is the same as [`tessellate`](Svg::tessellate).
+    pub(crate) fn tessellate_frames(&mut self) -> Mesh {
+        // consecutive paths which belong to the same outermost group
+        let mut runs: Vec<(Option<&str>, Range<usize>)> = Vec::new();
+        for (idx, path) in self.paths.iter().enumerate() {
+            let group = path.ancestor_ids.first().map(String::as_str);
+            match runs.last_mut() {
+                Some((last, range)) if *last == group => range.end = idx + 1,
+                _ => runs.push((group, idx..idx + 1)),
+            }
+        }
+        if runs.iter().filter(|(group, _)| group.is_some()).count() < 2 {
+            self.frames.clear();
+            return self.tessellate();
+        }
+
+        debug!("Tessellating SVG: {}", self.name);
+        let mut buffer = VertexBuffers::new();
+        let mut frames: Vec<SvgFrame> = Vec::new();
+        for (group, range) in runs {
+            let mut run = tessellation::generate_buffer_parallel(
+                &self.paths[range.clone()],
+                None,
+                self.antialias,
+            );
+            tessellation::offset_layers(&mut run, range.start);
+            let (vertex_start, index_start) =
+                (buffer.vertices.len() as u32, buffer.indices.len() as u32);
+            buffer.extend_one(run);
+            let Some(id) = group else {
+                continue;
+            };
+            if frames.iter().any(|frame| frame.id == id) {
+                warn!(
+                    "Svg `{}`: more than one group with the id `{id}`.",
+                    self.name
+                );
+                continue;
+            }
+            frames.push(SvgFrame {
+                id: id.to_string(),
+                mesh: Handle::default(),
+                vertices: vertex_start..buffer.vertices.len() as u32,
+                indices: index_start..buffer.indices.len() as u32,
+            });
+        }
+        debug!("Tessellating SVG: {} ... Done", self.name);
+        self.frames = frames;
+        self.to_mesh(buffer)
+    }
+
+    /// Creates a bevy mesh from the SVG data, with the given tolerance in SVG units instead of the
+    /// tolerance of each path.
+    pub fn tessellate_with_tolerance(&self, tolerance: f32) -> Mesh {
+        let buffer =
+            tessellation::generate_buffer_parallel(&self.paths, Some(tolerance), self.antialias);
+        self.to_mesh(buffer)
+    }
+
+    /// Returns the geometry of all elements, a path which is filled and stroked is only
+    /// returned once.
+    pub fn unique_paths(&self) -> impl Iterator<Item = &PathDescriptor> {
+        self.paths.iter().enumerate().filter_map(|(idx, path)| {
+            let is_duplicate = idx
+                .checked_sub(1)
+                .is_some_and(|prev| self.paths[prev].is_same_element(path));
+            (!is_duplicate).then_some(path)
+        })
+    }
+
+    /// Returns the geometry of the element with the given `id`, or of all elements in a group
+    /// with that `id`. A path which is filled and stroked is only returned once.
+    pub fn element_paths<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a PathDescriptor> {
+        self.unique_paths().filter(move |path| path.has_id(id))
+    }
+
+    /// Creates a bevy mesh from the SVG data, with all strokes trimmed by the given [`StrokeTrim`].
+    pub fn tessellate_trimmed(&self, trim: &StrokeTrim) -> Mesh {
+        self.to_mesh(TrimFills::new(self).buffer(self, trim))
     }
 
     pub(crate) fn from_tree(tree: usvg::Tree) -> Svg {
-        let view_box = tree.root().layer_bounding_box();
         let size = tree.size();
         let mut descriptors = Vec::new();
 
-        #[derive(Copy, Clone)]
+        #[derive(Clone)]
         struct NodeContext<'a> {
             node: &'a usvg::Node,
             transform: usvg::Transform,
             is_text: bool,
+            ancestor_ids: Vec<String>,
         }
 
         let mut node_stack = tree
@@ -117,6 +347,7 @@ impl Svg {
                 node,
                 transform: node.abs_transform(),
                 is_text: false,
+                ancestor_ids: Vec::new(),
             })
             .collect::<VecDeque<_>>();
 
@@ -124,6 +355,7 @@ impl Svg {
             node,
             transform,
             is_text,
+            ancestor_ids,
         }) = node_stack.pop_front()
         {
             trace!("---");
@@ -132,12 +364,14 @@ impl Svg {
                 usvg::Node::Group(group) => {
                     let transform = transform.pre_concat(group.transform());
                     trace!("group: {:?}", group.id());
+                    let ancestor_ids = with_id(&ancestor_ids, group.id());
                     if !group.should_isolate() {
                         for node in group.children() {
                             node_stack.push_front(NodeContext {
                                 node,
                                 transform,
                                 is_text: false,
+                                ancestor_ids: ancestor_ids.clone(),
                             });
                         }
                     } else {
@@ -151,11 +385,13 @@ impl Svg {
                     // all transforms from here on down are identity
                     // https://github.com/RazrFalcon/resvg/blob/1a6922d5bfcee9e69e04dc47cb0b586f1ca64a1c/crates/usvg/src/text/flatten.rs#L83-L83
                     let group = text.flattened();
+                    let ancestor_ids = with_id(&ancestor_ids, text.id());
                     for node in group.children() {
                         node_stack.push_front(NodeContext {
                             node,
                             transform,
                             is_text: true,
+                            ancestor_ids: ancestor_ids.clone(),
                         });
                     }
                 }
@@ -176,6 +412,7 @@ impl Svg {
                         path,
                         transform,
                         is_stroke: false,
+                        ancestor_ids: &ancestor_ids,
                     };
 
                     // inverted because we are reversing the list at the end
@@ -202,13 +439,17 @@ impl Svg {
             name: Default::default(),
             size: Vec2::new(size.width(), size.height()),
             view_box: ViewBox {
-                x: view_box.x() as f64,
-                y: view_box.y() as f64,
-                w: view_box.width() as f64,
-                h: view_box.height() as f64,
+                x: 0.0,
+                y: 0.0,
+                w: size.width() as f64,
+                h: size.height() as f64,
             },
             paths: descriptors,
+            elements: Vec::new(),
+            antialias: false,
+            layer_spacing: 0.0,
             mesh: Default::default(),
+            frames: Vec::new(),
         }
     }
 
@@ -243,8 +484,10 @@ impl Svg {
             abs_transform: path_with_transform.transform,
             segments: path_with_transform.convert().collect(),
             color,
-            draw_type: DrawType::Fill,
+            draw_type: DrawType::Fill(fill.convert()),
             is_stroke: false,
+            id: path.id().to_string(),
+            ancestor_ids: path_with_transform.ancestor_ids.to_vec(),
         });
     }
 
@@ -265,6 +508,8 @@ impl Svg {
             color,
             draw_type,
             is_stroke: true,
+            id: path.id().to_string(),
+            ancestor_ids: path_with_transform.ancestor_ids.to_vec(),
         });
     }
 }
@@ -276,11 +521,74 @@ pub struct PathDescriptor {
     pub draw_type: DrawType,
     pub abs_transform: usvg::Transform,
     pub is_stroke: bool,
+    /// The `id` of the SVG element this path was created from, may be empty.
+    pub id: String,
+    /// The `id`s of all enclosing groups, from the outermost to the innermost.
+    pub ancestor_ids: Vec<String>,
+}
+
+impl PathDescriptor {
+    /// Returns `true` if this path is the element with the given `id` or is part of a group
+    /// with that `id`.
+    pub fn has_id(&self, id: &str) -> bool {
+        self.id == id || self.ancestor_ids.iter().any(|ancestor| ancestor == id)
+    }
+
+    /// Returns the segments with [`abs_transform`](Self::abs_transform) applied, which puts
+    /// them into the coordinate system of the SVG.
+    pub fn transformed_segments(&self) -> Vec<PathEvent> {
+        let transform = self.abs_transform;
+        let map = |point: Point| {
+            let mut point = usvg::tiny_skia_path::Point::from_xy(point.x, point.y);
+            transform.map_point(&mut point);
+            point.convert()
+        };
+        self.segments
+            .iter()
+            .map(|event| match *event {
+                PathEvent::Begin { at } => PathEvent::Begin { at: map(at) },
+                PathEvent::Line { from, to } => PathEvent::Line {
+                    from: map(from),
+                    to: map(to),
+                },
+                PathEvent::Quadratic { from, ctrl, to } => PathEvent::Quadratic {
+                    from: map(from),
+                    ctrl: map(ctrl),
+                    to: map(to),
+                },
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => PathEvent::Cubic {
+                    from: map(from),
+                    ctrl1: map(ctrl1),
+                    ctrl2: map(ctrl2),
+                    to: map(to),
+                },
+                PathEvent::End { last, first, close } => PathEvent::End {
+                    last: map(last),
+                    first: map(first),
+                    close,
+                },
+            })
+            .collect()
+    }
+
+    /// Returns `true` if both descriptors were created from the same SVG element, which is the
+    /// case for the fill and the stroke of a path.
+    pub(crate) fn is_same_element(&self, other: &PathDescriptor) -> bool {
+        self.id == other.id
+            && self.abs_transform == other.abs_transform
+            && self.segments == other.segments
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum DrawType {
-    Fill,
+    /// A fill with the fill rule of the path.
+    Fill(lyon_tessellation::FillRule),
     Stroke(lyon_tessellation::StrokeOptions),
 }
 
@@ -289,6 +597,16 @@ struct PathWithTransform<'a> {
     path: &'a usvg::Path,
     is_stroke: bool,
     transform: usvg::Transform,
+    ancestor_ids: &'a [String],
+}
+
+/// Appends `id` to the given ancestor ids, if it is not empty.
+fn with_id(ancestor_ids: &[String], id: &str) -> Vec<String> {
+    let mut ids = ancestor_ids.to_vec();
+    if !id.is_empty() {
+        ids.push(id.to_string());
+    }
+    ids
 }
 
 // Taken from https://github.com/nical/lyon/blob/74e6b137fea70d71d3b537babae22c6652f8843e/examples/wgpu_svg/src/main.rs
@@ -339,6 +657,7 @@ impl<'iter> Iterator for PathConvIter<'iter> {
                 } else if self.iter.peek().is_some() {
                     // only bother sending begin if we have more items to process
                     self.first = point.convert();
+                    self.prev = self.first;
                     self.needs_end = true;
                     Some(PathEvent::Begin { at: self.first })
                 } else {
@@ -422,13 +741,29 @@ impl Convert<Color> for &usvg::Stop {
 
 impl<'iter> Convert<PathConvIter<'iter>> for PathWithTransform<'iter> {
     fn convert(self) -> PathConvIter<'iter> {
-        return PathConvIter {
-            iter: self.path.data().segments().peekable(),
+        self.path.data().convert()
+    }
+}
+
+impl<'iter> Convert<PathConvIter<'iter>> for &'iter usvg::tiny_skia_path::Path {
+    fn convert(self) -> PathConvIter<'iter> {
+        PathConvIter {
+            iter: self.segments().peekable(),
             first: Point::new(0.0, 0.0),
             prev: Point::new(0.0, 0.0),
             deferred: None,
             needs_end: false,
-        };
+        }
+    }
+}
+
+impl Convert<lyon_tessellation::FillRule> for &usvg::Fill {
+    #[inline]
+    fn convert(self) -> lyon_tessellation::FillRule {
+        match self.rule() {
+            usvg::FillRule::NonZero => lyon_tessellation::FillRule::NonZero,
+            usvg::FillRule::EvenOdd => lyon_tessellation::FillRule::EvenOdd,
+        }
     }
 }
 
@@ -0,0 +1,62 @@
+//! Per-entity colors for [`Svg`](crate::prelude::Svg)s, without giving up instanced drawing.
+
+use bevy::{
+    color::{Color, ColorToPacked, Srgba},
+    ecs::{
+        component::Component,
+        lifecycle::RemovedComponents,
+        query::{Changed, Without},
+        system::Query,
+    },
+    mesh::MeshTag,
+};
+
+/// Multiplies all colors of an [`Svg2d`](crate::prelude::Svg2d) or
+/// [`Svg3d`](crate::prelude::Svg3d) entity.
+///
+/// Entities which show the same [`Svg`] share its mesh and material, so Bevy draws all of them
+/// with a single instanced draw call, with the transform of every entity as per-instance data.
+/// The tint is stored in the [`MeshTag`] of the entity, which is per-instance data as well, so
+/// tinted entities are still drawn together. The [`MeshTag`] should not be used for anything
+/// else on these entities.
+///
+/// Entities whose [`Svg`] is re-tessellated for them, e.g. with a
+/// [`StrokeTrim`](crate::prelude::StrokeTrim) or [`SvgLod`](crate::prelude::SvgLod), get their
+/// own mesh and need a draw call each.
+///
+/// The alpha of the tint only has an effect if the [`Svg`] is rendered with alpha blending, e.g.
+/// with [`Svg::antialias`](crate::prelude::Svg::antialias).
+///
+/// [`Svg`]: crate::prelude::Svg
+#[derive(Clone, Component, Copy, Debug, PartialEq)]
+#[require(MeshTag)]
+pub struct SvgTint(pub Color);
+
+impl Default for SvgTint {
+    fn default() -> Self {
+        Self(Color::WHITE)
+    }
+}
+
+impl SvgTint {
+    /// The value of the [`MeshTag`], the inverted sRGB color, so that the tag `0` is white.
+    fn mesh_tag(&self) -> u32 {
+        !u32::from_le_bytes(Srgba::from(self.0).to_u8_array())
+    }
+}
+
+/// Writes changed [`SvgTint`]s to the [`MeshTag`] read by the shaders.
+pub fn apply_svg_tint(
+    mut tinted: Query<(&SvgTint, &mut MeshTag), Changed<SvgTint>>,
+    mut untinted: Query<&mut MeshTag, Without<SvgTint>>,
+    mut removed: RemovedComponents<SvgTint>,
+) {
+    for (tint, mut tag) in &mut tinted {
+        tag.0 = tint.mesh_tag();
+    }
+    for entity in removed.read() {
+        if let Ok(mut tag) = untinted.get_mut(entity) {
+            tag.0 = 0;
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Trimming of stroked paths, used to animate a path "drawing itself".
+
+#[cfg(feature = "2d")]
+use bevy::mesh::Mesh2d;
+#[cfg(feature = "3d")]
+use bevy::mesh::Mesh3d;
+#[cfg(any(feature = "2d", feature = "3d"))]
+use bevy::{
+    asset::{AssetEvent, AssetId, Assets},
+    ecs::{
+        change_detection::{DetectChanges, Ref},
+        lifecycle::RemovedComponents,
+        message::MessageReader,
+        query::Without,
+        resource::Resource,
+        system::{Query, Res, ResMut},
+    },
+    mesh::Mesh,
+};
+use bevy::{ecs::component::Component, platform::collections::HashMap};
+use std::ops::Range;
+
+#[cfg(any(feature = "2d", feature = "3d"))]
+use crate::frames::SvgFrameAnimation;
+#[cfg(feature = "2d")]
+use crate::render::Svg2d;
+#[cfg(feature = "3d")]
+use crate::render::Svg3d;
+use crate::{
+    geometry::PathMeasure,
+    render::{
+        tessellation,
+        vertex_buffer::{BufferExt, VertexBuffers},
+    },
+    svg::{PathDescriptor, Svg},
+};
+
+/// The visible part of a stroke, given as fractions of its arc length.
+///
+/// `start` and `end` are in the range `0.0..=1.0` and get shifted by `offset`. The visible part
+/// wraps around the end of the path, so animating `offset` moves a dash along the stroke.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrimRange {
+    /// Start of the visible part.
+    pub start: f32,
+    /// End of the visible part.
+    pub end: f32,
+    /// Offset which is added to `start` and `end`.
+    pub offset: f32,
+}
+
+impl Default for TrimRange {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl TrimRange {
+    /// The whole stroke is visible.
+    pub const FULL: Self = Self::new(0.0, 1.0);
+
+    /// Creates a new range without an offset.
+    pub const fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            offset: 0.0,
+        }
+    }
+
+    /// Sets the offset of the range.
+    pub const fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns `true` if the whole stroke is visible.
+    pub fn is_full(&self) -> bool {
+        self.end - self.start >= 1.0
+    }
+
+    /// Resolves the offset and returns up to two visible intervals in `0.0..=1.0`.
+    fn intervals(&self) -> Vec<(f32, f32)> {
+        if self.is_full() {
+            return vec![(0.0, 1.0)];
+        }
+        if self.end <= self.start {
+            return Vec::new();
+        }
+        let start = self.start + self.offset;
+        let shift = start.floor();
+        let (start, end) = (start - shift, self.end + self.offset - shift);
+        if end <= 1.0 {
+            vec![(start, end)]
+        } else {
+            vec![(0.0, end - 1.0), (start, 1.0)]
+        }
+    }
+
+    /// Returns the trimmed segments of a path.
+    pub(crate) fn trim(&self, path: &PathDescriptor) -> Vec<lyon_path::PathEvent> {
+        if self.is_full() {
+            return path.segments.clone();
+        }
+        let measure = PathMeasure::new(&path.segments);
+        let length = measure.length();
+        self.intervals()
+            .into_iter()
+            .flat_map(|(start, end)| measure.sub_path_events(start * length, end * length))
+            .collect()
+    }
+}
+
+/// Trims the strokes of an [`Svg`] entity, so only the visible part of each stroke gets
+/// tessellated.
+///
+/// The `range` applies to all strokes, `elements` overrides it for strokes which belong to
+/// the element with the given `id` or a group with that `id`. Fills are not affected.
+///
+/// An entity with this component gets its own mesh. The fills are tessellated once per [`Svg`],
+/// only the strokes are tessellated again every time the component changes. The component is
+/// ignored on entities with an [`SvgFrameAnimation`](crate::prelude::SvgFrameAnimation).
+#[derive(Clone, Component, Debug, Default, PartialEq)]
+pub struct StrokeTrim {
+    /// Visible part of all strokes.
+    pub range: TrimRange,
+    /// Visible part of the strokes of single elements, keyed by element `id`.
+    pub elements: HashMap<String, TrimRange>,
+}
+
+impl StrokeTrim {
+    /// Creates a trim which applies the same range to all strokes.
+    pub fn new(range: TrimRange) -> Self {
+        Self {
+            range,
+            elements: HashMap::default(),
+        }
+    }
+
+    /// Sets the range for the strokes of the element with the given `id`.
+    pub fn with_element(mut self, id: impl Into<String>, range: TrimRange) -> Self {
+        self.elements.insert(id.into(), range);
+        self
+    }
+
+    /// Returns the range that applies to the given path.
+    pub fn range_for(&self, path: &PathDescriptor) -> TrimRange {
+        std::iter::once(&path.id)
+            .chain(path.ancestor_ids.iter().rev())
+            .find_map(|id| self.elements.get(id))
+            .copied()
+            .unwrap_or(self.range)
+    }
+
+    /// Returns the paths of the [`Svg`] with all strokes trimmed.
+    pub fn apply(&self, paths: &[PathDescriptor]) -> Vec<PathDescriptor> {
+        paths
+            .iter()
+            .map(|path| {
+                if !path.is_stroke {
+                    return path.clone();
+                }
+                PathDescriptor {
+                    segments: self.range_for(path).trim(path),
+                    ..path.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// The fills of an [`Svg`] tessellated once, so only the strokes are tessellated again when a
+/// [`StrokeTrim`] changes.
+#[derive(Clone, Debug)]
+pub(crate) struct TrimFills {
+    /// Runs of consecutive paths which are all fills, with their buffer, or all strokes.
+    runs: Vec<(Range<usize>, Option<VertexBuffers>)>,
+}
+
+impl TrimFills {
+    /// Tessellates the fills of the [`Svg`].
+    pub(crate) fn new(svg: &Svg) -> Self {
+        let mut runs: Vec<(Range<usize>, Option<VertexBuffers>)> = Vec::new();
+        for (idx, path) in svg.paths.iter().enumerate() {
+            match runs.last_mut() {
+                Some((range, fills)) if fills.is_none() == path.is_stroke => range.end = idx + 1,
+                _ => runs.push((idx..idx + 1, (!path.is_stroke).then(VertexBuffers::new))),
+            }
+        }
+        for (range, fills) in &mut runs {
+            if let Some(fills) = fills {
+                *fills = tessellate(&svg.paths[range.clone()], range.start, svg.antialias);
+            }
+        }
+        Self { runs }
+    }
+
+    /// Tessellates the trimmed strokes of the [`Svg`] and merges them with the fills in paint
+    /// order, the result is the same as tessellating all trimmed paths.
+    pub(crate) fn buffer(&self, svg: &Svg, trim: &StrokeTrim) -> VertexBuffers {
+        let mut buffer = VertexBuffers::new();
+        for (range, fills) in &self.runs {
+            match fills {
+                Some(fills) => {
+                    let offset = buffer.vertices.len() as u32;
+                    buffer.vertices.extend_from_slice(&fills.vertices);
+                    buffer
+                        .indices
+                        .extend(fills.indices.iter().map(|idx| idx + offset));
+                }
+                None => {
+                    let strokes = trim.apply(&svg.paths[range.clone()]);
+                    buffer.extend_one(tessellate(&strokes, range.start, svg.antialias));
+                }
+            }
+        }
+        buffer
+    }
+}
+
+/// Tessellates paths which start at the index `first_layer` of a document.
+fn tessellate(paths: &[PathDescriptor], first_layer: usize, antialias: bool) -> VertexBuffers {
+    let mut buffer = tessellation::generate_buffer_parallel(paths, None, antialias);
+    tessellation::offset_layers(&mut buffer, first_layer);
+    buffer
+}
+
+/// The [`TrimFills`] of every [`Svg`] used by an entity with a [`StrokeTrim`].
+#[cfg(any(feature = "2d", feature = "3d"))]
+#[derive(Default, Resource)]
+pub(crate) struct SvgTrimFills(HashMap<AssetId<Svg>, TrimFills>);
+
+/// Re-tessellates the strokes of every [`Svg`] entity with a changed [`StrokeTrim`], whose [`Svg`]
+/// changed, or whose mesh has been reset to the shared mesh of the [`Svg`].
+#[cfg(any(feature = "2d", feature = "3d"))]
+pub fn apply_stroke_trim(
+    mut svg_events: MessageReader<AssetEvent<Svg>>,
+    svgs: Res<Assets<Svg>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut fills: ResMut<SvgTrimFills>,
+    mut query: Query<
+        (
+            Ref<StrokeTrim>,
+            Option<&Svg2d>,
+            Option<&Svg3d>,
+            Option<&mut Mesh2d>,
+            Option<&mut Mesh3d>,
+        ),
+        Without<SvgFrameAnimation>,
+    >,
+) {
+    let mut modified = Vec::new();
+    for event in svg_events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            fills.0.remove(id);
+            modified.push(*id);
+        }
+    }
+
+    for (trim, svg_2d, svg_3d, mesh_2d, mesh_3d) in &mut query {
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        let current = mesh_2d
+            .as_ref()
+            .map(|mesh| mesh.0.clone())
+            .or_else(|| mesh_3d.as_ref().map(|mesh| mesh.0.clone()));
+        let Some(current) = current else {
+            continue;
+        };
+        let is_shared = current == svg.mesh;
+        if !trim.is_changed() && !is_shared && !modified.contains(&handle.id()) {
+            continue;
+        }
+
+        let buffer = fills
+            .0
+            .entry(handle.id())
+            .or_insert_with(|| TrimFills::new(svg))
+            .buffer(svg, &trim);
+        let mesh = svg.to_mesh(buffer);
+        if is_shared {
+            let handle = meshes.add(mesh);
+            #[cfg(feature = "2d")]
+            if let Some(mut mesh) = mesh_2d {
+                mesh.0 = handle.clone();
+            }
+            #[cfg(feature = "3d")]
+            if let Some(mut mesh) = mesh_3d {
+                mesh.0 = handle;
+            }
+        } else if meshes.insert(&current, mesh).is_err() {
+            bevy::log::warn!("Svg `{}`: failed to update trimmed mesh.", svg.name);
+        }
+    }
+}
+
+/// Resets the mesh of an [`Svg`] entity to the shared mesh, once its [`StrokeTrim`] is removed.
+#[cfg(any(feature = "2d", feature = "3d"))]
+pub fn reset_stroke_trim(
+    mut removed: RemovedComponents<StrokeTrim>,
+    svgs: Res<Assets<Svg>>,
+    mut query: Query<(
+        Option<&Svg2d>,
+        Option<&Svg3d>,
+        Option<&mut Mesh2d>,
+        Option<&mut Mesh3d>,
+    )>,
+) {
+    for entity in removed.read() {
+        let Ok((svg_2d, svg_3d, mesh_2d, mesh_3d)) = query.get_mut(entity) else {
+            continue;
+        };
+        let Some(handle) = svg_2d.map_or_else(|| svg_3d.map(|x| &x.0), |x| Some(&x.0)) else {
+            continue;
+        };
+        let Some(svg) = svgs.get(handle) else {
+            continue;
+        };
+        #[cfg(feature = "2d")]
+        if let Some(mut mesh) = mesh_2d {
+            mesh.0 = svg.mesh.clone();
+        }
+        #[cfg(feature = "3d")]
+        if let Some(mut mesh) = mesh_3d {
+            mesh.0 = svg.mesh.clone();
+        }
+    }
+}
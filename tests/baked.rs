@@ -0,0 +1,158 @@
+//! Round trips of [`Svg`]s through the baked format of [`BakedSvgSaver`].
+
+use bevy::{
+    asset::io::memory::Dir,
+    mesh::{Indices, VertexAttributeValues},
+    prelude::*,
+};
+use bevy_svg::prelude::*;
+
+mod common;
+
+use common::{assert_same_paths, asset_app, insert_file, load_asset};
+
+fn attribute(mesh: &Mesh, attribute: bevy::mesh::MeshVertexAttribute) -> &VertexAttributeValues {
+    mesh.attribute(attribute).expect("mesh attribute")
+}
+
+fn indices(mesh: &Mesh) -> Vec<usize> {
+    mesh.indices()
+        .map(Indices::iter)
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn assert_same_mesh(a: &Mesh, b: &Mesh) {
+    assert_eq!(
+        attribute(a, Mesh::ATTRIBUTE_POSITION),
+        attribute(b, Mesh::ATTRIBUTE_POSITION)
+    );
+    assert_eq!(
+        attribute(a, Mesh::ATTRIBUTE_COLOR),
+        attribute(b, Mesh::ATTRIBUTE_COLOR)
+    );
+    assert_eq!(indices(a), indices(b));
+}
+
+/// Loads an SVG file with the SVG loader, bakes it and loads it again with the baked loader.
+fn bake(file: &str, data: &str) -> (App, Handle<Svg>, Handle<Svg>, Vec<u8>) {
+    let dir = Dir::default();
+    insert_file(&dir, file, data);
+    let mut app = asset_app(&dir);
+    let original = load_asset(&mut app, file).expect("valid SVG");
+
+    let svg = app
+        .world()
+        .resource::<Assets<Svg>>()
+        .get(&original)
+        .unwrap();
+    let mesh = &app
+        .world()
+        .resource::<Assets<Mesh>>()
+        .get(&svg.mesh)
+        .unwrap();
+    let bytes = svg.to_baked_bytes(mesh).expect("a tessellated mesh");
+    insert_file(&dir, "baked.svgb", bytes.clone());
+    let baked = load_asset(&mut app, "baked.svgb").expect("valid baked SVG");
+    (app, original, baked, bytes)
+}
+
+#[test]
+fn round_trip() {
+    for (file, data, has_frames) in [
+        (
+            "walk_cycle.svg",
+            include_str!("../assets/walk_cycle.svg"),
+            true,
+        ),
+        ("level.svg", include_str!("../assets/level.svg"), false),
+    ] {
+        let (app, original, baked, bytes) = bake(file, data);
+        let (svgs, meshes) = (
+            app.world().resource::<Assets<Svg>>(),
+            app.world().resource::<Assets<Mesh>>(),
+        );
+        let (original, baked) = (svgs.get(&original).unwrap(), svgs.get(&baked).unwrap());
+
+        assert_eq!(original.name, baked.name);
+        assert_eq!(original.view_box, baked.view_box);
+        assert_same_paths(original, baked);
+        assert_same_mesh(
+            meshes.get(&original.mesh).unwrap(),
+            meshes.get(&baked.mesh).unwrap(),
+        );
+
+        assert_eq!(original.elements.len(), baked.elements.len());
+        for (a, b) in original.elements.iter().zip(&baked.elements) {
+            assert_eq!((&a.id, &a.tag, &a.classes), (&b.id, &b.tag, &b.classes));
+            assert_eq!((&a.data, a.bounds), (&b.data, b.bounds));
+        }
+
+        assert!(!original.elements.is_empty());
+        assert_eq!(original.frames.len() > 1, has_frames);
+        assert_eq!(original.frames.len(), baked.frames.len());
+        for (a, b) in original.frames.iter().zip(&baked.frames) {
+            assert_eq!(a.id, b.id);
+            assert_same_mesh(meshes.get(&a.mesh).unwrap(), meshes.get(&b.mesh).unwrap());
+        }
+
+        // decoding without the asset server gives the same svg and mesh
+        let (decoded, mesh) = Svg::from_baked_bytes(&bytes).expect("valid baked SVG");
+        assert_same_paths(original, &decoded);
+        assert_same_mesh(meshes.get(&original.mesh).unwrap(), &mesh);
+        assert_eq!(
+            original.frames.iter().map(|f| &f.id).collect::<Vec<_>>(),
+            decoded.frames.iter().map(|f| &f.id).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn invalid_data() {
+    let (_, _, _, bytes) = bake("walk_cycle.svg", include_str!("../assets/walk_cycle.svg"));
+
+    // every prefix of the header and a sample of the longer ones
+    let lengths = (0..128).chain((128..bytes.len()).step_by(bytes.len() / 256 + 1));
+    for len in lengths.chain([bytes.len() - 1]) {
+        assert!(
+            Svg::from_baked_bytes(&bytes[..len]).is_err(),
+            "truncated to {len}"
+        );
+    }
+
+    // bytes of a simple generator, once after the header and once instead of all data
+    let mut state = 0x2545_f491_u32;
+    let mut garbage = || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as u8
+    };
+    for _ in 0..20 {
+        let mut corrupted = bytes[..6].to_vec();
+        corrupted.extend((0..bytes.len()).map(|_| garbage()));
+        assert!(Svg::from_baked_bytes(&corrupted).is_err());
+        let corrupted = (0..bytes.len()).map(|_| garbage()).collect::<Vec<_>>();
+        assert!(Svg::from_baked_bytes(&corrupted).is_err());
+    }
+
+    // an index past the last vertex
+    let (svg, mesh) = Svg::from_baked_bytes(&bytes).expect("valid baked SVG");
+    let vertex_count = mesh.count_vertices();
+    let name = 4 + svg.name.len();
+    let header = 4 + 2 + name + 8 + 32 + 1 + 4;
+    let first_index = header + 4 + vertex_count * 32 + 1 + 4;
+    let mut corrupted = bytes.clone();
+    corrupted[first_index..first_index + 4].copy_from_slice(&(vertex_count as u32).to_le_bytes());
+    assert!(matches!(
+        Svg::from_baked_bytes(&corrupted),
+        Err(BakedSvgError::InvalidData(_))
+    ));
+
+    // the baked loader fails instead of panicking
+    let dir = Dir::default();
+    common::insert_file(&dir, "corrupted.svgb", corrupted);
+    let mut app = common::asset_app(&dir);
+    assert!(load_asset(&mut app, "corrupted.svgb").is_none());
+}
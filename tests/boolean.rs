@@ -0,0 +1,150 @@
+//! Boolean operations on the filled areas of [`Svg`]s.
+
+use bevy::{
+    color::Color,
+    math::{Rect, Vec2},
+};
+use bevy_svg::{geometry::BooleanOp, prelude::*};
+
+mod common;
+
+use common::load;
+
+const TOLERANCE: f32 = 0.01;
+
+fn square(min: Vec2, size: f32) -> Svg {
+    Svg::builder()
+        .rect(
+            Rect::from_corners(min, min + size),
+            SvgStyle::fill(Color::WHITE),
+        )
+        .with_id("square")
+        .build()
+}
+
+fn circle(center: Vec2, radius: f32) -> Svg {
+    Svg::builder()
+        .circle(center, radius, SvgStyle::fill(Color::BLACK))
+        .with_id("circle")
+        .build()
+}
+
+fn assert_area(svg: &Svg, expected: f32) {
+    let area = svg.area(TOLERANCE);
+    assert!((area - expected).abs() < 0.5, "{area} != {expected}");
+}
+
+#[test]
+fn operations() {
+    let a = square(Vec2::ZERO, 100.0);
+    let b = square(Vec2::splat(50.0), 100.0);
+
+    let union = a.boolean(BooleanOp::Union, &b, TOLERANCE);
+    assert_area(&union, 17500.0);
+    assert!(union.contains_point(Vec2::new(140.0, 140.0), TOLERANCE));
+
+    let intersection = a.boolean(BooleanOp::Intersection, &b, TOLERANCE);
+    assert_area(&intersection, 2500.0);
+    assert_eq!(
+        intersection.bounds(),
+        Some(Rect::new(50.0, 50.0, 100.0, 100.0))
+    );
+
+    let difference = a.boolean(BooleanOp::Difference, &b, TOLERANCE);
+    assert_area(&difference, 7500.0);
+    assert!(!difference.contains_point(Vec2::new(75.0, 75.0), TOLERANCE));
+
+    let xor = a.boolean(BooleanOp::Xor, &b, TOLERANCE);
+    assert_area(&xor, 15000.0);
+    assert!(!xor.contains_point(Vec2::new(75.0, 75.0), TOLERANCE));
+    assert!(xor.contains_point(Vec2::new(25.0, 25.0), TOLERANCE));
+    assert!(xor.contains_point(Vec2::new(125.0, 125.0), TOLERANCE));
+}
+
+#[test]
+fn shared_edges() {
+    let a = square(Vec2::ZERO, 100.0);
+    let b = square(Vec2::new(100.0, 0.0), 100.0);
+    assert_area(&a.boolean(BooleanOp::Union, &b, TOLERANCE), 20000.0);
+    assert_area(&a.boolean(BooleanOp::Intersection, &b, TOLERANCE), 0.0);
+    assert_area(&a.boolean(BooleanOp::Difference, &a, TOLERANCE), 0.0);
+    assert_area(&a.boolean(BooleanOp::Intersection, &a, TOLERANCE), 10000.0);
+}
+
+#[test]
+fn curves_and_holes() {
+    // a frame with a hole, carved by a circle which overlaps the hole
+    let level = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <path id="frame" fill="green" fill-rule="evenodd"
+                d="M0 0 H100 V100 H0 Z M40 40 H60 V60 H40 Z"/>
+        </svg>"#,
+    );
+    let explosion = circle(Vec2::new(30.0, 50.0), 20.0);
+    let carved = level.boolean(BooleanOp::Difference, &explosion, TOLERANCE);
+
+    // the circle covers 1074 of the frame, the rest of it is inside the hole
+    assert_area(&carved, 8526.0);
+    assert!(!carved.contains_point(Vec2::new(15.0, 50.0), TOLERANCE));
+    assert!(!carved.contains_point(Vec2::new(50.0, 50.0), TOLERANCE));
+    assert!(carved.contains_point(Vec2::new(5.0, 5.0), TOLERANCE));
+    assert!(carved.contains_point(Vec2::new(70.0, 50.0), TOLERANCE));
+
+    // the path keeps its style
+    assert_eq!(carved.paths.len(), 1);
+    assert_eq!(carved.paths[0].id, "frame");
+    assert_eq!(carved.paths[0].color, level.paths[0].color);
+    assert!(carved.tessellate().count_vertices() > 0);
+
+    // carving again at the same spot removes nothing more
+    let twice = carved.boolean(BooleanOp::Difference, &explosion, TOLERANCE);
+    assert_area(&twice, carved.area(TOLERANCE));
+}
+
+#[test]
+fn elements_and_strokes() {
+    let level = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect id="sky" width="100" height="50" fill="blue"/>
+            <rect id="ground" y="50" width="100" height="50" fill="brown"
+                stroke="black" stroke-width="2"/>
+        </svg>"#,
+    );
+    let explosion = square(Vec2::new(40.0, 40.0), 20.0);
+    let carved =
+        level.element_boolean("ground", BooleanOp::Difference, &explosion, None, TOLERANCE);
+
+    assert_area(&carved, 5000.0 + 5000.0 - 200.0);
+    assert_eq!(carved.element_area("sky", TOLERANCE), 5000.0);
+    assert!(carved.contains_point(Vec2::new(50.0, 45.0), TOLERANCE));
+    assert!(!carved.element_contains_point("ground", Vec2::new(50.0, 55.0), TOLERANCE));
+    // the top edge of the stroke is cut where the square covers it
+    let stroke_length = level.element_path_length("ground");
+    let carved_length = carved
+        .paths
+        .iter()
+        .filter(|path| path.is_stroke)
+        .map(|path| bevy_svg::geometry::PathMeasure::new(&path.transformed_segments()).length())
+        .sum::<f32>();
+    assert!((stroke_length - carved_length - 20.0).abs() < 1e-3);
+
+    // with an `other_id` only that element of the other svg cuts
+    let two = square(Vec2::ZERO, 10.0);
+    let carved = level.element_boolean(
+        "ground",
+        BooleanOp::Difference,
+        &two,
+        Some("missing"),
+        TOLERANCE,
+    );
+    assert_area(&carved, 10000.0);
+
+    // union adds the other paths outside of the subject
+    let merged = level.boolean(
+        BooleanOp::Union,
+        &square(Vec2::new(90.0, 90.0), 20.0),
+        TOLERANCE,
+    );
+    assert_eq!(merged.paths.len(), level.paths.len() + 1);
+    assert_area(&merged, 10000.0 + 300.0);
+}
@@ -0,0 +1,158 @@
+//! Helpers shared by the integration tests, not every test uses all of them.
+#![allow(dead_code)]
+
+use std::{collections::HashMap, path::Path, thread, time::Duration};
+
+use bevy::{
+    asset::{
+        AssetPlugin, LoadState, UntypedAssetId,
+        io::{
+            AssetSourceBuilder, AssetSourceId,
+            memory::{Dir, MemoryAssetReader},
+        },
+    },
+    color::{ColorToPacked, Srgba},
+    mesh::{Indices, VertexAttributeValues},
+    prelude::*,
+};
+use bevy_svg::prelude::*;
+
+/// Parses an SVG document, panics if it is invalid.
+pub fn load(data: &str) -> Svg {
+    Svg::from_bytes(data.as_bytes(), "test.svg", None::<&str>).expect("valid SVG")
+}
+
+/// Asserts that both [`Svg`]s have the same size and paths.
+pub fn assert_same_paths(a: &Svg, b: &Svg) {
+    assert_eq!(a.size, b.size);
+    assert_eq!(a.paths.len(), b.paths.len());
+    for (a, b) in a.paths.iter().zip(&b.paths) {
+        assert_eq!(a.id, b.id);
+        assert_eq!(a.ancestor_ids, b.ancestor_ids);
+        assert_eq!(a.is_stroke, b.is_stroke);
+        assert_eq!(
+            Srgba::from(a.color).to_u8_array(),
+            Srgba::from(b.color).to_u8_array()
+        );
+        assert_eq!(a.transformed_segments(), b.transformed_segments());
+    }
+}
+
+/// Creates an app with the [`Svg`] asset loaders, which loads assets from the files in `dir`.
+pub fn asset_app(dir: &Dir) -> App {
+    let reader = MemoryAssetReader { root: dir.clone() };
+    let mut app = App::new();
+    app.register_asset_source(
+        AssetSourceId::Default,
+        AssetSourceBuilder::new(move || Box::new(reader.clone())),
+    )
+    .add_plugins((MinimalPlugins, AssetPlugin::default()))
+    .init_asset::<Mesh>()
+    .init_asset::<Svg>()
+    .register_asset_loader(SvgAssetLoader)
+    .register_asset_loader(BakedSvgLoader);
+    app
+}
+
+/// Writes a file into `dir`.
+pub fn insert_file(dir: &Dir, path: &str, bytes: impl Into<Vec<u8>>) {
+    dir.insert_asset(Path::new(path), bytes.into());
+}
+
+/// Loads an [`Svg`] with the asset server of the app and waits until it is loaded, returns
+/// `None` if loading failed.
+pub fn load_asset(app: &mut App, path: &str) -> Option<Handle<Svg>> {
+    let handle = app.world().resource::<AssetServer>().load(path.to_string());
+    wait_for_asset(app, handle)
+}
+
+/// Updates the app until the [`Svg`] is loaded, returns `None` if loading failed.
+pub fn wait_for_asset(app: &mut App, handle: Handle<Svg>) -> Option<Handle<Svg>> {
+    let id = UntypedAssetId::from(&handle);
+    for _ in 0..1000 {
+        app.update();
+        match app.world().resource::<AssetServer>().load_state(id) {
+            LoadState::Loaded => return Some(handle),
+            LoadState::Failed(_) => return None,
+            _ => thread::sleep(Duration::from_millis(1)),
+        }
+    }
+    panic!(
+        "loading {} timed out",
+        handle.path().map(ToString::to_string).unwrap_or_default()
+    );
+}
+
+/// A vertex of a triangle, with its position and normal.
+#[derive(Clone, Copy, Debug)]
+pub struct MeshVertex {
+    pub position: Vec3,
+    pub normal: Vec3,
+}
+
+/// The triangles of a triangle list [`Mesh`] with positions, normals and `u32` indices.
+pub fn mesh_triangles(mesh: &Mesh) -> Vec<[MeshVertex; 3]> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("the mesh has no positions");
+    };
+    let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+    else {
+        panic!("the mesh has no normals");
+    };
+    let Some(Indices::U32(indices)) = mesh.indices() else {
+        panic!("the mesh has no u32 indices");
+    };
+    indices
+        .chunks_exact(3)
+        .map(|triangle| {
+            [0, 1, 2].map(|corner| {
+                let idx = triangle[corner] as usize;
+                MeshVertex {
+                    position: Vec3::from(positions[idx]),
+                    normal: Vec3::from(normals[idx]),
+                }
+            })
+        })
+        .collect()
+}
+
+/// Asserts that the mesh is closed and consistently wound: vertices at the same position are
+/// merged, then every edge is used by exactly two triangles, once in each direction.
+pub fn assert_closed(mesh: &Mesh) {
+    let key = |position: Vec3| (position * 1e3).round().as_ivec3().to_array();
+    // for every edge, the number of triangles using it and the sum of their directions
+    let mut edges = HashMap::<_, (u32, i32)>::new();
+    for triangle in mesh_triangles(mesh) {
+        let corners = triangle.map(|vertex| key(vertex.position));
+        for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+            let (a, b) = (corners[a], corners[b]);
+            assert_ne!(a, b, "degenerate triangle {corners:?}");
+            let (edge, direction) = if a < b { ((a, b), 1) } else { ((b, a), -1) };
+            let entry = edges.entry(edge).or_default();
+            entry.0 += 1;
+            entry.1 += direction;
+        }
+    }
+    for (edge, (count, direction)) in edges {
+        assert_eq!(count, 2, "edge {edge:?} is used by {count} triangles");
+        assert_eq!(
+            direction, 0,
+            "edge {edge:?} is used twice in the same direction"
+        );
+    }
+}
+
+/// Asserts that the winding of every triangle agrees with its vertex normals.
+pub fn assert_winding_matches_normals(mesh: &Mesh) {
+    for [a, b, c] in mesh_triangles(mesh) {
+        let face = (b.position - a.position).cross(c.position - a.position);
+        let normal = a.normal + b.normal + c.normal;
+        assert!(
+            face.dot(normal) > 0.0,
+            "triangle {:?} faces away from its normals",
+            [a.position, b.position, c.position]
+        );
+    }
+}
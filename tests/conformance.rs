@@ -0,0 +1,372 @@
+//! Renders a corpus of SVGs through the loader and tessellator, rasterizes the meshes on the CPU
+//! and compares them with reference images.
+//!
+//! The corpus has the layout of the resvg test suite, `<category>/<feature>/<name>.svg` with the
+//! reference `<name>.png` next to it. `tests/conformance` is used unless the
+//! `BEVY_SVG_CONFORMANCE_DIR` environment variable points to another corpus, e.g. the `tests`
+//! directory of a resvg checkout. A report with the results per feature is written to
+//! `conformance/report.md` in the temporary directory of the integration tests, together with
+//! the renders of all failing tests.
+//!
+//! The tests listed in `expected-pass.txt` in the corpus must pass, so regressions fail this
+//! test. Set `BEVY_SVG_CONFORMANCE_BLESS=1` to write the passing tests to that file instead.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    asset::RenderAssetUsages,
+    color::{ColorToComponents, ColorToPacked, LinearRgba, Srgba},
+    image::{CompressedImageFormats, Image, ImageSampler, ImageType},
+    math::{UVec2, Vec2, Vec4},
+    mesh::{Mesh, VertexAttributeValues},
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_svg::prelude::*;
+
+/// Samples per pixel along each axis, like multisampling.
+const SUPERSAMPLING: u32 = 4;
+/// The difference of a channel, out of 255, above which two pixels differ.
+const CHANNEL_TOLERANCE: f32 = 40.0;
+/// The fraction of differing pixels above which a test fails.
+const PIXEL_TOLERANCE: f32 = 0.01;
+
+/// The outcome of one test.
+enum Outcome {
+    Pass,
+    /// The fraction of differing pixels.
+    Mismatch(f32),
+    /// The SVG could not be loaded or rendered.
+    Error(String),
+}
+
+#[test]
+fn conformance() {
+    let corpus = std::env::var_os("BEVY_SVG_CONFORMANCE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/conformance"));
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("conformance");
+    let _ = fs::remove_dir_all(&out_dir);
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut tests = Vec::new();
+    collect_tests(&corpus, &mut tests);
+    tests.sort();
+    assert!(!tests.is_empty(), "no tests in {}", corpus.display());
+
+    // isolated groups reach the `todo!()` in `Svg::from_tree`, the panic is reported as an error
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let results = tests
+        .iter()
+        .map(|svg_path| {
+            let name = svg_path
+                .strip_prefix(&corpus)
+                .unwrap()
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/");
+            let outcome = run_test(svg_path, &out_dir.join(format!("{name}.png")));
+            (name, outcome)
+        })
+        .collect::<Vec<_>>();
+    panic::set_hook(hook);
+
+    let report_path = out_dir.join("report.md");
+    fs::write(&report_path, report(&results)).unwrap();
+
+    let passed = results
+        .iter()
+        .filter(|(_, outcome)| matches!(outcome, Outcome::Pass))
+        .map(|(name, _)| name.as_str())
+        .collect::<BTreeSet<_>>();
+    let expected_path = corpus.join("expected-pass.txt");
+    if std::env::var_os("BEVY_SVG_CONFORMANCE_BLESS").is_some() {
+        let list = passed
+            .iter()
+            .fold(String::new(), |list, name| list + name + "\n");
+        fs::write(&expected_path, list).unwrap();
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&expected_path) else {
+        return;
+    };
+    let regressions = expected
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !passed.contains(name))
+        .collect::<Vec<_>>();
+    assert!(
+        regressions.is_empty(),
+        "tests which are expected to pass failed: {regressions:?}, see {}",
+        report_path.display()
+    );
+}
+
+/// Collects all SVGs with a reference image.
+fn collect_tests(dir: &Path, tests: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_tests(&path, tests);
+        } else if path.extension().is_some_and(|ext| ext == "svg")
+            && path.with_extension("png").exists()
+        {
+            tests.push(path);
+        }
+    }
+}
+
+/// Renders an SVG and compares it with its reference, the render is saved if they differ.
+fn run_test(svg_path: &Path, render_path: &Path) -> Outcome {
+    let reference = match load_reference(&svg_path.with_extension("png")) {
+        Ok(reference) => reference,
+        Err(err) => return Outcome::Error(err),
+    };
+    let size = reference.size;
+    let render = panic::catch_unwind(AssertUnwindSafe(|| {
+        let svg = Svg::from_bytes(&fs::read(svg_path).unwrap(), svg_path, None::<&str>)
+            .map_err(|err| err.to_string())?;
+        Ok::<_, String>(rasterize_mesh(&svg.tessellate(), svg.size, size))
+    }));
+    let pixels = match render {
+        Ok(Ok(pixels)) => pixels,
+        Ok(Err(err)) => return Outcome::Error(err),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            return Outcome::Error(format!("panicked: {message}"));
+        }
+    };
+
+    let differing = pixels
+        .iter()
+        .zip(&reference.data)
+        .filter(|&(&pixel, reference)| {
+            let reference = Srgba::from_u8_array(*reference);
+            let difference = (over_white(pixel) - over_white(reference)).abs() * 255.0;
+            difference.max_element() > CHANNEL_TOLERANCE
+        })
+        .count() as f32
+        / pixels.len() as f32;
+    if differing <= PIXEL_TOLERANCE {
+        return Outcome::Pass;
+    }
+    save_render(&pixels, size, render_path);
+    Outcome::Mismatch(differing)
+}
+
+struct Reference {
+    size: UVec2,
+    data: Vec<[u8; 4]>,
+}
+
+fn load_reference(path: &Path) -> Result<Reference, String> {
+    let bytes = fs::read(path).map_err(|err| err.to_string())?;
+    let image = Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .map_err(|err| err.to_string())?;
+    let image = image
+        .convert(TextureFormat::Rgba8UnormSrgb)
+        .ok_or("unsupported reference format")?;
+    Ok(Reference {
+        size: image.size(),
+        data: image
+            .data
+            .unwrap_or_default()
+            .chunks_exact(4)
+            .map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]])
+            .collect(),
+    })
+}
+
+/// Composites a color over white, as the RGB part of a premultiplied color.
+fn over_white(color: Srgba) -> Vec4 {
+    let rgb = Vec4::new(color.red, color.green, color.blue, 0.0);
+    rgb * color.alpha + Vec4::splat(1.0 - color.alpha).with_w(0.0)
+}
+
+/// Rasterizes the triangles of a mesh of an [`Svg`] of the given size, like the GPU does with
+/// multisampling and alpha blending. The colors are blended in sRGB space, like browsers do.
+fn rasterize_mesh(mesh: &Mesh, svg_size: Vec2, size: UVec2) -> Vec<Srgba> {
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return vec![Srgba::NONE; (size.x * size.y) as usize];
+    };
+    let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR)
+    else {
+        return vec![Srgba::NONE; (size.x * size.y) as usize];
+    };
+    let indices = mesh
+        .indices()
+        .map(|indices| indices.iter().collect::<Vec<_>>());
+    let indices = indices.unwrap_or_else(|| (0..positions.len()).collect());
+
+    let samples = size * SUPERSAMPLING;
+    let scale = samples.as_vec2() / svg_size.max(Vec2::splat(f32::EPSILON));
+    // the mesh is flipped vertically
+    let points = positions
+        .iter()
+        .map(|[x, y, _]| Vec2::new(*x, -*y) * scale)
+        .collect::<Vec<_>>();
+    let colors = colors
+        .iter()
+        .map(|color| {
+            let color = Srgba::from(LinearRgba::from_f32_array(*color));
+            Vec4::new(color.red, color.green, color.blue, 1.0) * color.alpha
+        })
+        .collect::<Vec<_>>();
+
+    // premultiplied colors of every sample
+    let mut buffer = vec![Vec4::ZERO; (samples.x * samples.y) as usize];
+    for triangle in indices.chunks_exact(3) {
+        let [mut a, mut b, c] = [triangle[0], triangle[1], triangle[2]];
+        if edge(points[a], points[b], points[c]) < 0.0 {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let [pa, pb, pc] = [points[a], points[b], points[c]];
+        let area = edge(pa, pb, pc);
+        if area <= 0.0 {
+            continue;
+        }
+        let min = pa.min(pb).min(pc).floor().max(Vec2::ZERO).as_uvec2();
+        let max = pa.max(pb).max(pc).ceil().as_uvec2().min(samples);
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let weights = [edge(pb, pc, p), edge(pc, pa, p), edge(pa, pb, p)];
+                let owns = [(pb, pc), (pc, pa), (pa, pb)].into_iter().zip(weights).all(
+                    |((from, to), weight)| weight > 0.0 || weight == 0.0 && owns_edge(from, to),
+                );
+                if !owns {
+                    continue;
+                }
+                let color =
+                    (colors[a] * weights[0] + colors[b] * weights[1] + colors[c] * weights[2])
+                        / area;
+                let sample = &mut buffer[(y * samples.x + x) as usize];
+                *sample = color + *sample * (1.0 - color.w);
+            }
+        }
+    }
+
+    // resolve the samples of every pixel
+    let mut pixels = Vec::with_capacity((size.x * size.y) as usize);
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let mut sum = Vec4::ZERO;
+            for sy in 0..SUPERSAMPLING {
+                for sx in 0..SUPERSAMPLING {
+                    let sample = UVec2::new(x, y) * SUPERSAMPLING + UVec2::new(sx, sy);
+                    sum += buffer[(sample.y * samples.x + sample.x) as usize];
+                }
+            }
+            let color = sum / (SUPERSAMPLING * SUPERSAMPLING) as f32;
+            pixels.push(if color.w > 0.0 {
+                Srgba::new(
+                    color.x / color.w,
+                    color.y / color.w,
+                    color.z / color.w,
+                    color.w,
+                )
+            } else {
+                Srgba::NONE
+            });
+        }
+    }
+    pixels
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`, positive if `p` is to the right of the
+/// edge from `a` to `b` with the y-axis pointing down.
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b - a).perp_dot(p - a)
+}
+
+/// Samples exactly on an edge belong to only one of the two triangles which share it.
+fn owns_edge(from: Vec2, to: Vec2) -> bool {
+    let direction = to - from;
+    direction.y > 0.0 || direction.y == 0.0 && direction.x < 0.0
+}
+
+fn save_render(pixels: &[Srgba], size: UVec2, path: &Path) {
+    let image = Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        pixels
+            .iter()
+            .flat_map(|pixel| pixel.to_u8_array())
+            .collect(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).unwrap();
+    }
+    if let Ok(image) = image.try_into_dynamic() {
+        let _ = image.save(path);
+    }
+}
+
+/// A markdown report with the results per feature, the parent directory of a test.
+fn report(results: &[(String, Outcome)]) -> String {
+    let mut features = BTreeMap::<&str, Vec<(&str, &Outcome)>>::new();
+    for (name, outcome) in results {
+        let (feature, test) = name.rsplit_once('/').unwrap_or(("", name));
+        features.entry(feature).or_default().push((test, outcome));
+    }
+
+    let passed = results
+        .iter()
+        .filter(|(_, outcome)| matches!(outcome, Outcome::Pass))
+        .count();
+    let mut report = format!(
+        "# SVG conformance\n\n{passed} of {} tests passed.\n\n| Feature | Passed | Failed tests |\n|---|---|---|\n",
+        results.len()
+    );
+    for (feature, tests) in &features {
+        let passed = tests
+            .iter()
+            .filter(|(_, outcome)| matches!(outcome, Outcome::Pass))
+            .count();
+        let failed = tests
+            .iter()
+            .filter_map(|(test, outcome)| match outcome {
+                Outcome::Pass => None,
+                Outcome::Mismatch(differing) => {
+                    Some(format!("{test} ({:.1}% differ)", differing * 100.0))
+                }
+                Outcome::Error(err) => Some(format!("{test} ({})", err.replace('|', "/"))),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            report,
+            "| {feature} | {passed}/{} | {failed} |",
+            tests.len()
+        );
+    }
+    report
+}
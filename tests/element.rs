@@ -0,0 +1,178 @@
+//! Parsing and matching of [`SvgSelector`]s.
+
+use bevy::{math::Affine2, prelude::*};
+use bevy_svg::prelude::*;
+
+mod common;
+
+use common::load;
+
+fn select(svg: &Svg, selector: &str) -> Vec<String> {
+    svg.select(selector)
+        .map(|element| element.id.clone())
+        .collect()
+}
+
+#[test]
+fn parse() {
+    let selector =
+        SvgSelector::parse(" circle#enemy_1.spawn.élite[data-kind='goblin'][data-boss] ");
+    assert_eq!(selector.tag.as_deref(), Some("circle"));
+    assert_eq!(selector.id.as_deref(), Some("enemy_1"));
+    assert_eq!(selector.classes, ["spawn", "élite"]);
+    assert_eq!(
+        selector.data,
+        [
+            ("kind".to_string(), Some("goblin".to_string())),
+            ("boss".to_string(), None)
+        ]
+    );
+    assert_eq!(selector.unsupported, None);
+
+    // multi-byte characters are skipped as a whole
+    let selector = SvgSelector::parse("ü#ö.ß€[data-ä=ë]");
+    assert_eq!(selector.tag.as_deref(), Some("ü"));
+    assert_eq!(selector.id.as_deref(), Some("ö"));
+    assert_eq!(selector.classes, ["ß€"]);
+    assert_eq!(selector.data, [("ä".to_string(), Some("ë".to_string()))]);
+}
+
+#[test]
+fn combinators() {
+    for (selector, unsupported) in [
+        ("g circle", " circle"),
+        ("g > circle", " > circle"),
+        ("g>circle", ">circle"),
+        (".spawn ~ .spawn", " ~ .spawn"),
+        ("rect, circle", ", circle"),
+        ("#a+#b", "+#b"),
+    ] {
+        assert_eq!(
+            SvgSelector::parse(selector).unsupported.as_deref(),
+            Some(unsupported),
+            "{selector}"
+        );
+    }
+    assert_eq!(SvgSelector::parse("g circle").tag.as_deref(), Some("g"));
+}
+
+#[test]
+fn matches() {
+    let svg = load(include_str!("../assets/level.svg"));
+    assert_eq!(select(&svg, "#wall"), ["wall"]);
+    assert_eq!(select(&svg, "rect.obstacle"), ["wall"]);
+    assert_eq!(
+        select(&svg, "circle.spawn[data-kind]"),
+        ["enemy_1", "enemy_2", "enemy_3"]
+    );
+    assert_eq!(select(&svg, "[data-kind=troll]"), ["enemy_3"]);
+    assert!(select(&svg, "svg circle").is_empty());
+    assert!(select(&svg, "rect, circle").is_empty());
+}
+
+#[test]
+fn builder() {
+    let style = SvgStyle::fill(Color::WHITE).with_stroke(Color::BLACK, 2.0);
+    let svg = Svg::builder()
+        .rect(Rect::new(0.0, 0.0, 100.0, 60.0), style)
+        .with_id("body")
+        .group(|group| {
+            group
+                .circle(Vec2::new(25.0, 60.0), 10.0, style)
+                .with_id("front")
+                .circle(Vec2::new(75.0, 60.0), 10.0, style)
+                .with_transform(Affine2::from_translation(Vec2::new(5.0, 0.0)))
+                .with_id("back")
+        })
+        .with_id("wheels")
+        .polygon(&[Vec2::ZERO, Vec2::X, Vec2::Y], style)
+        .build();
+
+    // in document order, only shapes and groups with an `id`
+    let elements = svg
+        .elements
+        .iter()
+        .map(|element| (element.id.as_str(), element.tag.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        elements,
+        [
+            ("body", "rect"),
+            ("wheels", "g"),
+            ("front", "circle"),
+            ("back", "circle")
+        ]
+    );
+
+    // the bounds follow the transform and do not include the stroke
+    let bounds = |id| svg.element(id).and_then(|element| element.bounds).unwrap();
+    let near = |a: Rect, b: Rect| (a.min - b.min).length() + (a.max - b.max).length() < 1e-3;
+    assert!(near(bounds("body"), Rect::new(0.0, 0.0, 100.0, 60.0)));
+    assert!(near(bounds("back"), Rect::new(70.0, 50.0, 90.0, 70.0)));
+    assert!(near(bounds("wheels"), Rect::new(15.0, 50.0, 90.0, 70.0)));
+
+    assert_eq!(select(&svg, "circle"), ["front", "back"]);
+    assert_eq!(select(&svg, "g#wheels"), ["wheels"]);
+}
+
+#[test]
+fn builder_nested_groups() {
+    let style = SvgStyle::fill(Color::WHITE);
+    // both groups start at the same path
+    let svg = Svg::builder()
+        .group(|outer| {
+            outer
+                .group(|inner| {
+                    inner
+                        .rect(Rect::new(0.0, 0.0, 10.0, 10.0), style)
+                        .rect(Rect::new(10.0, 0.0, 20.0, 10.0), style)
+                })
+                .with_id("inner")
+                .circle(Vec2::new(50.0, 50.0), 10.0, style)
+        })
+        .with_id("outer")
+        .build();
+
+    let ids = svg
+        .elements
+        .iter()
+        .map(|element| element.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["outer", "inner"]);
+    assert_eq!(svg.element_paths("inner").count(), 2);
+    assert_eq!(svg.element_paths("outer").count(), 3);
+
+    let bounds = |id| svg.element(id).and_then(|element| element.bounds).unwrap();
+    let near = |a: Rect, b: Rect| (a.min - b.min).length() + (a.max - b.max).length() < 1e-3;
+    assert!(near(bounds("inner"), Rect::new(0.0, 0.0, 20.0, 10.0)));
+    assert!(near(bounds("outer"), Rect::new(0.0, 0.0, 60.0, 60.0)));
+}
+
+#[test]
+fn bounds_without_id() {
+    let svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <circle class="coin" cx="20" cy="30" r="5" fill="gold"/>
+            <g class="coins" transform="translate(50 0)">
+                <circle data-value="5" cx="10" cy="10" r="2" fill="gold"/>
+                <circle class="coin" cx="30" cy="10" r="2" fill="gold"/>
+            </g>
+            <rect id="wall" class="coin" x="0" y="90" width="100" height="10"/>
+        </svg>"#,
+    );
+    let coins = svg.select(".coin").collect::<Vec<_>>();
+    assert_eq!(coins.len(), 3);
+    // the y-axis of the position is flipped
+    assert_eq!(coins[0].bounds, Some(Rect::new(15.0, 25.0, 25.0, 35.0)));
+    assert_eq!(coins[0].position(), Some(Vec2::new(20.0, -30.0)));
+    assert_eq!(coins[1].position(), Some(Vec2::new(80.0, -10.0)));
+    assert_eq!(coins[2].position(), Some(Vec2::new(50.0, -95.0)));
+    // the generated ids used to look up the bounds are not visible
+    assert_eq!(coins[0].id, "");
+    assert!(svg.paths.iter().all(|path| !path.id.starts_with("__")));
+
+    let group = svg.select("g.coins").next().unwrap();
+    assert_eq!(group.bounds, Some(Rect::new(58.0, 8.0, 82.0, 12.0)));
+    let value = svg.select("[data-value=5]").next().unwrap();
+    assert_eq!(value.position(), Some(Vec2::new(60.0, -10.0)));
+}
@@ -0,0 +1,154 @@
+//! Round trips of [`Svg`]s through [`Svg::to_svg_string`], [`SvgAssetSaver`] and the SVG loader.
+
+use bevy::{
+    asset::{
+        ErasedLoadedAsset, LoadedAsset,
+        io::memory::Dir,
+        saver::{AssetSaver, SavedAsset},
+    },
+    math::{Rect, Vec2},
+    prelude::*,
+    tasks::block_on,
+};
+use bevy_svg::prelude::*;
+use svgtypes::ViewBox;
+
+mod common;
+
+use common::{assert_same_paths, asset_app, insert_file, load, wait_for_asset};
+
+#[test]
+fn round_trip_file() {
+    let original = load(include_str!("../assets/route.svg"));
+    let reloaded = load(&original.to_svg_string());
+    assert_same_paths(&original, &reloaded);
+}
+
+#[test]
+fn round_trip_elements() {
+    let original = load(include_str!("../assets/level.svg"));
+    let reloaded = load(&original.to_svg_string());
+    assert_same_paths(&original, &reloaded);
+    // every element is written as `<path>`, so only the tag changes
+    assert_eq!(original.elements.len(), reloaded.elements.len());
+    for (a, b) in original.elements.iter().zip(&reloaded.elements) {
+        assert_eq!((&a.id, &a.classes, &a.data), (&b.id, &b.classes, &b.data));
+        assert_eq!(a.bounds, b.bounds);
+    }
+}
+
+#[test]
+fn round_trip_view_box() {
+    let original = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="200" viewBox="-50 -50 100 100">
+            <circle id="dot" cx="0" cy="0" r="25" fill="red"/>
+            <path id="line" d="M-50 -50 L50 50" stroke="black"/>
+        </svg>"#,
+    );
+    assert_eq!(original.view_box, ViewBox::new(-50.0, -50.0, 100.0, 100.0));
+    // the paths are scaled from the view box to the size
+    assert_eq!(
+        original.element("dot").unwrap().bounds,
+        Some(Rect::new(50.0, 50.0, 150.0, 150.0))
+    );
+
+    let written = original.to_svg_string();
+    assert!(
+        written.contains(r#"viewBox="-50 -50 100 100""#),
+        "{written}"
+    );
+    let reloaded = load(&written);
+    assert_eq!(reloaded.view_box, original.view_box);
+    assert_same_paths(&original, &reloaded);
+    assert_eq!(
+        reloaded.element("dot").unwrap().bounds,
+        original.element("dot").unwrap().bounds
+    );
+}
+
+#[test]
+fn round_trip_stretched_view_box() {
+    let original = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="10 20 50 50" preserveAspectRatio="none">
+            <rect id="box" x="10" y="20" width="25" height="25" fill="red"/>
+        </svg>"#,
+    );
+    let written = original.to_svg_string();
+    assert!(
+        written.contains(r#"preserveAspectRatio="none""#),
+        "{written}"
+    );
+    let reloaded = load(&written);
+    assert_same_paths(&original, &reloaded);
+    assert_eq!(
+        reloaded.element("box").unwrap().bounds,
+        Some(Rect::new(0.0, 0.0, 100.0, 50.0))
+    );
+}
+
+#[test]
+fn round_trip_builder() {
+    let original = Svg::builder()
+        .size(100.0, 100.0)
+        .rect(
+            Rect::new(10.0, 10.0, 90.0, 50.0),
+            SvgStyle::fill(bevy::color::Color::srgba_u8(255, 0, 0, 128))
+                .with_stroke(bevy::color::Color::BLACK, 2.0),
+        )
+        .group(|group| {
+            group
+                .circle(
+                    Vec2::new(30.0, 70.0),
+                    10.0,
+                    SvgStyle::fill(bevy::color::Color::WHITE),
+                )
+                .with_id("wheel")
+        })
+        .with_id("wheels")
+        .with_transform(bevy::math::Affine2::from_translation(Vec2::new(5.0, 0.0)))
+        .build();
+    let reloaded = load(&original.to_svg_string());
+    assert_same_paths(&original, &reloaded);
+}
+
+#[test]
+fn round_trip_saver() {
+    let mut original = load(include_str!("../assets/level.svg"));
+    original.antialias = true;
+    original.layer_spacing = 0.5;
+
+    let loaded = ErasedLoadedAsset::from(LoadedAsset::from(original.clone()));
+    let saved = SavedAsset::from_loaded(&loaded).expect("an Svg");
+    let mut bytes = Vec::new();
+    let settings = block_on(SvgAssetSaver.save(&mut bytes, saved, &())).expect("saved SVG");
+
+    let dir = Dir::default();
+    insert_file(&dir, "saved.svg", bytes);
+    let mut app = asset_app(&dir);
+    let handle = app.world().resource::<AssetServer>().load_with_settings(
+        "saved.svg",
+        move |s: &mut SvgLoaderSettings| {
+            *s = settings.clone();
+        },
+    );
+    let handle = wait_for_asset(&mut app, handle).expect("valid SVG");
+
+    let reloaded = app.world().resource::<Assets<Svg>>().get(&handle).unwrap();
+    assert_same_paths(&original, reloaded);
+    assert_eq!(reloaded.antialias, original.antialias);
+    assert_eq!(reloaded.layer_spacing, original.layer_spacing);
+    assert_eq!(original.elements.len(), reloaded.elements.len());
+    for (a, b) in original.elements.iter().zip(&reloaded.elements) {
+        assert_eq!((&a.id, &a.classes, &a.data), (&b.id, &b.classes, &b.data));
+    }
+    // the loader tessellates the mesh, with the anti-aliasing fringe of the saved settings
+    let mesh = app
+        .world()
+        .resource::<Assets<Mesh>>()
+        .get(&reloaded.mesh)
+        .unwrap();
+    assert_eq!(
+        mesh.count_vertices(),
+        original.tessellate().count_vertices()
+    );
+}
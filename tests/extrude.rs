@@ -0,0 +1,88 @@
+//! Extrusion of filled areas into solid meshes.
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+mod common;
+
+use common::{assert_closed, assert_winding_matches_normals, load, mesh_triangles};
+
+/// A 40 by 20 rectangle, its center is at (30, -20) in the coordinate system of the mesh.
+const RECTANGLE: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+    <rect id="box" x="10" y="10" width="40" height="20" fill="red"/>
+</svg>"#;
+
+#[test]
+fn rectangle() {
+    let mesh = load(RECTANGLE).extrude(&SvgExtrusion::new(10.0));
+    // two caps with 4 vertices and 2 triangles, and sides with a vertex per corner and face
+    assert_eq!(mesh.count_vertices(), 2 * 4 + 2 * 4 * 2);
+    let triangles = mesh_triangles(&mesh);
+    assert_eq!(triangles.len(), 2 * 2 + 4 * 2);
+    assert_closed(&mesh);
+    assert_winding_matches_normals(&mesh);
+
+    let center = Vec3::new(30.0, -20.0, 0.0);
+    for vertex in triangles.iter().flatten() {
+        assert_eq!(vertex.position.z.abs(), 5.0);
+        let outwards = vertex.position - center;
+        if vertex.normal.z != 0.0 {
+            // a cap
+            assert_eq!(vertex.normal, Vec3::Z * vertex.position.z.signum());
+        } else {
+            // a side, its normal is perpendicular to the face
+            assert!(vertex.normal.is_normalized());
+            assert!(vertex.normal.dot(outwards) > 0.0);
+            assert!(
+                vertex.normal.x == 0.0 || vertex.normal.y == 0.0,
+                "{:?}",
+                vertex.normal
+            );
+        }
+    }
+}
+
+#[test]
+fn bevel() {
+    let extrusion = SvgExtrusion::new(10.0).with_bevel(2.0, 2.0, 3);
+    let mesh = load(RECTANGLE).extrude(&extrusion);
+    // the sides have two bevels with 4 rings each, every ring has a vertex per corner and face
+    assert_eq!(mesh.count_vertices(), 2 * 4 + 8 * 4 * 2);
+    assert_eq!(mesh_triangles(&mesh).len(), 2 * 2 + 7 * 4 * 2);
+    assert_closed(&mesh);
+    assert_winding_matches_normals(&mesh);
+
+    let center = Vec3::new(30.0, -20.0, 0.0);
+    for vertex in mesh_triangles(&mesh).iter().flatten() {
+        assert!(vertex.normal.dot(vertex.position - center) > 0.0);
+        // the caps are moved inwards by the bevel
+        if vertex.normal.z.abs() == 1.0 {
+            let offset = (vertex.position - center).abs();
+            assert!(offset.x <= 18.0 + 1e-4 && offset.y <= 8.0 + 1e-4);
+        }
+    }
+}
+
+#[test]
+fn hole() {
+    let svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <path id="frame" d="M0 0H30V30H0Z M10 10V20H20V10Z" fill="red"/>
+            <rect x="50" y="50" width="10" height="10" fill="blue"/>
+        </svg>"#,
+    );
+    let mesh = svg.extrude_element("frame", &SvgExtrusion::new(4.0));
+    // the caps of the frame and the sides of both rings
+    assert_eq!(mesh.count_vertices(), 2 * 8 + 2 * 2 * 4 * 2);
+    assert_closed(&mesh);
+    assert_winding_matches_normals(&mesh);
+
+    // the sides of the hole face its center
+    let center = Vec3::new(15.0, -15.0, 0.0);
+    for vertex in mesh_triangles(&mesh).iter().flatten() {
+        let offset = (vertex.position - center).truncate();
+        if vertex.normal.z == 0.0 && offset.abs().max_element() < 6.0 {
+            assert!(vertex.normal.truncate().dot(offset) < 0.0);
+        }
+    }
+}
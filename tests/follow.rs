@@ -0,0 +1,115 @@
+//! Paths as curves and following them.
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+mod common;
+
+use common::load;
+
+const EPSILON: f32 = 0.1;
+
+fn assert_near(a: Vec2, b: Vec2) {
+    assert!(a.distance(b) < EPSILON, "{a} != {b}");
+}
+
+const PATHS: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+    <path id="line" d="M10 20 H110" stroke="black"/>
+    <path id="arc" d="M0 50 A50 50 0 0 1 100 50" stroke="black" fill="none"/>
+    <g transform="translate(100 0)">
+        <path id="moved" d="M10 20 H110" transform="scale(0.5)" stroke="black"/>
+    </g>
+</svg>"#;
+
+#[test]
+fn line() {
+    let curve = load(PATHS).path_curve("line").unwrap();
+    assert!((curve.length() - 100.0).abs() < EPSILON);
+    // the y-axis is flipped compared to the SVG
+    let (point, tangent) = curve.sample_with_tangent(50.0);
+    assert_near(point, Vec2::new(60.0, -20.0));
+    assert_near(tangent, Vec2::X);
+    assert_near(curve.normal(50.0), Vec2::Y);
+    // the distance is clamped to the path
+    assert_near(curve.sample_with_tangent(-10.0).0, Vec2::new(10.0, -20.0));
+    assert_near(curve.sample_with_tangent(200.0).0, Vec2::new(110.0, -20.0));
+    assert_eq!(curve.domain(), Interval::new(0.0, curve.length()).unwrap());
+}
+
+#[test]
+fn half_circle() {
+    let curve = load(PATHS).path_curve("arc").unwrap();
+    assert!((curve.length() - 50.0 * PI).abs() < EPSILON);
+    // the arc goes over the top of the SVG, which is up in the mesh
+    let center = Vec2::new(50.0, -50.0);
+    for step in 0..=8 {
+        let distance = curve.length() * step as f32 / 8.0;
+        let (point, tangent) = curve.sample_with_tangent(distance);
+        let angle = PI - PI * step as f32 / 8.0;
+        assert_near(point, center + Vec2::from_angle(angle) * 50.0);
+        assert_near(tangent, Vec2::from_angle(angle - PI / 2.0));
+    }
+    assert_near(
+        curve.sample(curve.length() / 2.0).unwrap(),
+        Vec2::new(50.0, 0.0),
+    );
+}
+
+#[test]
+fn transforms() {
+    let svg = load(PATHS);
+    // the transforms of the element and its group are applied
+    let curve = svg.path_curve("moved").unwrap();
+    assert!((curve.length() - 50.0).abs() < EPSILON);
+    assert_near(curve.sample_with_tangent(0.0).0, Vec2::new(105.0, -10.0));
+    assert_near(curve.sample_with_tangent(50.0).0, Vec2::new(155.0, -10.0));
+    assert!(svg.path_curve("missing").is_none());
+}
+
+fn follower(repeat: PathRepeat) -> FollowSvgPath {
+    FollowSvgPath::new(Entity::PLACEHOLDER, "line", 1.0).with_repeat(repeat)
+}
+
+#[test]
+fn once() {
+    let mut follow = follower(PathRepeat::Once);
+    follow.advance(60.0, 100.0);
+    assert_eq!(follow.distance, 60.0);
+    follow.advance(60.0, 100.0);
+    assert_eq!(follow.distance, 100.0);
+    follow.advance(-150.0, 100.0);
+    assert_eq!(follow.distance, 0.0);
+}
+
+#[test]
+fn wrap_loop() {
+    let mut follow = follower(PathRepeat::Loop);
+    follow.advance(60.0, 100.0);
+    follow.advance(60.0, 100.0);
+    assert_eq!(follow.distance, 20.0);
+    follow.advance(250.0, 100.0);
+    assert_eq!(follow.distance, 70.0);
+    // backwards past the start
+    follow.advance(-90.0, 100.0);
+    assert_eq!(follow.distance, 80.0);
+    // a path without length
+    follow.advance(10.0, 0.0);
+    assert_eq!(follow.distance, 0.0);
+}
+
+#[test]
+fn wrap_ping_pong() {
+    let mut follow = follower(PathRepeat::PingPong);
+    follow.advance(60.0, 100.0);
+    follow.advance(60.0, 100.0);
+    assert_eq!(follow.distance, 80.0);
+    // it keeps going backwards until it reaches the start
+    follow.advance(50.0, 100.0);
+    assert_eq!(follow.distance, 30.0);
+    follow.advance(40.0, 100.0);
+    assert_eq!(follow.distance, 10.0);
+    follow.advance(30.0, 100.0);
+    assert_eq!(follow.distance, 40.0);
+}
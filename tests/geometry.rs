@@ -0,0 +1,142 @@
+//! Geometry queries on the paths of an [`Svg`].
+
+use bevy::{
+    math::{Rect, Vec2, Vec3},
+    transform::components::Transform,
+};
+use bevy_svg::prelude::*;
+
+mod common;
+
+use common::load;
+
+const TOLERANCE: f32 = 0.01;
+
+fn shapes() -> Svg {
+    load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+            <path id="frame" fill-rule="evenodd" d="M10 10 H90 V90 H10 Z M30 30 H70 V70 H30 Z"/>
+            <circle id="dot" cx="150" cy="50" r="20"/>
+            <line id="line" x1="100" y1="95" x2="200" y2="95" stroke="black" stroke-width="4"/>
+        </svg>"#,
+    )
+}
+
+fn assert_near(a: Vec2, b: Vec2) {
+    assert!(a.distance(b) < 0.05, "{a} != {b}");
+}
+
+#[test]
+fn bounds() {
+    let svg = shapes();
+    assert_eq!(
+        svg.element_bounds("frame"),
+        Some(Rect::new(10.0, 10.0, 90.0, 90.0))
+    );
+    // the extrema of the curves, not the control points
+    let dot = svg.element_bounds("dot").unwrap();
+    assert_near(dot.min, Vec2::new(130.0, 30.0));
+    assert_near(dot.max, Vec2::new(170.0, 70.0));
+    // half the stroke width
+    assert_eq!(
+        svg.element_bounds("line"),
+        Some(Rect::new(98.0, 93.0, 202.0, 97.0))
+    );
+    assert_eq!(svg.bounds(), Some(Rect::new(10.0, 10.0, 202.0, 97.0)));
+    assert_eq!(svg.element_bounds("missing"), None);
+}
+
+#[test]
+fn contains_point() {
+    let svg = shapes();
+    assert!(svg.element_contains_point("frame", Vec2::new(20.0, 20.0), TOLERANCE));
+    // the hole of the even-odd fill
+    assert!(!svg.element_contains_point("frame", Vec2::new(50.0, 50.0), TOLERANCE));
+    assert!(svg.element_contains_point("dot", Vec2::new(150.0, 50.0), TOLERANCE));
+    assert!(!svg.element_contains_point("dot", Vec2::new(131.0, 31.0), TOLERANCE));
+    assert!(svg.element_contains_point("line", Vec2::new(150.0, 96.5), TOLERANCE));
+    assert!(!svg.element_contains_point("line", Vec2::new(150.0, 98.0), TOLERANCE));
+    assert!(svg.contains_point(Vec2::new(150.0, 96.5), TOLERANCE));
+    assert!(!svg.contains_point(Vec2::new(110.0, 10.0), TOLERANCE));
+}
+
+#[test]
+fn nearest_point() {
+    let svg = shapes();
+    assert_near(
+        svg.element_nearest_point("frame", Vec2::new(50.0, 45.0), TOLERANCE)
+            .unwrap(),
+        Vec2::new(50.0, 30.0),
+    );
+    assert_near(
+        svg.element_nearest_point("dot", Vec2::new(150.0, 0.0), TOLERANCE)
+            .unwrap(),
+        Vec2::new(150.0, 30.0),
+    );
+    assert_near(
+        svg.nearest_point(Vec2::new(120.0, 99.0), TOLERANCE)
+            .unwrap(),
+        Vec2::new(120.0, 95.0),
+    );
+    assert_eq!(
+        svg.element_nearest_point("missing", Vec2::ZERO, TOLERANCE),
+        None
+    );
+}
+
+#[test]
+fn length_area_and_centroid() {
+    let svg = shapes();
+    assert!((svg.element_path_length("frame") - 480.0).abs() < 1e-3);
+    assert!((svg.element_path_length("dot") - 40.0 * std::f32::consts::PI).abs() < 0.1);
+    assert!((svg.element_path_length("line") - 100.0).abs() < 1e-3);
+
+    assert!((svg.element_area("frame", TOLERANCE) - 4800.0).abs() < 1e-2);
+    assert!((svg.element_area("dot", TOLERANCE) - 400.0 * std::f32::consts::PI).abs() < 1.0);
+    assert_eq!(svg.element_area("line", TOLERANCE), 0.0);
+
+    assert_near(
+        svg.element_centroid("frame", TOLERANCE).unwrap(),
+        Vec2::new(50.0, 50.0),
+    );
+    assert_near(
+        svg.element_centroid("dot", TOLERANCE).unwrap(),
+        Vec2::new(150.0, 50.0),
+    );
+    assert_eq!(svg.element_centroid("line", TOLERANCE), None);
+    // weighted by area
+    let area = 4800.0 + 400.0 * std::f32::consts::PI;
+    let expected = (Vec2::new(50.0, 50.0) * 4800.0
+        + Vec2::new(150.0, 50.0) * 400.0 * std::f32::consts::PI)
+        / area;
+    assert_near(svg.centroid(TOLERANCE).unwrap(), expected);
+}
+
+#[test]
+fn world_space() {
+    let svg = shapes();
+    let transform = Transform::from_xyz(5.0, 10.0, 1.0).with_scale(Vec3::splat(2.0));
+    // the top left corner is at the translation, the y-axis points up
+    assert_eq!(
+        svg.svg_to_world(Vec2::ZERO, Origin::TopLeft, &transform),
+        Vec3::new(5.0, 10.0, 1.0)
+    );
+    assert_eq!(
+        svg.svg_to_world(Vec2::new(10.0, 20.0), Origin::TopLeft, &transform),
+        Vec3::new(25.0, -30.0, 1.0)
+    );
+    // the center of the svg is at the translation
+    assert_eq!(
+        svg.svg_to_world(Vec2::new(100.0, 50.0), Origin::Center, &transform),
+        Vec3::new(5.0, 10.0, 1.0)
+    );
+    for origin in [
+        Origin::TopLeft,
+        Origin::Center,
+        Origin::Custom((0.25, 0.75)),
+    ] {
+        let point = Vec2::new(30.0, 70.0);
+        let world = svg.svg_to_world(point, origin, &transform);
+        assert_near(svg.world_to_svg(world, origin, &transform), point);
+    }
+}
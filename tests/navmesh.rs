@@ -0,0 +1,133 @@
+//! Building navigation meshes and finding paths on them.
+
+use bevy::math::Vec2;
+use bevy_svg::geometry::{NavMesh, NavMeshOptions, SvgPolygon};
+
+mod common;
+
+use common::load;
+
+/// A counter-clockwise rectangle.
+fn rect(min: Vec2, max: Vec2) -> SvgPolygon {
+    SvgPolygon {
+        exterior: vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)],
+        holes: Vec::new(),
+    }
+}
+
+fn square(min: f32, max: f32) -> SvgPolygon {
+    rect(Vec2::splat(min), Vec2::splat(max))
+}
+
+fn length(path: &[Vec2]) -> f32 {
+    path.windows(2).map(|pair| pair[0].distance(pair[1])).sum()
+}
+
+/// Returns `true` if the segment passes through the inside of the axis-aligned rectangle.
+fn crosses(a: Vec2, b: Vec2, min: Vec2, max: Vec2) -> bool {
+    (1..100).any(|i| {
+        let point = a.lerp(b, i as f32 / 100.0);
+        point.cmpgt(min + 0.01).all() && point.cmplt(max - 0.01).all()
+    })
+}
+
+#[test]
+fn straight_line() {
+    let navmesh = NavMesh::new(&[square(0.0, 100.0)], &[], 0.0);
+    assert!(!navmesh.triangles.is_empty());
+    let (start, end) = (Vec2::new(10.0, 20.0), Vec2::new(90.0, 70.0));
+    // nothing is in the way, so every corner is pulled out of the path
+    assert_eq!(navmesh.find_path(start, end), Some(vec![start, end]));
+    let path = navmesh.find_path(start, start).unwrap();
+    assert_eq!((path.first(), path.last()), (Some(&start), Some(&start)));
+}
+
+#[test]
+fn around_obstacle() {
+    let navmesh = NavMesh::new(&[square(0.0, 100.0)], &[square(40.0, 60.0)], 0.0);
+    assert!(navmesh.contains(Vec2::new(20.0, 50.0)));
+    assert!(!navmesh.contains(Vec2::new(50.0, 50.0)));
+    assert!(navmesh.triangle_at(Vec2::new(50.0, 50.0)).is_none());
+
+    let (start, end) = (Vec2::new(20.0, 50.0), Vec2::new(80.0, 50.0));
+    let path = navmesh.find_path(start, end).unwrap();
+    // the path goes around two corners on the same side of the obstacle
+    assert_eq!(path.len(), 4, "{path:?}");
+    assert_eq!((path[0], path[3]), (start, end));
+    let y = path[1].y;
+    assert!(y == 40.0 || y == 60.0, "{path:?}");
+    assert_eq!(path[1..3], [Vec2::new(40.0, y), Vec2::new(60.0, y)]);
+    for pair in path.windows(2) {
+        assert!(!crosses(
+            pair[0],
+            pair[1],
+            Vec2::splat(40.0),
+            Vec2::splat(60.0)
+        ));
+    }
+    assert!((length(&path) - (2.0 * 20.0f32.hypot(10.0) + 20.0)).abs() < 0.01);
+}
+
+#[test]
+fn blocked_points() {
+    let navmesh = NavMesh::new(&[square(0.0, 100.0)], &[square(40.0, 60.0)], 0.0);
+    let outside = Vec2::new(20.0, 20.0);
+    // start or goal inside the obstacle or outside of the walkable area
+    assert_eq!(navmesh.find_path(Vec2::new(50.0, 50.0), outside), None);
+    assert_eq!(navmesh.find_path(outside, Vec2::new(50.0, 50.0)), None);
+    assert_eq!(navmesh.find_path(outside, Vec2::new(150.0, 50.0)), None);
+
+    // two walkable areas which are not connected
+    let navmesh = NavMesh::new(&[square(0.0, 10.0), square(20.0, 30.0)], &[], 0.0);
+    assert_eq!(navmesh.find_path(Vec2::splat(5.0), Vec2::splat(25.0)), None);
+}
+
+#[test]
+fn agent_radius() {
+    // two rooms connected by a corridor which is 10 units wide
+    let walkable = [
+        rect(Vec2::ZERO, Vec2::new(40.0, 40.0)),
+        rect(Vec2::new(40.0, 15.0), Vec2::new(60.0, 25.0)),
+        rect(Vec2::new(60.0, 0.0), Vec2::new(100.0, 40.0)),
+    ];
+    // one room with the corridor as a gap between two obstacles
+    let room = [rect(Vec2::ZERO, Vec2::new(100.0, 40.0))];
+    let obstacles = [
+        rect(Vec2::new(40.0, 0.0), Vec2::new(60.0, 15.0)),
+        rect(Vec2::new(40.0, 25.0), Vec2::new(60.0, 40.0)),
+    ];
+    let (start, end) = (Vec2::new(20.0, 20.0), Vec2::new(80.0, 20.0));
+
+    let navmesh = NavMesh::new(&room, &obstacles, 2.0);
+    assert_eq!(navmesh.find_path(start, end), Some(vec![start, end]));
+    // the walls are moved inwards by the radius
+    assert!(!navmesh.contains(Vec2::new(20.0, 1.0)));
+    assert!(!navmesh.contains(Vec2::new(50.0, 16.0)));
+    assert!(navmesh.contains(Vec2::new(50.0, 18.0)));
+
+    assert_eq!(
+        NavMesh::new(&room, &obstacles, 6.0).find_path(start, end),
+        None
+    );
+    assert!(NavMesh::new(&walkable[1..2], &[], 2.0).contains(Vec2::new(50.0, 20.0)));
+    assert!(NavMesh::new(&walkable[1..2], &[], 6.0).triangles.is_empty());
+}
+
+#[test]
+fn svg_navmesh() {
+    let svg = load(include_str!("../assets/level.svg"));
+    let navmesh = svg.navmesh(&["floor"], &["wall"], NavMeshOptions::default());
+    // the mesh has a flipped y-axis, the wall covers x 180..220 and y 80..320 of the SVG
+    let (start, end) = (Vec2::new(80.0, -200.0), Vec2::new(300.0, -200.0));
+    assert!(!navmesh.contains(Vec2::new(200.0, -200.0)));
+    let path = navmesh.find_path(start, end).unwrap();
+    assert!(path.len() > 2);
+    for pair in path.windows(2) {
+        assert!(!crosses(
+            pair[0],
+            pair[1],
+            Vec2::new(180.0, -320.0),
+            Vec2::new(220.0, -80.0)
+        ));
+    }
+}
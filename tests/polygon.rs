@@ -0,0 +1,191 @@
+//! Polygons from rings, their convex decomposition and collision shapes.
+
+use bevy::math::Vec2;
+use bevy_svg::geometry::{ColliderShape, MIN_TOLERANCE, SvgPolygon, flatten, signed_area};
+use lyon_tessellation::FillRule;
+
+mod common;
+
+use common::load;
+
+/// A counter-clockwise square.
+fn square(min: f32, max: f32) -> Vec<Vec2> {
+    vec![
+        Vec2::new(min, min),
+        Vec2::new(max, min),
+        Vec2::new(max, max),
+        Vec2::new(min, max),
+    ]
+}
+
+fn reversed(mut ring: Vec<Vec2>) -> Vec<Vec2> {
+    ring.reverse();
+    ring
+}
+
+fn areas(polygons: &[SvgPolygon]) -> Vec<f32> {
+    polygons.iter().map(SvgPolygon::area).collect()
+}
+
+#[test]
+fn hole() {
+    let same_direction = || vec![square(0.0, 10.0), square(2.0, 8.0)];
+    let opposite = || vec![square(0.0, 10.0), reversed(square(2.0, 8.0))];
+
+    let polygons = SvgPolygon::from_rings(same_direction(), FillRule::EvenOdd);
+    assert_eq!(areas(&polygons), [64.0]);
+    assert_eq!(polygons[0].holes.len(), 1);
+    // holes are clockwise
+    assert!(signed_area(&polygons[0].holes[0]) < 0.0);
+    assert!(!polygons[0].contains(Vec2::splat(5.0)));
+    assert!(polygons[0].contains(Vec2::splat(1.0)));
+
+    // with non-zero, an inner ring is only a hole if it goes the other way around
+    assert_eq!(
+        areas(&SvgPolygon::from_rings(same_direction(), FillRule::NonZero)),
+        [100.0]
+    );
+    for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+        assert_eq!(
+            areas(&SvgPolygon::from_rings(opposite(), fill_rule)),
+            [64.0]
+        );
+    }
+    // clockwise exteriors are turned around
+    let polygons = SvgPolygon::from_rings(vec![reversed(square(0.0, 10.0))], FillRule::NonZero);
+    assert!(signed_area(&polygons[0].exterior) > 0.0);
+}
+
+#[test]
+fn islands() {
+    // an island inside the hole of a polygon, and another polygon next to it
+    let rings = vec![
+        square(20.0, 30.0),
+        square(4.0, 6.0),
+        square(0.0, 10.0),
+        square(2.0, 8.0),
+    ];
+    let mut polygons = SvgPolygon::from_rings(rings, FillRule::EvenOdd);
+    polygons.sort_by(|a, b| b.area().total_cmp(&a.area()));
+    assert_eq!(areas(&polygons), [100.0, 64.0, 4.0]);
+    assert_eq!(
+        polygons.iter().map(|p| p.holes.len()).collect::<Vec<_>>(),
+        [0, 1, 0]
+    );
+    assert_eq!(polygons[1].exterior, square(0.0, 10.0));
+    assert!(polygons[2].contains(Vec2::splat(5.0)));
+}
+
+#[test]
+fn touching_rings() {
+    // the first point of the hole is on the exterior
+    let hole = vec![
+        Vec2::new(0.0, 5.0),
+        Vec2::new(5.0, 3.0),
+        Vec2::new(5.0, 7.0),
+    ];
+    let polygons = SvgPolygon::from_rings(vec![square(0.0, 10.0), hole], FillRule::EvenOdd);
+    assert_eq!(polygons.len(), 1);
+    assert_eq!(areas(&polygons), [90.0]);
+}
+
+fn assert_convex_cover(polygon: &SvgPolygon) {
+    let parts = polygon.convex_decomposition();
+    assert!(!parts.is_empty());
+    for part in &parts {
+        let len = part.len();
+        assert!(len >= 3);
+        for i in 0..len {
+            let (a, b, c) = (part[i], part[(i + 1) % len], part[(i + 2) % len]);
+            assert!((b - a).perp_dot(c - b) >= -1e-4, "{part:?} is not convex");
+        }
+    }
+    // counter-clockwise pieces which together have the area of the polygon
+    let area = parts.iter().map(|part| signed_area(part)).sum::<f32>();
+    assert!(
+        (area - polygon.area()).abs() < 1e-3,
+        "{area} != {}",
+        polygon.area()
+    );
+}
+
+#[test]
+fn convex_decomposition() {
+    let l_shape = SvgPolygon {
+        exterior: vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 4.0),
+            Vec2::new(4.0, 4.0),
+            Vec2::new(4.0, 10.0),
+            Vec2::new(0.0, 10.0),
+        ],
+        holes: Vec::new(),
+    };
+    assert_convex_cover(&l_shape);
+    assert!(l_shape.convex_decomposition().len() >= 2);
+
+    let square = SvgPolygon::from_rings(vec![square(0.0, 10.0)], FillRule::NonZero);
+    assert_eq!(square[0].convex_decomposition().len(), 1);
+
+    let with_hole = SvgPolygon::from_rings(
+        vec![self::square(0.0, 10.0), self::square(2.0, 8.0)],
+        FillRule::EvenOdd,
+    );
+    assert_convex_cover(&with_hole[0]);
+}
+
+#[test]
+fn collider_shapes() {
+    let svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <path id="frame" fill-rule="evenodd" d="M0 0 H10 V10 H0 Z M2 2 H8 V8 H2 Z"/>
+            <circle id="ring" cx="50" cy="50" r="10" fill="none" stroke="black"/>
+            <rect id="box" x="20" y="20" width="10" height="10" fill="red" stroke="black"/>
+        </svg>"#,
+    );
+    let shapes = svg.collider_shapes(0.1);
+    // a filled and stroked element only gets its polygon
+    assert_eq!(shapes.len(), 3);
+
+    let ColliderShape::Polygon(frame) = &svg.element_collider_shapes("frame", 0.1)[0] else {
+        panic!("a polygon");
+    };
+    assert_eq!(frame.holes.len(), 1);
+    assert!((frame.area() - 64.0).abs() < 1e-3);
+    // the y-axis is flipped
+    assert!(frame.contains(Vec2::new(1.0, -1.0)));
+    assert!(!frame.contains(Vec2::new(5.0, -5.0)));
+    assert_convex_cover(frame);
+
+    let ring = svg.element_collider_shapes("ring", 0.1);
+    let [ColliderShape::Polyline { points, closed }] = ring.as_slice() else {
+        panic!("a polyline");
+    };
+    assert!(*closed);
+    for point in points {
+        assert!((point.distance(Vec2::new(50.0, -50.0)) - 10.0).abs() < 0.11);
+    }
+    assert!(ring[0].convex_decomposition().is_empty());
+
+    let ColliderShape::Polygon(rect) = &svg.element_collider_shapes("box", 0.1)[0] else {
+        panic!("a polygon");
+    };
+    assert!((rect.area() - 100.0).abs() < 1e-3);
+}
+
+#[test]
+fn tolerance() {
+    let svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <circle cx="50" cy="50" r="10"/>
+        </svg>"#,
+    );
+    let segments = svg.paths[0].transformed_segments();
+    let count = |tolerance| flatten(&segments, tolerance)[0].points.len();
+    // a tolerance below the minimum is clamped instead of looping forever
+    for tolerance in [0.0, -1.0, f32::NAN] {
+        assert_eq!(count(tolerance), count(MIN_TOLERANCE));
+    }
+    assert!(count(0.01) < count(MIN_TOLERANCE));
+}
@@ -0,0 +1,84 @@
+//! Lathe and sweep meshes from the profiles of an [`Svg`].
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_svg::prelude::*;
+
+mod common;
+
+use common::{assert_closed, assert_winding_matches_normals, load, mesh_triangles};
+
+/// A 10 by 30 rectangle as profile, and a circle with a radius of 50 around (0, 0) as path.
+const PROFILES: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+    <rect id="profile" x="10" y="0" width="10" height="30" fill="red"/>
+    <circle id="path" cx="0" cy="0" r="50" fill="none" stroke="black"/>
+</svg>"#;
+
+#[test]
+fn lathe() {
+    let svg = load(PROFILES);
+    let mesh = svg
+        .lathe("profile", &SvgLathe::new(0.0).with_segments(8))
+        .unwrap();
+    // 9 copies of the profile, every corner has a vertex for each of its edges, the start of
+    // the closed profile is repeated
+    assert_eq!(mesh.count_vertices(), 9 * 5 * 2);
+    assert_eq!(mesh_triangles(&mesh).len(), 8 * 4 * 2);
+    assert_closed(&mesh);
+    assert_winding_matches_normals(&mesh);
+
+    // the normals point away from the center of the profile, which is revolved around the y-axis
+    for vertex in mesh_triangles(&mesh).iter().flatten() {
+        let radius = vertex.position.xz().length();
+        assert!((10.0 - 1e-3..=20.0 + 1e-3).contains(&radius));
+        let direction = vertex.position.xz() / radius;
+        let center = Vec3::new(direction.x * 15.0, -15.0, direction.y * 15.0);
+        assert!(vertex.normal.dot(vertex.position - center) > 0.0);
+    }
+
+    // half a turn is open at both ends
+    let half = svg
+        .lathe(
+            "profile",
+            &SvgLathe::new(0.0).with_segments(4).with_angle(PI),
+        )
+        .unwrap();
+    assert_eq!(mesh_triangles(&half).len(), 4 * 4 * 2);
+    assert!(
+        mesh_triangles(&half)
+            .iter()
+            .flatten()
+            .all(|vertex| vertex.position.z <= 1e-4)
+    );
+}
+
+#[test]
+fn sweep() {
+    let svg = load(PROFILES);
+    let mesh = svg
+        .sweep("profile", "path", &SvgSweep::default().with_segments(32))
+        .unwrap();
+    assert_eq!(mesh.count_vertices(), 33 * 5 * 2);
+    assert_eq!(mesh_triangles(&mesh).len(), 32 * 4 * 2);
+    // the path is closed, so the ends of the sweep meet
+    assert_closed(&mesh);
+    assert_winding_matches_normals(&mesh);
+
+    // the cross section is centered on the path and its normals point away from the path
+    for vertex in mesh_triangles(&mesh).iter().flatten() {
+        let center = (vertex.position.xy().normalize() * 50.0).extend(0.0);
+        let offset = vertex.position - center;
+        assert!(offset.xy().length() <= 5.0 + 0.1 && offset.z.abs() <= 15.0 + 1e-3);
+        assert!(vertex.normal.dot(offset) > 0.0);
+    }
+}
+
+#[test]
+fn missing_elements() {
+    let svg = load(PROFILES);
+    assert!(svg.lathe("missing", &SvgLathe::default()).is_none());
+    let sweep = SvgSweep::default();
+    assert!(svg.sweep("missing", "path", &sweep).is_none());
+    assert!(svg.sweep("profile", "missing", &sweep).is_none());
+}
@@ -0,0 +1,31 @@
+//! Rasterizing [`Svg`]s on the CPU.
+
+use bevy::prelude::*;
+
+mod common;
+
+use common::load;
+
+#[test]
+fn element_bounds() {
+    let svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <path id="hill" d="M0 60 Q20 20 40 60 Z" fill="black"/>
+            <rect id="box" x="60" y="60" width="20" height="20" fill="none" stroke="black" stroke-width="4"/>
+        </svg>"#,
+    );
+    // the control point is outside of the hill, which reaches up to y = 40
+    let image = svg.rasterize_element("hill", UVec2::new(40, 20)).unwrap();
+    let alpha = |x, y| image.get_color_at(x, y).unwrap().alpha();
+    assert!(alpha(20, 0) > 0.5);
+    assert!(alpha(1, 19) > 0.5 && alpha(38, 19) > 0.5);
+    assert_eq!(alpha(1, 1), 0.0);
+
+    // the bounds include the stroke
+    let image = svg.rasterize_element("box", UVec2::new(24, 24)).unwrap();
+    let alpha = |x, y| image.get_color_at(x, y).unwrap().alpha();
+    assert!(alpha(0, 12) > 0.5 && alpha(23, 12) > 0.5 && alpha(12, 0) > 0.5);
+    assert_eq!(alpha(12, 12), 0.0);
+
+    assert!(svg.rasterize_element("missing", UVec2::ONE).is_none());
+}
@@ -0,0 +1,199 @@
+//! Measuring paths and trimming strokes.
+
+use bevy::{
+    mesh::{Indices, VertexAttributeValues},
+    prelude::*,
+};
+use bevy_svg::{geometry::PathMeasure, prelude::*};
+use lyon_path::{PathEvent, math::point};
+
+mod common;
+
+use common::load;
+
+const EPSILON: f32 = 1e-3;
+
+fn assert_near(a: f32, b: f32) {
+    assert!((a - b).abs() < EPSILON, "{a} != {b}");
+}
+
+/// A horizontal stroke from `(0, 0)` to `(100, 0)`.
+fn line() -> Svg {
+    load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="10">
+            <path id="line" d="M0 0 H100" stroke="black" fill="none"/>
+        </svg>"#,
+    )
+}
+
+/// The start and end x of every sub-path.
+fn sub_paths(events: &[PathEvent]) -> Vec<(f32, f32)> {
+    events
+        .iter()
+        .filter_map(|event| match *event {
+            PathEvent::End { last, first, .. } => Some((first.x, last.x)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn trimmed(range: TrimRange) -> Vec<(f32, f32)> {
+    let svg = line();
+    let paths = StrokeTrim::new(range).apply(&svg.paths);
+    sub_paths(&paths[0].segments)
+}
+
+fn assert_sub_paths(a: &[(f32, f32)], b: &[(f32, f32)]) {
+    assert_eq!(a.len(), b.len(), "{a:?} != {b:?}");
+    for (a, b) in a.iter().zip(b) {
+        assert_near(a.0, b.0);
+        assert_near(a.1, b.1);
+    }
+}
+
+#[test]
+fn measure() {
+    let events = [
+        PathEvent::Begin {
+            at: point(0.0, 0.0),
+        },
+        PathEvent::Line {
+            from: point(0.0, 0.0),
+            to: point(30.0, 0.0),
+        },
+        PathEvent::Line {
+            from: point(30.0, 0.0),
+            to: point(30.0, 40.0),
+        },
+        PathEvent::End {
+            last: point(30.0, 40.0),
+            first: point(0.0, 0.0),
+            close: true,
+        },
+    ];
+    let measure = PathMeasure::new(&events);
+    assert!(!measure.is_empty());
+    // the closing line is measured as well
+    assert_near(measure.length(), 120.0);
+
+    let (position, tangent) = measure.sample(45.0).unwrap();
+    assert_near(position.x, 30.0);
+    assert_near(position.y, 15.0);
+    assert_near(tangent.y, 1.0);
+    // distances are clamped to the path
+    let (position, _) = measure.sample(-10.0).unwrap();
+    assert_near(position.x, 0.0);
+    let (position, _) = measure.sample(1000.0).unwrap();
+    assert_near(position.x, 0.0);
+
+    assert_sub_paths(
+        &sub_paths(&measure.sub_path_events(10.0, 20.0)),
+        &[(10.0, 20.0)],
+    );
+    assert!(measure.sub_path_events(20.0, 10.0).is_empty());
+
+    let curve = [
+        PathEvent::Begin {
+            at: point(0.0, 0.0),
+        },
+        PathEvent::Quadratic {
+            from: point(0.0, 0.0),
+            ctrl: point(50.0, 0.0),
+            to: point(100.0, 0.0),
+        },
+        PathEvent::End {
+            last: point(100.0, 0.0),
+            first: point(0.0, 0.0),
+            close: false,
+        },
+    ];
+    assert_near(PathMeasure::new(&curve).length(), 100.0);
+    assert!(PathMeasure::new(&[]).is_empty());
+}
+
+#[test]
+fn trim_range() {
+    assert_sub_paths(&trimmed(TrimRange::FULL), &[(0.0, 100.0)]);
+    assert_sub_paths(&trimmed(TrimRange::new(0.25, 0.5)), &[(25.0, 50.0)]);
+    assert!(trimmed(TrimRange::new(0.5, 0.5)).is_empty());
+    assert!(trimmed(TrimRange::new(0.6, 0.4)).is_empty());
+
+    // the offset moves the range and wraps it around the end of the path
+    assert_sub_paths(
+        &trimmed(TrimRange::new(0.2, 0.6).with_offset(0.2)),
+        &[(40.0, 80.0)],
+    );
+    assert_sub_paths(
+        &trimmed(TrimRange::new(0.2, 0.6).with_offset(0.6)),
+        &[(0.0, 20.0), (80.0, 100.0)],
+    );
+    assert_sub_paths(
+        &trimmed(TrimRange::new(0.2, 0.6).with_offset(1.3)),
+        &[(50.0, 90.0)],
+    );
+    assert_sub_paths(
+        &trimmed(TrimRange::new(0.2, 0.6).with_offset(-0.5)),
+        &[(0.0, 10.0), (70.0, 100.0)],
+    );
+    // a full range stays full with any offset
+    assert_sub_paths(&trimmed(TrimRange::FULL.with_offset(0.5)), &[(0.0, 100.0)]);
+}
+
+#[test]
+fn element_ranges() {
+    let svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="10">
+            <g id="group"><path id="a" d="M0 0 H100" stroke="black" fill="none"/></g>
+            <path id="b" d="M0 0 H100" stroke="black" fill="none"/>
+        </svg>"#,
+    );
+    let trim =
+        StrokeTrim::new(TrimRange::new(0.0, 0.5)).with_element("group", TrimRange::new(0.5, 1.0));
+    let paths = trim.apply(&svg.paths);
+    assert_sub_paths(&sub_paths(&paths[0].segments), &[(50.0, 100.0)]);
+    assert_sub_paths(&sub_paths(&paths[1].segments), &[(0.0, 50.0)]);
+}
+
+fn mesh_data(mesh: &Mesh) -> (&VertexAttributeValues, &VertexAttributeValues, Vec<usize>) {
+    (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION).unwrap(),
+        mesh.attribute(Mesh::ATTRIBUTE_COLOR).unwrap(),
+        mesh.indices()
+            .map(Indices::iter)
+            .into_iter()
+            .flatten()
+            .collect(),
+    )
+}
+
+#[test]
+fn trimmed_mesh() {
+    // fills and strokes in between each other, the fills are tessellated separately
+    let mut svg = load(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100">
+            <rect width="50" height="50" fill="red" stroke="blue" stroke-width="4"/>
+            <path id="line" d="M0 60 C30 90 60 30 100 60" stroke="green" fill="none"/>
+            <circle cx="70" cy="70" r="20" fill="yellow"/>
+            <path d="M0 90 H100" stroke="black" fill="none"/>
+        </svg>"#,
+    );
+    for antialias in [false, true] {
+        svg.antialias = antialias;
+        for trim in [
+            StrokeTrim::default(),
+            StrokeTrim::new(TrimRange::new(0.1, 0.7).with_offset(0.5)),
+            StrokeTrim::default().with_element("line", TrimRange::new(0.0, 0.0)),
+        ] {
+            let paths = trim.apply(&svg.paths);
+            let expected = Svg {
+                paths,
+                ..svg.clone()
+            }
+            .tessellate();
+            assert_eq!(
+                mesh_data(&svg.tessellate_trimmed(&trim)),
+                mesh_data(&expected)
+            );
+        }
+    }
+}